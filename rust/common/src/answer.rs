@@ -0,0 +1,184 @@
+use std::borrow::Cow;
+
+use crate::SolveError;
+
+/// The answer of a part. Answers compare by the value they show rather than by how they were
+/// built, so `Answer::from(1651u32) == Answer::from(1651i64)` and an answer read back as text
+/// from an answer file equals the number it was recorded from.
+#[derive(Debug, Clone)]
+pub enum Answer {
+    /// The part produced no answer, as opposed to an empty one
+    None,
+    Int(i128),
+    /// Decimal digits with an optional leading `-` of an integer beyond `i128`; see [`Answer::big_int`]
+    BigInt(String),
+    Text(String),
+    /// Multi-line ASCII art, one row per line, shown starting on its own line
+    Art(String),
+}
+
+impl Answer {
+    pub fn none() -> Self {
+        Answer::None
+    }
+
+    pub fn is_none(&self) -> bool {
+        matches!(self, Answer::None)
+    }
+
+    /// An integer from its decimal digits, for answers beyond `i128`; `None` when `digits` is not
+    /// an integer.
+    pub fn big_int(digits: &str) -> Option<Self> {
+        let (sign, magnitude) = match digits.strip_prefix('-') {
+            Some(magnitude) => ("-", magnitude),
+            None => ("", digits),
+        };
+        if magnitude.is_empty() || !magnitude.bytes().all(|b| b.is_ascii_digit()) {
+            return None;
+        }
+        let magnitude = magnitude.trim_start_matches('0');
+        if magnitude.is_empty() {
+            return Some(Answer::Int(0));
+        }
+        let digits = format!("{}{}", sign, magnitude);
+        Some(match digits.parse() {
+            Ok(value) => Answer::Int(value),
+            Err(_) => Answer::BigInt(digits),
+        })
+    }
+
+    /// ASCII art; trailing whitespace and surrounding blank lines are dropped.
+    pub fn art(text: impl AsRef<str>) -> Self {
+        let rows: Vec<&str> = text.as_ref().lines().map(str::trim_end).collect();
+        let first = rows
+            .iter()
+            .position(|row| !row.is_empty())
+            .unwrap_or(rows.len());
+        let last = rows
+            .iter()
+            .rposition(|row| !row.is_empty())
+            .map_or(first, |last| last + 1);
+        Answer::Art(rows[first..last].join("\n"))
+    }
+
+    /// The answer as shown, an empty string when there is none.
+    pub fn get_result(&self) -> String {
+        self.to_string()
+    }
+
+    /// What answers are compared by.
+    fn key(&self) -> Option<Cow<'_, str>> {
+        match self {
+            Answer::None => None,
+            Answer::Int(value) => Some(Cow::Owned(value.to_string())),
+            Answer::BigInt(digits) => Some(Cow::Borrowed(digits)),
+            Answer::Text(text) => Some(Cow::Borrowed(text.trim())),
+            Answer::Art(rows) => Some(Cow::Borrowed(rows)),
+        }
+    }
+}
+
+impl PartialEq for Answer {
+    fn eq(&self, other: &Self) -> bool {
+        self.key() == other.key()
+    }
+}
+
+impl Eq for Answer {}
+
+impl std::fmt::Display for Answer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Answer::None => Ok(()),
+            Answer::Int(value) => write!(f, "{}", value),
+            Answer::BigInt(digits) => f.write_str(digits),
+            Answer::Text(text) => f.write_str(text),
+            Answer::Art(rows) => write!(f, "\n{}", rows),
+        }
+    }
+}
+
+impl Into<Result<Answer, SolveError>> for Answer {
+    fn into(self) -> Result<Answer, SolveError> {
+        Ok(self)
+    }
+}
+
+impl From<String> for Answer {
+    fn from(value: String) -> Self {
+        Answer::Text(value)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(value: &str) -> Self {
+        Answer::Text(String::from(value))
+    }
+}
+
+macro_rules! from_numeric_to_answer {
+    ($type:ty, $value:ident => $answer:expr) => {
+        impl From<$type> for Answer {
+            fn from($value: $type) -> Self {
+                $answer
+            }
+        }
+        impl From<&$type> for Answer {
+            fn from(value: &$type) -> Self {
+                Answer::from(*value)
+            }
+        }
+        impl From<Option<$type>> for Answer {
+            fn from(value: Option<$type>) -> Self {
+                value.map_or(Answer::None, Answer::from)
+            }
+        }
+    };
+    ($($type:ty),*) => {
+        $(from_numeric_to_answer!($type, value => Answer::Int(value as i128));)*
+    };
+}
+from_numeric_to_answer!(usize, u64, u32, u16, u8, isize, i128, i64, i32, i16, i8);
+from_numeric_to_answer!(u128, value => match i128::try_from(value) {
+    Ok(value) => Answer::Int(value),
+    Err(_) => Answer::BigInt(value.to_string()),
+});
+from_numeric_to_answer!(f64, value => match value.fract() == 0.0 && value.abs() < 2f64.powi(53) {
+    true => Answer::Int(value as i128),
+    false => Answer::Text(value.to_string()),
+});
+from_numeric_to_answer!(f32, value => Answer::from(value as f64));
+
+#[cfg(test)]
+mod tests {
+    use super::Answer;
+
+    #[test]
+    fn compares_across_representations() {
+        assert_eq!(Answer::from(1651u32), Answer::from(1651i64));
+        assert_eq!(Answer::from(1651usize), Answer::from("1651"));
+        assert_eq!(Answer::from(24.0), Answer::from(24));
+        assert_ne!(Answer::from(1651), Answer::from(1652));
+        assert_eq!(Answer::big_int("-0012"), Some(Answer::from(-12)));
+        assert_eq!(
+            Answer::from(u128::MAX),
+            Answer::big_int(&u128::MAX.to_string()).unwrap()
+        );
+        assert!(matches!(Answer::from(u128::MAX), Answer::BigInt(_)));
+        assert_eq!(Answer::big_int("12a"), None);
+
+        assert_eq!(
+            Answer::art("\n#..#  \n####\n\n"),
+            Answer::from("#..#\n####")
+        );
+        assert_eq!(Answer::art("#..#\n####").get_result(), "\n#..#\n####");
+    }
+
+    #[test]
+    fn no_answer_is_not_empty() {
+        assert_eq!(Answer::from(None::<u32>), Answer::none());
+        assert_ne!(Answer::none(), Answer::from(""));
+        assert_eq!(Answer::none().get_result(), "");
+        assert!(Answer::from(Some(3u8)) == Answer::from(3));
+    }
+}
@@ -0,0 +1,104 @@
+use std::time::Duration;
+
+/// Why a part could not produce an answer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SolveError {
+    /// The input did not have the expected format; `line` and `column` are 1-based.
+    Parse {
+        line: usize,
+        column: usize,
+        text: String,
+        message: String,
+    },
+    /// The input is valid, but outside what the solution handles
+    Unsupported(String),
+    /// The solution gave up after its own time budget
+    Timeout(Duration),
+    /// A bug in the solution; an assumption about its own state did not hold
+    Internal(String),
+}
+
+impl SolveError {
+    /// A parse error for `fragment`, a slice of `input` (e.g. one of its `lines()`) that could not
+    /// be parsed; its position is derived from where it sits in the input.
+    pub fn parse(input: &str, fragment: &str, message: impl Into<String>) -> Self {
+        let start = input.as_ptr() as usize;
+        let offset = (fragment.as_ptr() as usize)
+            .checked_sub(start)
+            .filter(|offset| offset + fragment.len() <= input.len())
+            .or_else(|| input.find(fragment))
+            .unwrap_or(0);
+        let before = &input[..offset];
+        let line = before.matches('\n').count() + 1;
+        let column = before.len() - before.rfind('\n').map_or(0, |newline| newline + 1) + 1;
+        SolveError::Parse {
+            line,
+            column,
+            text: fragment.to_string(),
+            message: message.into(),
+        }
+    }
+
+    pub fn unsupported(message: impl Into<String>) -> Self {
+        SolveError::Unsupported(message.into())
+    }
+
+    pub fn internal(message: impl Into<String>) -> Self {
+        SolveError::Internal(message.into())
+    }
+}
+
+impl std::fmt::Display for SolveError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SolveError::Parse {
+                line,
+                column,
+                text,
+                message,
+            } => write!(
+                f,
+                "Parse error at line {}, column {}: {} ({:?})",
+                line, column, message, text
+            ),
+            SolveError::Unsupported(message) => write!(f, "Unsupported input: {}", message),
+            SolveError::Timeout(duration) => write!(f, "Gave up after {:.2?}", duration),
+            SolveError::Internal(message) => write!(f, "Internal error: {}", message),
+        }
+    }
+}
+
+impl std::error::Error for SolveError {}
+
+#[cfg(test)]
+mod tests {
+    use super::SolveError;
+
+    #[test]
+    fn parse_positions() {
+        let input = "addx 3\nnoop\njump 5";
+        let line = input.lines().nth(2).unwrap();
+        assert_eq!(
+            SolveError::parse(input, &line[5..], "Invalid number"),
+            SolveError::Parse {
+                line: 3,
+                column: 6,
+                text: String::from("5"),
+                message: String::from("Invalid number")
+            }
+        );
+        assert_eq!(
+            SolveError::parse(input, "noop", "Unexpected").to_string(),
+            "Parse error at line 2, column 1: Unexpected (\"noop\")"
+        );
+        let owned = String::from("jump");
+        assert!(matches!(
+            SolveError::parse(input, &owned, "Unknown instruction"),
+            SolveError::Parse {
+                line: 3,
+                column: 1,
+                ..
+            }
+        ));
+    }
+}
@@ -0,0 +1,490 @@
+use std::{
+    collections::HashMap,
+    fmt::{Display, Formatter},
+    ops::{Index, IndexMut},
+};
+
+use crate::{error::SolveError, render::Point};
+
+/// The four cells next to a cell, clockwise from above.
+pub fn neighbors4((x, y): Point) -> [Point; 4] {
+    [(x, y - 1), (x + 1, y), (x, y + 1), (x - 1, y)]
+}
+
+/// The eight cells around a cell, clockwise from above.
+pub fn neighbors8((x, y): Point) -> [Point; 8] {
+    [
+        (x, y - 1),
+        (x + 1, y - 1),
+        (x + 1, y),
+        (x + 1, y + 1),
+        (x, y + 1),
+        (x - 1, y + 1),
+        (x - 1, y),
+        (x - 1, y - 1),
+    ]
+}
+
+/// Parses every character of every line of the input with `cell`, reporting the position of the
+/// first character it rejects.
+fn parse_lines<T>(
+    input: &str,
+    mut cell: impl FnMut(char) -> Result<T, &'static str>,
+) -> Result<Vec<Vec<T>>, SolveError> {
+    input
+        .lines()
+        .map(|line| {
+            line.char_indices()
+                .map(|(i, c)| {
+                    cell(c).map_err(|message| {
+                        SolveError::parse(input, &line[i..i + c.len_utf8()], message)
+                    })
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// The first and last of the points, or `None` without points.
+fn bounding_box(points: impl Iterator<Item = Point>) -> Option<(Point, Point)> {
+    points.fold(None, |bounds, (x, y)| match bounds {
+        None => Some(((x, y), (x, y))),
+        Some(((min_x, min_y), (max_x, max_y))) => {
+            Some(((min_x.min(x), min_y.min(y)), (max_x.max(x), max_y.max(y))))
+        }
+    })
+}
+
+/// A grid with a cell at every point from `(0, 0)` up to its width and height, stored row by row.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    pub fn new(width: usize, height: usize, cell: T) -> Self
+    where
+        T: Clone,
+    {
+        Self {
+            width,
+            cells: vec![cell; width * height],
+        }
+    }
+
+    /// A grid with a cell per character of the input, rows being its lines.
+    pub fn parse(
+        input: &str,
+        cell: impl FnMut(char) -> Result<T, &'static str>,
+    ) -> Result<Self, SolveError> {
+        let rows = parse_lines(input, cell)?;
+        if let Some((line, _)) = input
+            .lines()
+            .zip(rows.iter())
+            .find(|(_, row)| row.len() != rows[0].len())
+        {
+            return Err(SolveError::parse(input, line, "Rows differ in length"));
+        }
+        Ok(Self {
+            width: rows.first().map_or(0, |row| row.len()),
+            cells: rows.into_iter().flatten().collect(),
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        match self.width {
+            0 => 0,
+            width => self.cells.len() / width,
+        }
+    }
+
+    pub fn contains(&self, (x, y): Point) -> bool {
+        (0..self.width as i64).contains(&x) && (0..self.height() as i64).contains(&y)
+    }
+
+    fn offset(&self, point: Point) -> Option<usize> {
+        match self.contains(point) {
+            true => Some(point.1 as usize * self.width + point.0 as usize),
+            false => None,
+        }
+    }
+
+    pub fn get(&self, point: Point) -> Option<&T> {
+        self.offset(point).map(|offset| &self.cells[offset])
+    }
+
+    pub fn get_mut(&mut self, point: Point) -> Option<&mut T> {
+        self.offset(point).map(|offset| &mut self.cells[offset])
+    }
+
+    /// The cells with their point, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = (Point, &T)> {
+        let width = self.width.max(1);
+        self.cells.iter().enumerate().map(move |(offset, cell)| {
+            let point = ((offset % width) as i64, (offset / width) as i64);
+            (point, cell)
+        })
+    }
+
+    /// The point of the first cell, row by row, matching the predicate.
+    pub fn find(&self, mut predicate: impl FnMut(&T) -> bool) -> Option<Point> {
+        self.iter()
+            .find(|(_, cell)| predicate(cell))
+            .map(|(point, _)| point)
+    }
+
+    /// The cells next to a cell that are within the grid, clockwise from above.
+    pub fn neighbors4(&self, point: Point) -> impl Iterator<Item = (Point, &T)> {
+        neighbors4(point)
+            .into_iter()
+            .filter_map(|point| self.get(point).map(|cell| (point, cell)))
+    }
+
+    /// The cells around a cell that are within the grid, clockwise from above.
+    pub fn neighbors8(&self, point: Point) -> impl Iterator<Item = (Point, &T)> {
+        neighbors8(point)
+            .into_iter()
+            .filter_map(|point| self.get(point).map(|cell| (point, cell)))
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn row_mut(&mut self, y: usize) -> &mut [T] {
+        &mut self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        self.cells.chunks(self.width.max(1))
+    }
+
+    /// The cells of a column, from top to bottom.
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        self.cells.iter().skip(x).step_by(self.width.max(1))
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(|x| self.column(x))
+    }
+
+    /// The grid mirrored along its diagonal, so its columns become rows.
+    pub fn transposed(&self) -> Self
+    where
+        T: Clone,
+    {
+        Self {
+            width: self.height(),
+            cells: self.columns().flatten().cloned().collect(),
+        }
+    }
+
+    pub fn map<U>(&self, cell: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            cells: self.cells.iter().map(cell).collect(),
+        }
+    }
+
+    /// The first and last cell, or `None` for an empty grid.
+    pub fn bounds(&self) -> Option<(Point, Point)> {
+        match self.cells.is_empty() {
+            true => None,
+            false => Some(((0, 0), (self.width as i64 - 1, self.height() as i64 - 1))),
+        }
+    }
+}
+
+impl<T> Index<Point> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, point: Point) -> &T {
+        self.get(point)
+            .unwrap_or_else(|| panic!("{:?} lies outside the grid", point))
+    }
+}
+
+impl<T> IndexMut<Point> for Grid<T> {
+    fn index_mut(&mut self, point: Point) -> &mut T {
+        self.get_mut(point)
+            .unwrap_or_else(|| panic!("{:?} lies outside the grid", point))
+    }
+}
+
+/// The rows of cells, one per line.
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for (y, row) in self.rows().enumerate() {
+            if y > 0 {
+                writeln!(f)?;
+            }
+            row.iter().try_for_each(|cell| write!(f, "{}", cell))?;
+        }
+        Ok(())
+    }
+}
+
+/// A grid that grows in every direction, with cells only at the points that were set. Like the
+/// `EndlessGrid` of the TS tree.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SparseGrid<T> {
+    cells: HashMap<Point, T>,
+}
+
+impl<T> Default for SparseGrid<T> {
+    fn default() -> Self {
+        Self {
+            cells: HashMap::new(),
+        }
+    }
+}
+
+impl<T> SparseGrid<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// A grid with a cell for every character of the input that `cell` gives a cell for, rows
+    /// being its lines starting at `(0, 0)`.
+    pub fn parse(
+        input: &str,
+        cell: impl FnMut(char) -> Result<Option<T>, &'static str>,
+    ) -> Result<Self, SolveError> {
+        let rows = parse_lines(input, cell)?;
+        let cells = rows.into_iter().enumerate().flat_map(|(y, row)| {
+            row.into_iter()
+                .enumerate()
+                .filter_map(move |(x, cell)| cell.map(|cell| ((x as i64, y as i64), cell)))
+        });
+        Ok(Self {
+            cells: cells.collect(),
+        })
+    }
+
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    pub fn contains(&self, point: Point) -> bool {
+        self.cells.contains_key(&point)
+    }
+
+    pub fn get(&self, point: Point) -> Option<&T> {
+        self.cells.get(&point)
+    }
+
+    pub fn get_mut(&mut self, point: Point) -> Option<&mut T> {
+        self.cells.get_mut(&point)
+    }
+
+    /// Sets the cell at the point, returning the cell it replaced.
+    pub fn set(&mut self, point: Point, cell: T) -> Option<T> {
+        self.cells.insert(point, cell)
+    }
+
+    pub fn remove(&mut self, point: Point) -> Option<T> {
+        self.cells.remove(&point)
+    }
+
+    /// The cells with their point, in no particular order.
+    pub fn iter(&self) -> impl Iterator<Item = (Point, &T)> {
+        self.cells.iter().map(|(point, cell)| (*point, cell))
+    }
+
+    /// The cells next to a point that are set, clockwise from above.
+    pub fn neighbors4(&self, point: Point) -> impl Iterator<Item = (Point, &T)> {
+        neighbors4(point)
+            .into_iter()
+            .filter_map(|point| self.get(point).map(|cell| (point, cell)))
+    }
+
+    /// The cells around a point that are set, clockwise from above.
+    pub fn neighbors8(&self, point: Point) -> impl Iterator<Item = (Point, &T)> {
+        neighbors8(point)
+            .into_iter()
+            .filter_map(|point| self.get(point).map(|cell| (point, cell)))
+    }
+
+    /// The first and last point of the smallest box around the cells, or `None` for an empty
+    /// grid.
+    pub fn bounds(&self) -> Option<(Point, Point)> {
+        bounding_box(self.cells.keys().copied())
+    }
+
+    /// A row within the bounds, `None` where no cell is set.
+    pub fn row(&self, y: i64) -> impl Iterator<Item = Option<&T>> {
+        self.row_within(y, self.extent())
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = impl Iterator<Item = Option<&T>>> {
+        let extent @ (min, max) = self.extent();
+        (min.1..=max.1).map(move |y| self.row_within(y, extent))
+    }
+
+    /// A column within the bounds from top to bottom, `None` where no cell is set.
+    pub fn column(&self, x: i64) -> impl Iterator<Item = Option<&T>> {
+        self.column_within(x, self.extent())
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = Option<&T>>> {
+        let extent @ (min, max) = self.extent();
+        (min.0..=max.0).map(move |x| self.column_within(x, extent))
+    }
+
+    /// The bounds, or an empty range of points for an empty grid. Computing them visits every
+    /// cell, so the views over several rows or columns compute them once.
+    fn extent(&self) -> (Point, Point) {
+        self.bounds().unwrap_or(((0, 0), (-1, -1)))
+    }
+
+    fn row_within(&self, y: i64, (min, max): (Point, Point)) -> impl Iterator<Item = Option<&T>> {
+        (min.0..=max.0).map(move |x| self.get((x, y)))
+    }
+
+    fn column_within(
+        &self,
+        x: i64,
+        (min, max): (Point, Point),
+    ) -> impl Iterator<Item = Option<&T>> {
+        (min.1..=max.1).map(move |y| self.get((x, y)))
+    }
+
+    /// The grid mirrored along its diagonal, so its columns become rows.
+    pub fn transposed(&self) -> Self
+    where
+        T: Clone,
+    {
+        Self {
+            cells: self
+                .iter()
+                .map(|((x, y), cell)| ((y, x), cell.clone()))
+                .collect(),
+        }
+    }
+
+    /// The cells within the bounds as a grid starting at the first of the bounds, with `empty`
+    /// where no cell is set.
+    pub fn to_dense(&self, empty: T) -> Grid<T>
+    where
+        T: Clone,
+    {
+        let extent @ (min, max) = self.extent();
+        Grid {
+            width: (max.0 - min.0 + 1) as usize,
+            cells: (min.1..=max.1)
+                .flat_map(|y| self.row_within(y, extent))
+                .map(|cell| cell.unwrap_or(&empty).clone())
+                .collect(),
+        }
+    }
+}
+
+/// The rows within the bounds, one per line, with the default cell where no cell is set.
+impl<T: Display + Default> Display for SparseGrid<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let empty = T::default();
+        for (y, row) in self.rows().enumerate() {
+            if y > 0 {
+                writeln!(f)?;
+            }
+            row.into_iter()
+                .try_for_each(|cell| write!(f, "{}", cell.unwrap_or(&empty)))?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{neighbors8, Grid, SparseGrid};
+    use crate::SolveError;
+
+    fn digits(input: &str) -> Result<Grid<u32>, SolveError> {
+        Grid::parse(input, |c| c.to_digit(10).ok_or("Invalid digit"))
+    }
+
+    #[test]
+    fn parses_dense_grids() {
+        let grid = digits("123\n456").unwrap();
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid[(2, 1)], 6);
+        assert_eq!(grid.get((3, 0)), None);
+        assert_eq!(grid.get((0, -1)), None);
+        assert_eq!(grid.bounds(), Some(((0, 0), (2, 1))));
+        assert_eq!(grid.find(|cell| *cell > 4), Some((1, 1)));
+        assert_eq!(grid.to_string(), "123\n456");
+
+        assert_eq!(
+            digits("12\n4x6").unwrap_err().to_string(),
+            "Parse error at line 2, column 2: Invalid digit (\"x\")"
+        );
+        assert_eq!(
+            digits("12\n456").unwrap_err().to_string(),
+            "Parse error at line 2, column 1: Rows differ in length (\"456\")"
+        );
+        assert_eq!(digits("").unwrap().bounds(), None);
+    }
+
+    #[test]
+    fn views_dense_grids() {
+        let mut grid = digits("123\n456").unwrap();
+        assert_eq!(grid.row(1), &[4, 5, 6]);
+        assert_eq!(grid.column(1).copied().collect::<Vec<_>>(), vec![2, 5]);
+        assert_eq!(grid.rows().count(), 2);
+        assert_eq!(grid.columns().count(), 3);
+        assert_eq!(grid.transposed().to_string(), "14\n25\n36");
+        assert_eq!(
+            grid.neighbors4((0, 0)).collect::<Vec<_>>(),
+            vec![((1, 0), &2), ((0, 1), &4)]
+        );
+        assert_eq!(grid.neighbors8((1, 0)).count(), 5);
+
+        grid.row_mut(0)[0] = 9;
+        grid[(2, 1)] = 0;
+        assert_eq!(grid.map(|cell| cell % 2).to_string(), "101\n010");
+    }
+
+    #[test]
+    fn grows_sparse_grids() {
+        let mut grid = SparseGrid::parse(".#\n#.", |c| match c {
+            '#' => Ok(Some('#')),
+            '.' => Ok(None),
+            _ => Err("Invalid cell"),
+        })
+        .unwrap();
+        assert_eq!(grid.len(), 2);
+        assert_eq!(grid.bounds(), Some(((0, 0), (1, 1))));
+
+        grid.set((-2, 3), '@');
+        assert_eq!(grid.bounds(), Some(((-2, 0), (1, 3))));
+        assert_eq!(grid.to_dense('.').to_string(), "...#\n..#.\n....\n@...");
+        assert_eq!(grid.row(3).flatten().collect::<Vec<_>>(), vec![&'@']);
+        assert_eq!(grid.columns().count(), 4);
+        assert_eq!(grid.transposed().get((3, -2)), Some(&'@'));
+        assert_eq!(
+            grid.neighbors8((0, 0))
+                .map(|(point, _)| point)
+                .collect::<Vec<_>>(),
+            vec![(1, 0), (0, 1)]
+        );
+        assert_eq!(neighbors8((0, 0))[1], (1, -1));
+
+        grid.remove((-2, 3));
+        assert_eq!(grid.bounds(), Some(((0, 0), (1, 1))));
+        assert!(SparseGrid::<char>::new().bounds().is_none());
+
+        let mut counts = SparseGrid::new();
+        counts.set((0, 0), 1);
+        counts.set((2, 1), 2);
+        assert_eq!(counts.to_string(), "100\n002");
+    }
+}
@@ -1,3 +1,13 @@
+pub(crate) mod answer;
+pub(crate) mod error;
+pub(crate) mod grid;
+pub(crate) mod progress;
+pub(crate) mod render;
 pub(crate) mod solution;
 
-pub use self::solution::*;
\ No newline at end of file
+pub use self::answer::*;
+pub use self::error::*;
+pub use self::grid::*;
+pub use self::progress::*;
+pub use self::render::*;
+pub use self::solution::*;
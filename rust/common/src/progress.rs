@@ -0,0 +1,144 @@
+use std::sync::{
+    atomic::{AtomicU64, Ordering},
+    Arc, Mutex, MutexGuard,
+};
+
+/// Handle for a solution to report what it is working on, e.g. `blueprint 3/30` or `turn 250`.
+/// A default handle is not tracked, so reporting costs nothing unless the runner shows progress.
+/// Tasks and phases take a lock, the steps done are a single atomic store so they can be updated
+/// from hot loops.
+#[derive(Debug, Clone, Default)]
+pub struct Progress(Option<Arc<Tracked>>);
+
+#[derive(Debug, Default)]
+struct Tracked {
+    status: Mutex<Status>,
+    done: AtomicU64,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Status {
+    /// What the runner is running, e.g. `Part A`
+    pub task: Option<String>,
+    /// What the solution is working through, e.g. `blueprint`
+    pub phase: Option<String>,
+    pub done: u64,
+    pub total: Option<u64>,
+}
+
+impl Progress {
+    /// A handle whose reports can be read back with [`Progress::status`].
+    pub fn tracked() -> Self {
+        Self(Some(Arc::default()))
+    }
+
+    pub fn is_tracked(&self) -> bool {
+        self.0.is_some()
+    }
+
+    /// Whether both handles report to the same status.
+    pub fn same(&self, other: &Progress) -> bool {
+        match (&self.0, &other.0) {
+            (Some(status), Some(other)) => Arc::ptr_eq(status, other),
+            _ => false,
+        }
+    }
+
+    /// Starts the next task, forgetting the phase of the previous one.
+    pub fn task(&self, name: impl Into<String>) {
+        if let Some((mut status, done)) = self.lock() {
+            *status = Status {
+                task: Some(name.into()),
+                ..Status::default()
+            };
+            done.store(0, Ordering::Relaxed);
+        }
+    }
+
+    /// Starts a phase of `total` steps, or of an unknown number of steps.
+    pub fn phase(&self, name: impl Into<String>, total: Option<u64>) {
+        if let Some((mut status, done)) = self.lock() {
+            status.phase = Some(name.into());
+            status.total = total;
+            done.store(0, Ordering::Relaxed);
+        }
+    }
+
+    /// Number of steps of the current phase done so far.
+    pub fn update(&self, done: u64) {
+        if let Some(tracked) = &self.0 {
+            tracked.done.store(done, Ordering::Relaxed);
+        }
+    }
+
+    pub fn status(&self) -> Option<Status> {
+        self.lock().map(|(status, done)| Status {
+            done: done.load(Ordering::Relaxed),
+            ..status.clone()
+        })
+    }
+
+    fn lock(&self) -> Option<(MutexGuard<'_, Status>, &AtomicU64)> {
+        // A part panicking while reporting does not make the status any less readable
+        self.0.as_ref().map(|tracked| {
+            let status = tracked.status.lock().unwrap_or_else(|e| e.into_inner());
+            (status, &tracked.done)
+        })
+    }
+}
+
+impl std::fmt::Display for Status {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if let Some(task) = &self.task {
+            write!(f, "{}", task)?;
+            if self.phase.is_some() {
+                write!(f, ": ")?;
+            }
+        }
+        if let Some(phase) = &self.phase {
+            match self.total {
+                Some(total) if total > 0 => write!(
+                    f,
+                    "{} {}/{} ({:.0}%)",
+                    phase,
+                    self.done,
+                    total,
+                    self.done as f64 / total as f64 * 100.0
+                )?,
+                _ => write!(f, "{} {}", phase, self.done)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Progress;
+
+    #[test]
+    fn reports_phases() {
+        let untracked = Progress::default();
+        untracked.phase("blueprint", Some(30));
+        assert_eq!(untracked.status(), None);
+
+        let progress = Progress::tracked();
+        progress.task("Part A");
+        assert_eq!(progress.status().unwrap().to_string(), "Part A");
+        let handle = progress.clone();
+        handle.phase("blueprint", Some(30));
+        handle.update(3);
+        assert_eq!(
+            progress.status().unwrap().to_string(),
+            "Part A: blueprint 3/30 (10%)"
+        );
+        handle.phase("turn", None);
+        handle.update(250);
+        assert_eq!(progress.status().unwrap().to_string(), "Part A: turn 250");
+
+        progress.task("Part B");
+        assert_eq!(progress.status().unwrap().phase, None);
+        assert!(progress.same(&handle));
+        assert!(!progress.same(&Progress::tracked()));
+    }
+}
@@ -0,0 +1,294 @@
+use std::sync::{Arc, Mutex, MutexGuard};
+
+/// A cell as `(x, y)`, with `y` going down like the rows of a puzzle input.
+pub type Point = (i64, i64);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Color(pub u8, pub u8, pub u8);
+
+impl Color {
+    pub const BLACK: Color = Color(0, 0, 0);
+    pub const WHITE: Color = Color(255, 255, 255);
+    pub const GRAY: Color = Color(128, 128, 128);
+    pub const DARK_GRAY: Color = Color(64, 64, 64);
+    pub const RED: Color = Color(220, 50, 47);
+    pub const GREEN: Color = Color(60, 160, 60);
+    pub const BLUE: Color = Color(38, 110, 210);
+    pub const CYAN: Color = Color(42, 161, 152);
+    pub const YELLOW: Color = Color(230, 190, 30);
+    pub const ORANGE: Color = Color(220, 130, 40);
+
+    /// As a CSS color, e.g. `#dc8228`.
+    pub fn hex(&self) -> String {
+        format!("#{:02x}{:02x}{:02x}", self.0, self.1, self.2)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Shape {
+    /// Rows of cells starting at `origin`, colored by character; characters without a color in
+    /// the palette are left empty
+    Grid {
+        origin: Point,
+        rows: Vec<String>,
+        palette: Vec<(char, Color)>,
+    },
+    Points {
+        points: Vec<Point>,
+        color: Color,
+    },
+    /// A line through the centers of the cells
+    Path {
+        points: Vec<Point>,
+        color: Color,
+    },
+    Text {
+        at: Point,
+        text: String,
+        color: Color,
+    },
+}
+
+/// What a solution drew between two flushes.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Frame {
+    pub shapes: Vec<Shape>,
+}
+
+impl Frame {
+    /// The first and last cell covered by the shapes, or `None` for an empty frame.
+    pub fn bounds(&self) -> Option<(Point, Point)> {
+        let corners = self.shapes.iter().flat_map(|shape| match shape {
+            Shape::Grid { origin, rows, .. } => {
+                let width = rows
+                    .iter()
+                    .map(|row| row.chars().count())
+                    .max()
+                    .unwrap_or(0) as i64;
+                vec![
+                    *origin,
+                    (
+                        origin.0 + (width - 1).max(0),
+                        origin.1 + (rows.len() as i64 - 1).max(0),
+                    ),
+                ]
+            }
+            Shape::Points { points, .. } | Shape::Path { points, .. } => points.clone(),
+            Shape::Text { at, .. } => vec![*at],
+        });
+        corners.fold(None, |bounds, (x, y)| match bounds {
+            None => Some(((x, y), (x, y))),
+            Some(((min_x, min_y), (max_x, max_y))) => {
+                Some(((min_x.min(x), min_y.min(y)), (max_x.max(x), max_y.max(y))))
+            }
+        })
+    }
+}
+
+/// A type of grid cell, like the tiles of a cave, drawn as its symbol in the color the palette of
+/// the type gives that symbol.
+pub trait GridCell {
+    /// Colors of the symbols; cells with a symbol without a color are left empty
+    const PALETTE: &'static [(char, Color)];
+
+    fn symbol(&self) -> char;
+}
+
+/// Where flushed frames go, e.g. the visualization server.
+pub trait FrameSink: Send {
+    fn frame(&mut self, frame: Frame);
+}
+
+struct Canvas {
+    buffer: Frame,
+    sink: Box<dyn FrameSink>,
+}
+
+/// Handle for a solution to draw its state as frames: shapes are buffered until
+/// [`Renderer::flush`] hands them to the sink as one frame. A default handle draws nowhere, so
+/// check [`Renderer::is_enabled`] before building a frame that takes time.
+#[derive(Clone, Default)]
+pub struct Renderer(Option<Arc<Mutex<Canvas>>>);
+
+impl std::fmt::Debug for Renderer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Renderer")
+            .field("enabled", &self.is_enabled())
+            .finish()
+    }
+}
+
+impl Renderer {
+    pub fn new(sink: impl FrameSink + 'static) -> Self {
+        Self(Some(Arc::new(Mutex::new(Canvas {
+            buffer: Frame::default(),
+            sink: Box::new(sink),
+        }))))
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.0.is_some()
+    }
+
+    /// Forgets what was drawn since the last flush.
+    pub fn clear(&self) {
+        if let Some(mut canvas) = self.lock() {
+            canvas.buffer.shapes.clear();
+        }
+    }
+
+    pub fn grid(
+        &self,
+        origin: Point,
+        rows: impl IntoIterator<Item = String>,
+        palette: &[(char, Color)],
+    ) {
+        self.draw(|| Shape::Grid {
+            origin,
+            rows: rows.into_iter().collect(),
+            palette: palette.to_vec(),
+        });
+    }
+
+    /// Draws rows of cells with the palette of their type.
+    pub fn cells<'a, C, R>(&self, origin: Point, rows: impl IntoIterator<Item = R>)
+    where
+        C: GridCell + 'a,
+        R: IntoIterator<Item = &'a C>,
+    {
+        self.draw(|| Shape::Grid {
+            origin,
+            rows: rows
+                .into_iter()
+                .map(|row| row.into_iter().map(C::symbol).collect())
+                .collect(),
+            palette: C::PALETTE.to_vec(),
+        });
+    }
+
+    pub fn points(&self, points: impl IntoIterator<Item = Point>, color: Color) {
+        self.draw(|| Shape::Points {
+            points: points.into_iter().collect(),
+            color,
+        });
+    }
+
+    pub fn path(&self, points: impl IntoIterator<Item = Point>, color: Color) {
+        self.draw(|| Shape::Path {
+            points: points.into_iter().collect(),
+            color,
+        });
+    }
+
+    pub fn text(&self, at: Point, text: impl Into<String>, color: Color) {
+        self.draw(|| Shape::Text {
+            at,
+            text: text.into(),
+            color,
+        });
+    }
+
+    /// Hands what was drawn since the last flush to the sink as one frame.
+    pub fn flush(&self) {
+        if let Some(mut canvas) = self.lock() {
+            let frame = std::mem::take(&mut canvas.buffer);
+            canvas.sink.frame(frame);
+        }
+    }
+
+    fn draw(&self, shape: impl FnOnce() -> Shape) {
+        if let Some(mut canvas) = self.lock() {
+            canvas.buffer.shapes.push(shape());
+        }
+    }
+
+    fn lock(&self) -> Option<MutexGuard<'_, Canvas>> {
+        self.0
+            .as_ref()
+            .map(|canvas| canvas.lock().unwrap_or_else(|e| e.into_inner()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use super::{Color, Frame, FrameSink, GridCell, Renderer, Shape};
+
+    struct Frames(Arc<Mutex<Vec<Frame>>>);
+
+    impl FrameSink for Frames {
+        fn frame(&mut self, frame: Frame) {
+            self.0.lock().unwrap().push(frame);
+        }
+    }
+
+    #[test]
+    fn buffers_until_flushed() {
+        let frames = Arc::new(Mutex::new(vec![]));
+        let renderer = Renderer::new(Frames(frames.clone()));
+        renderer.points([(0, 0)], Color::RED);
+        renderer.clear();
+        renderer.grid(
+            (-2, 1),
+            [String::from("#.#"), String::from("#")],
+            &[('#', Color::GRAY)],
+        );
+        renderer.text((0, -1), "round 1", Color::BLACK);
+        assert!(frames.lock().unwrap().is_empty());
+
+        renderer.flush();
+        renderer.flush();
+        let frames = frames.lock().unwrap();
+        assert_eq!(frames.len(), 2);
+        assert_eq!(frames[0].shapes.len(), 2);
+        assert!(matches!(
+            frames[0].shapes[0],
+            Shape::Grid {
+                origin: (-2, 1),
+                ..
+            }
+        ));
+        assert_eq!(frames[0].bounds(), Some(((-2, -1), (0, 2))));
+        assert_eq!(frames[1].bounds(), None);
+
+        let untracked = Renderer::default();
+        untracked.points([(0, 0)], Color::RED);
+        untracked.flush();
+        assert!(!untracked.is_enabled());
+        assert_eq!(Color::ORANGE.hex(), "#dc8228");
+    }
+
+    enum Tile {
+        Rock,
+        Air,
+    }
+
+    impl GridCell for Tile {
+        const PALETTE: &'static [(char, Color)] = &[('#', Color::GRAY)];
+
+        fn symbol(&self) -> char {
+            match self {
+                Tile::Rock => '#',
+                Tile::Air => '.',
+            }
+        }
+    }
+
+    #[test]
+    fn draws_cells_with_their_palette() {
+        let frames = Arc::new(Mutex::new(vec![]));
+        let renderer = Renderer::new(Frames(frames.clone()));
+        let grid = [[Tile::Rock, Tile::Air], [Tile::Air, Tile::Rock]];
+        renderer.cells((0, 0), grid.iter());
+        renderer.flush();
+        assert_eq!(
+            frames.lock().unwrap()[0].shapes,
+            vec![Shape::Grid {
+                origin: (0, 0),
+                rows: vec![String::from("#."), String::from(".#")],
+                palette: vec![('#', Color::GRAY)],
+            }]
+        );
+    }
+}
@@ -1,75 +1,244 @@
-use async_trait::async_trait;
+use std::{
+    any::Any,
+    future::Future,
+    pin::pin,
+    sync::Arc,
+    task::{Context, Poll, Wake, Waker},
+    thread,
+};
 
-#[async_trait]
-pub trait Solution {
-    async fn solve_a(&mut self, input: String) -> Result<Answer, String>;
-    async fn solve_b(&mut self, input: String) -> Result<Answer, String>;
+/// For implementing [`AsyncSolution`] without depending on `async-trait` directly
+pub use async_trait::async_trait;
+
+use crate::{Answer, Progress, Renderer, SolveError};
+
+/// A puzzle day. The input is parsed once into [`Solution::Parsed`], which both parts share;
+/// days without much to parse can keep the input itself, e.g. `type Parsed = String`.
+pub trait Solution: Send {
+    type Parsed: Send + 'static;
+
+    fn parse(&self, input: &str) -> Result<Self::Parsed, SolveError>;
+    fn solve_a(&mut self, parsed: &Self::Parsed) -> Result<Answer, SolveError>;
+    fn solve_b(&mut self, parsed: &Self::Parsed) -> Result<Answer, SolveError>;
+
+    /// Example inputs from the puzzle statement, with the answers they should produce.
+    fn examples(&self) -> Vec<Example> {
+        vec![]
+    }
+
+    /// Switches to the parameters the puzzle statement uses for its examples, for the days
+    /// where those differ from the real puzzle (e.g. a smaller search area).
+    fn example_mode(&mut self) {}
+
+    /// Hands over where to report progress, for the days that take long enough to want it.
+    fn set_progress(&mut self, _progress: Progress) {}
+
+    /// Hands over where to draw frames, for the days that can show their state while they run.
+    fn set_renderer(&mut self, _renderer: Renderer) {}
 }
 
-#[derive(Debug, PartialEq, Eq)]
-pub struct Answer {
-    result: String,
+/// Parsed input of a [`DynSolution`], only usable by the day that parsed it.
+pub type ParsedInput = Box<dyn Any + Send>;
+
+/// A [`Solution`] with its `Parsed` type erased, so days of different types can be stored and run
+/// alike. Implemented for every `Solution`.
+pub trait DynSolution: Send {
+    fn parse(&self, input: &str) -> Result<ParsedInput, SolveError>;
+    fn solve_a(&mut self, parsed: &ParsedInput) -> Result<Answer, SolveError>;
+    fn solve_b(&mut self, parsed: &ParsedInput) -> Result<Answer, SolveError>;
+    fn examples(&self) -> Vec<Example>;
+    fn example_mode(&mut self);
+    fn set_progress(&mut self, progress: Progress);
+    fn set_renderer(&mut self, renderer: Renderer);
+}
+
+impl<T: Solution> DynSolution for T {
+    fn parse(&self, input: &str) -> Result<ParsedInput, SolveError> {
+        Ok(Box::new(Solution::parse(self, input)?))
+    }
+
+    fn solve_a(&mut self, parsed: &ParsedInput) -> Result<Answer, SolveError> {
+        Solution::solve_a(self, downcast::<T>(parsed)?)
+    }
+
+    fn solve_b(&mut self, parsed: &ParsedInput) -> Result<Answer, SolveError> {
+        Solution::solve_b(self, downcast::<T>(parsed)?)
+    }
+
+    fn examples(&self) -> Vec<Example> {
+        Solution::examples(self)
+    }
+
+    fn example_mode(&mut self) {
+        Solution::example_mode(self)
+    }
+
+    fn set_progress(&mut self, progress: Progress) {
+        Solution::set_progress(self, progress)
+    }
+
+    fn set_renderer(&mut self, renderer: Renderer) {
+        Solution::set_renderer(self, renderer)
+    }
+}
+
+fn downcast<T: Solution>(parsed: &ParsedInput) -> Result<&T::Parsed, SolveError> {
+    parsed
+        .downcast_ref()
+        .ok_or_else(|| SolveError::internal("Input was parsed by another day"))
+}
+
+/// A day whose parts need to await, run through [`AsyncAdapter`].
+#[async_trait]
+pub trait AsyncSolution: Send {
+    type Parsed: Send + Sync + 'static;
+
+    fn parse(&self, input: &str) -> Result<Self::Parsed, SolveError>;
+    async fn solve_a(&mut self, parsed: &Self::Parsed) -> Result<Answer, SolveError>;
+    async fn solve_b(&mut self, parsed: &Self::Parsed) -> Result<Answer, SolveError>;
+
+    fn examples(&self) -> Vec<Example> {
+        vec![]
+    }
+
+    fn example_mode(&mut self) {}
+
+    fn set_progress(&mut self, _progress: Progress) {}
+    fn set_renderer(&mut self, _renderer: Renderer) {}
 }
-impl Answer {
-    pub fn get_result(&self) -> String {
-        self.result.clone()
+
+/// Runs an [`AsyncSolution`] as a [`Solution`], driving its parts to completion on the calling
+/// thread. Register it as `Day::new::<AsyncAdapter<Puzzle>>(..)`. The runner enters its tokio
+/// runtime on the thread of a part, so the parts can use tokio timers and IO; elsewhere only
+/// futures that do not need a runtime can be awaited.
+#[derive(Default)]
+pub struct AsyncAdapter<T>(pub T);
+
+impl<T: AsyncSolution> Solution for AsyncAdapter<T> {
+    type Parsed = T::Parsed;
+
+    fn parse(&self, input: &str) -> Result<Self::Parsed, SolveError> {
+        self.0.parse(input)
+    }
+
+    fn solve_a(&mut self, parsed: &Self::Parsed) -> Result<Answer, SolveError> {
+        block_on(self.0.solve_a(parsed))
+    }
+
+    fn solve_b(&mut self, parsed: &Self::Parsed) -> Result<Answer, SolveError> {
+        block_on(self.0.solve_b(parsed))
+    }
+
+    fn examples(&self) -> Vec<Example> {
+        self.0.examples()
+    }
+
+    fn example_mode(&mut self) {
+        self.0.example_mode()
+    }
+
+    fn set_progress(&mut self, progress: Progress) {
+        self.0.set_progress(progress)
+    }
+
+    fn set_renderer(&mut self, renderer: Renderer) {
+        self.0.set_renderer(renderer)
     }
 }
 
-impl Into<Result<Answer, String>> for Answer {
-    fn into(self) -> Result<Answer, String> {
-        Ok(self)
+struct Unpark(thread::Thread);
+
+impl Wake for Unpark {
+    fn wake(self: Arc<Self>) {
+        self.0.unpark();
     }
 }
 
-impl From<String> for Answer {
-    fn from(value: String) -> Self {
-        Self { result: value }
+/// Polls the future on this thread, parking it until the future is woken again.
+fn block_on<F: Future>(future: F) -> F::Output {
+    let waker = Waker::from(Arc::new(Unpark(thread::current())));
+    let mut context = Context::from_waker(&waker);
+    let mut future = pin!(future);
+    loop {
+        match future.as_mut().poll(&mut context) {
+            Poll::Ready(output) => return output,
+            Poll::Pending => thread::park(),
+        }
     }
 }
 
-impl From<&str> for Answer {
-    fn from(value: &str) -> Self {
+#[derive(Debug, PartialEq, Eq)]
+pub struct Example {
+    pub input: &'static str,
+    pub part_a: Option<Answer>,
+    pub part_b: Option<Answer>,
+}
+
+impl Example {
+    pub fn new(input: &'static str) -> Self {
         Self {
-            result: String::from(value),
+            input,
+            part_a: None,
+            part_b: None,
         }
     }
+
+    pub fn part_a(mut self, answer: impl Into<Answer>) -> Self {
+        self.part_a = Some(answer.into());
+        self
+    }
+
+    pub fn part_b(mut self, answer: impl Into<Answer>) -> Self {
+        self.part_b = Some(answer.into());
+        self
+    }
 }
 
-macro_rules! from_numeric_to_answer {
-    ($type:ty) => {
-        impl From<$type> for Answer {
-            fn from(value: $type) -> Self {
-                Self {
-                    result: value.to_string(),
-                }
-            }
+#[cfg(test)]
+mod tests {
+    use async_trait::async_trait;
+
+    use super::{AsyncAdapter, AsyncSolution, DynSolution};
+    use crate::{Answer, SolveError};
+
+    #[derive(Default)]
+    struct Doubling;
+
+    #[async_trait]
+    impl AsyncSolution for Doubling {
+        type Parsed = Vec<u32>;
+
+        fn parse(&self, input: &str) -> Result<Vec<u32>, SolveError> {
+            input
+                .lines()
+                .map(|line| {
+                    line.parse()
+                        .map_err(|_| SolveError::parse(input, line, "Invalid number"))
+                })
+                .collect()
         }
-        impl From<&$type> for Answer {
-            fn from(value: &$type) -> Self {
-                Self {
-                    result: value.to_string(),
-                }
-            }
+
+        async fn solve_a(&mut self, parsed: &Vec<u32>) -> Result<Answer, SolveError> {
+            Ok(Answer::from(parsed.iter().sum::<u32>() * 2))
         }
-        impl From<Option<$type>> for Answer {
-            fn from(value: Option<$type>) -> Self {
-                Self {
-                    result: value.map(|v| v.to_string()).unwrap_or(String::new()),
-                }
-            }
+
+        async fn solve_b(&mut self, parsed: &Vec<u32>) -> Result<Answer, SolveError> {
+            Ok(Answer::from(parsed.len()))
         }
-    };
+    }
+
+    #[test]
+    fn runs_erased_and_async() {
+        let mut solution: Box<dyn DynSolution> = Box::<AsyncAdapter<Doubling>>::default();
+        let parsed = solution.parse("1\n2\n3").unwrap();
+        assert_eq!(solution.solve_a(&parsed), Ok(Answer::from(12)));
+        assert_eq!(solution.solve_b(&parsed), Ok(Answer::from(3)));
+        assert!(solution.parse("1\nx").is_err());
+
+        let foreign: super::ParsedInput = Box::new(String::from("1"));
+        assert!(matches!(
+            solution.solve_a(&foreign),
+            Err(SolveError::Internal(_))
+        ));
+    }
 }
-from_numeric_to_answer!(usize);
-from_numeric_to_answer!(u64);
-from_numeric_to_answer!(u32);
-from_numeric_to_answer!(u16);
-from_numeric_to_answer!(u8);
-from_numeric_to_answer!(isize);
-from_numeric_to_answer!(i64);
-from_numeric_to_answer!(i32);
-from_numeric_to_answer!(i16);
-from_numeric_to_answer!(i8);
-from_numeric_to_answer!(f32);
-from_numeric_to_answer!(f64);
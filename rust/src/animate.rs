@@ -0,0 +1,476 @@
+use std::{
+    collections::HashMap,
+    io::{self, Write},
+    sync::{Arc, Condvar, Mutex, MutexGuard},
+    thread::{self, JoinHandle},
+    time::{Duration, Instant},
+};
+
+use common::{Color, Frame, FrameSink, Point, Renderer, Shape};
+use crossterm::{
+    cursor::{Hide, MoveTo, Show},
+    event::{self, Event, KeyCode, KeyEventKind, KeyModifiers},
+    execute, queue,
+    style::{Print, ResetColor, SetForegroundColor},
+    terminal::{self, Clear, ClearType, EnterAlternateScreen, LeaveAlternateScreen},
+};
+
+const DEFAULT_FPS: u32 = 20;
+const MAX_FPS: u32 = 960;
+/// How often the input thread checks whether the animation finished
+const INPUT_POLL: Duration = Duration::from_millis(100);
+
+/// Animates the frames of the running days in the terminal, pacing the days to the frame rate.
+/// The viewport follows the cells that changed since the previous frame.
+pub struct Animator {
+    renderer: Renderer,
+    controls: Arc<Controls>,
+    input: Option<JoinHandle<()>>,
+}
+
+#[derive(Default)]
+struct Controls {
+    state: Mutex<State>,
+    changed: Condvar,
+}
+
+struct State {
+    paused: bool,
+    /// Frames to show while paused
+    steps: usize,
+    fps: u32,
+    /// Stopped animating, days run at full speed without drawing
+    stopped: bool,
+    finished: bool,
+    redraw: bool,
+}
+
+impl Default for State {
+    fn default() -> Self {
+        Self {
+            paused: false,
+            steps: 0,
+            fps: DEFAULT_FPS,
+            stopped: false,
+            finished: false,
+            redraw: false,
+        }
+    }
+}
+
+impl Controls {
+    fn lock(&self) -> MutexGuard<'_, State> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn update(&self, update: impl FnOnce(&mut State)) {
+        let mut state = self.lock();
+        update(&mut state);
+        state.redraw = true;
+        self.changed.notify_all();
+    }
+}
+
+impl Animator {
+    /// Takes over the terminal until [`Animator::finish`].
+    pub fn start() -> io::Result<Self> {
+        terminal::enable_raw_mode()?;
+        execute!(io::stdout(), EnterAlternateScreen, Hide)?;
+        let controls = Arc::new(Controls::default());
+        let input = {
+            let controls = controls.clone();
+            thread::spawn(move || read_keys(&controls))
+        };
+        Ok(Self {
+            renderer: Renderer::new(TerminalSink::new(controls.clone())),
+            controls,
+            input: Some(input),
+        })
+    }
+
+    pub fn renderer(&self) -> &Renderer {
+        &self.renderer
+    }
+
+    /// Keeps the last frame on screen until the animation is stopped, then gives the terminal back.
+    pub fn finish(self) {
+        let mut state = self.controls.lock();
+        if !state.stopped {
+            let mut out = io::stdout();
+            let _ = queue!(
+                out,
+                MoveTo(0, 0),
+                Clear(ClearType::CurrentLine),
+                Print("done | q: show the results")
+            );
+            let _ = out.flush();
+        }
+        while !state.stopped {
+            state = self
+                .controls
+                .changed
+                .wait(state)
+                .unwrap_or_else(|e| e.into_inner());
+        }
+    }
+}
+
+impl Drop for Animator {
+    fn drop(&mut self) {
+        self.controls.update(|state| state.finished = true);
+        if let Some(input) = self.input.take() {
+            let _ = input.join();
+        }
+        restore();
+    }
+}
+
+fn restore() {
+    let _ = execute!(io::stdout(), LeaveAlternateScreen, Show);
+    let _ = terminal::disable_raw_mode();
+}
+
+fn read_keys(controls: &Controls) {
+    while !controls.lock().finished {
+        if !event::poll(INPUT_POLL).unwrap_or(false) {
+            continue;
+        }
+        match event::read() {
+            Ok(Event::Key(key)) if key.kind != KeyEventKind::Release => match key.code {
+                // Raw mode swallows the interrupt
+                KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                    restore();
+                    std::process::exit(130);
+                }
+                KeyCode::Char(' ') => controls.update(|state| state.paused = !state.paused),
+                KeyCode::Char('n') | KeyCode::Right => {
+                    controls.update(|state| match state.paused {
+                        true => state.steps += 1,
+                        false => state.paused = true,
+                    })
+                }
+                KeyCode::Char('+') | KeyCode::Up => {
+                    controls.update(|state| state.fps = (state.fps * 2).min(MAX_FPS))
+                }
+                KeyCode::Char('-') | KeyCode::Down => {
+                    controls.update(|state| state.fps = (state.fps / 2).max(1))
+                }
+                KeyCode::Char('q') | KeyCode::Esc => controls.update(|state| state.stopped = true),
+                _ => {}
+            },
+            Ok(Event::Resize(_, _)) => controls.update(|_| {}),
+            _ => {}
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Cell {
+    pub symbol: char,
+    /// `None` for text, which keeps the terminal's own color
+    pub color: Option<Color>,
+    /// Whether the viewport follows changes of the cell; text like counters is left out
+    pub followed: bool,
+}
+
+/// The cells the shapes of a frame cover, later shapes drawing over earlier ones.
+pub fn raster(frame: &Frame) -> HashMap<Point, Cell> {
+    let mut cells = HashMap::new();
+    let mut set = |point: Point, symbol: char, color: Option<Color>, followed: bool| {
+        cells.insert(
+            point,
+            Cell {
+                symbol,
+                color,
+                followed,
+            },
+        );
+    };
+    for shape in frame.shapes.iter() {
+        match shape {
+            Shape::Grid {
+                origin,
+                rows,
+                palette,
+            } => {
+                for (y, row) in rows.iter().enumerate() {
+                    for (x, symbol) in row.chars().enumerate() {
+                        if let Some((_, color)) = palette.iter().find(|(c, _)| *c == symbol) {
+                            let point = (origin.0 + x as i64, origin.1 + y as i64);
+                            set(point, symbol, Some(*color), true);
+                        }
+                    }
+                }
+            }
+            Shape::Points { points, color } => {
+                for point in points {
+                    set(*point, '█', Some(*color), true);
+                }
+            }
+            Shape::Path { points, color } => {
+                for line in points.windows(2) {
+                    let (mut point, end) = (line[0], line[1]);
+                    loop {
+                        set(point, '▒', Some(*color), true);
+                        if point == end {
+                            break;
+                        }
+                        point.0 += (end.0 - point.0).signum();
+                        point.1 += (end.1 - point.1).signum();
+                    }
+                }
+            }
+            // Frame colors are picked for a light canvas, the terminal's own color is readable
+            Shape::Text { at, text, .. } => {
+                for (x, symbol) in text.chars().enumerate() {
+                    set((at.0 + x as i64, at.1), symbol, None, false);
+                }
+            }
+        }
+    }
+    cells
+}
+
+/// The first and last of the followed cells that differ between two frames.
+fn changes(before: &HashMap<Point, Cell>, after: &HashMap<Point, Cell>) -> Option<(Point, Point)> {
+    let changed = after
+        .iter()
+        .filter(|(point, cell)| cell.followed && before.get(point) != Some(cell))
+        .map(|(point, _)| *point)
+        .chain(
+            before
+                .iter()
+                .filter(|(point, cell)| cell.followed && !after.contains_key(point))
+                .map(|(point, _)| *point),
+        );
+    changed.fold(None, |bounds, (x, y)| match bounds {
+        None => Some(((x, y), (x, y))),
+        Some(((min_x, min_y), (max_x, max_y))) => {
+            Some(((min_x.min(x), min_y.min(y)), (max_x.max(x), max_y.max(y))))
+        }
+    })
+}
+
+/// Moves the top left of a viewport of `size` as little as possible to show the region of
+/// interest, keeping it within the bounds of the frame.
+fn follow(
+    view: Point,
+    size: (i64, i64),
+    roi: Option<(Point, Point)>,
+    bounds: (Point, Point),
+) -> Point {
+    let axis = |view: i64, size: i64, roi: Option<(i64, i64)>, (low, high): (i64, i64)| {
+        if high - low < size {
+            return low;
+        }
+        let view = match roi {
+            Some((first, last)) if last - first >= size || first < view => first,
+            Some((_, last)) if last >= view + size => last - size + 1,
+            _ => view,
+        };
+        view.clamp(low, high - size + 1)
+    };
+    let ((min_x, min_y), (max_x, max_y)) = bounds;
+    (
+        axis(
+            view.0,
+            size.0,
+            roi.map(|r| (r.0 .0, r.1 .0)),
+            (min_x, max_x),
+        ),
+        axis(
+            view.1,
+            size.1,
+            roi.map(|r| (r.0 .1, r.1 .1)),
+            (min_y, max_y),
+        ),
+    )
+}
+
+struct TerminalSink {
+    controls: Arc<Controls>,
+    cells: HashMap<Point, Cell>,
+    view: Point,
+    frames: u64,
+}
+
+impl TerminalSink {
+    fn new(controls: Arc<Controls>) -> Self {
+        Self {
+            controls,
+            cells: HashMap::new(),
+            view: (0, 0),
+            frames: 0,
+        }
+    }
+
+    fn draw(&self, state: &State, (width, height): (i64, i64)) -> io::Result<()> {
+        let mut out = io::stdout().lock();
+        let status = match state.stopped {
+            true => format!("frame {} | stopped animating, running the days", self.frames),
+            false => format!(
+                "frame {} | {} at {} fps | space: play/pause, n: step, +/-: speed, q: stop animating",
+                self.frames,
+                if state.paused { "paused" } else { "playing" },
+                state.fps
+            ),
+        };
+        let status: String = status.chars().take(width as usize).collect();
+        queue!(
+            out,
+            MoveTo(0, 0),
+            Clear(ClearType::CurrentLine),
+            Print(status)
+        )?;
+        for row in 0..height {
+            queue!(out, MoveTo(0, row as u16 + 1))?;
+            let mut color = None;
+            let mut run = String::new();
+            for col in 0..width {
+                let cell = self.cells.get(&(self.view.0 + col, self.view.1 + row));
+                let cell_color = cell.and_then(|cell| cell.color);
+                if cell_color != color {
+                    queue!(out, Print(&run))?;
+                    run.clear();
+                    match cell_color {
+                        Some(Color(r, g, b)) => queue!(
+                            out,
+                            SetForegroundColor(crossterm::style::Color::Rgb { r, g, b })
+                        )?,
+                        None => queue!(out, ResetColor)?,
+                    }
+                    color = cell_color;
+                }
+                run.push(cell.map_or(' ', |cell| cell.symbol));
+            }
+            queue!(out, Print(&run), ResetColor)?;
+        }
+        out.flush()
+    }
+}
+
+/// Columns and rows left for the frame below the status line.
+fn viewport() -> (i64, i64) {
+    let (columns, rows) = terminal::size().unwrap_or((80, 24));
+    ((columns as i64).max(1), (rows as i64 - 1).max(1))
+}
+
+impl FrameSink for TerminalSink {
+    /// Shows the frame and holds the day until the next frame is due.
+    fn frame(&mut self, frame: Frame) {
+        let controls = self.controls.clone();
+        let mut state = controls.lock();
+        if state.stopped || state.finished {
+            if state.redraw {
+                state.redraw = false;
+                let _ = self.draw(&state, viewport());
+            }
+            return;
+        }
+        let cells = raster(&frame);
+        let roi = changes(&self.cells, &cells);
+        self.cells = cells;
+        self.frames += 1;
+        let size = viewport();
+        if let Some(bounds) = frame.bounds() {
+            self.view = follow(self.view, size, roi, bounds);
+        }
+        let _ = self.draw(&state, size);
+        let shown = Instant::now();
+        loop {
+            if state.redraw {
+                state.redraw = false;
+                let _ = self.draw(&state, viewport());
+            }
+            if state.stopped || state.finished {
+                return;
+            }
+            if state.paused {
+                if state.steps > 0 {
+                    state.steps -= 1;
+                    return;
+                }
+                state = controls
+                    .changed
+                    .wait(state)
+                    .unwrap_or_else(|e| e.into_inner());
+                continue;
+            }
+            let due = shown + Duration::from_secs_f64(1.0 / state.fps as f64);
+            let now = Instant::now();
+            if now >= due {
+                return;
+            }
+            state = match controls.changed.wait_timeout(state, due - now) {
+                Ok((state, _)) => state,
+                Err(e) => e.into_inner().0,
+            };
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use common::{Color, Frame, Shape};
+
+    use super::{changes, follow, raster};
+
+    #[test]
+    fn follows_changes() {
+        let bounds = ((0, 0), (99, 9));
+        // The whole frame fits
+        assert_eq!(follow((5, 5), (120, 20), None, ((-3, 0), (99, 9))), (-3, 0));
+        // Nothing changed
+        assert_eq!(follow((30, 0), (20, 10), None, bounds), (30, 0));
+        // Scrolls just far enough to show the change, on either side
+        assert_eq!(
+            follow((30, 0), (20, 10), Some(((55, 2), (56, 2))), bounds),
+            (37, 0)
+        );
+        assert_eq!(
+            follow((30, 0), (20, 10), Some(((12, 2), (14, 2))), bounds),
+            (12, 0)
+        );
+        // Stays within the frame
+        assert_eq!(
+            follow((30, 0), (20, 10), Some(((99, 2), (99, 2))), bounds),
+            (80, 0)
+        );
+    }
+
+    #[test]
+    fn rasters_shapes() {
+        let frame = |sand: (i64, i64)| Frame {
+            shapes: vec![
+                Shape::Grid {
+                    origin: (10, 0),
+                    rows: vec![String::from("#.#")],
+                    palette: vec![('#', Color::GRAY)],
+                },
+                Shape::Path {
+                    points: vec![(0, 0), (0, 2), (2, 2)],
+                    color: Color::GRAY,
+                },
+                Shape::Points {
+                    points: vec![sand],
+                    color: Color::ORANGE,
+                },
+                Shape::Text {
+                    at: (0, -1),
+                    text: format!("sand: {}", sand.0),
+                    color: Color::BLACK,
+                },
+            ],
+        };
+        let before = raster(&frame((5, 5)));
+        assert_eq!(before.len(), 2 + 5 + 1 + 7);
+        assert_eq!(before[&(12, 0)].symbol, '#');
+        assert!(!before.contains_key(&(11, 0)));
+        assert_eq!(before[&(1, 2)].symbol, '▒');
+        assert_eq!(before[&(0, -1)].color, None);
+
+        let after = raster(&frame((7, 6)));
+        assert_eq!(changes(&before, &after), Some(((5, 5), (7, 6))));
+        assert_eq!(changes(&after, &after), None);
+    }
+}
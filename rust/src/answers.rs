@@ -0,0 +1,144 @@
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use common::Answer;
+
+use crate::report::Part;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Verification {
+    Pass,
+    Fail { expected: String },
+    Missing,
+}
+
+impl Verification {
+    /// Compares an answer with the expected one by value, so a number passes against its
+    /// text and ASCII art against the rows it shows.
+    pub fn of(expected: Option<String>, answer: Option<&Answer>) -> Self {
+        let expected = match expected {
+            Some(expected) => expected,
+            None => return Verification::Missing,
+        };
+        let matches = |answer: &Answer| match answer {
+            // Art keeps the leading spaces of its rows, text is compared trimmed
+            Answer::Art(_) => answer == &Answer::art(&expected),
+            _ => answer == &Answer::from(expected.as_str()),
+        };
+        match answer {
+            Some(answer) if matches(answer) => Verification::Pass,
+            _ => Verification::Fail { expected },
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Verification::Pass => "pass",
+            Verification::Fail { .. } => "fail",
+            Verification::Missing => "missing",
+        }
+    }
+}
+
+/// Expected answers, stored as `answers/{year}/answer_{day}_{part}.txt` next to the `inputs` folder.
+pub struct AnswerStore {
+    dir: PathBuf,
+}
+
+impl AnswerStore {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    /// The answers of a year in a profile's data directory.
+    pub fn in_data_dir(data_dir: &Path, year: u16) -> Self {
+        Self::new(data_dir.join("answers").join(year.to_string()))
+    }
+
+    fn path(&self, day: u8, part: Part) -> PathBuf {
+        self.dir.join(format!("answer_{}_{}.txt", day, part.id()))
+    }
+
+    pub fn expected(&self, day: u8, part: Part) -> Option<String> {
+        read_answer(&self.path(day, part))
+    }
+
+    pub fn verify(&self, day: u8, part: Part, answer: Option<&Answer>) -> Verification {
+        Verification::of(self.expected(day, part), answer)
+    }
+
+    /// Stores the answer unless one was recorded before or there is none; returns whether it was
+    /// written.
+    pub fn record(&self, day: u8, part: Part, answer: &Answer) -> io::Result<bool> {
+        let path = self.path(day, part);
+        let result = answer.get_result();
+        if path.exists() || result.trim().is_empty() {
+            return Ok(false);
+        }
+        fs::create_dir_all(&self.dir)?;
+        fs::write(path, result.trim_end_matches(['\r', '\n']))?;
+        Ok(true)
+    }
+}
+
+fn read_answer(path: &Path) -> Option<String> {
+    fs::read_to_string(path)
+        .ok()
+        .map(|data| data.trim_end().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{AnswerStore, Verification};
+    use crate::report::Part;
+    use common::Answer;
+
+    #[test]
+    fn record_and_verify() {
+        let dir = std::env::temp_dir().join(format!("aoc2022-answers-{}", std::process::id()));
+        let store = AnswerStore::new(&dir);
+
+        assert_eq!(
+            store.verify(1, Part::A, Some(&Answer::from(24000))),
+            Verification::Missing
+        );
+        assert!(store.record(1, Part::A, &Answer::from(24000)).unwrap());
+        assert!(!store.record(1, Part::A, &Answer::from(1)).unwrap());
+        assert!(!store.record(1, Part::B, &Answer::from("")).unwrap());
+        assert!(!store.record(1, Part::B, &Answer::none()).unwrap());
+        assert!(store.record(2, Part::B, &Answer::art("#..\n###")).unwrap());
+        assert_eq!(
+            store.verify(2, Part::B, Some(&Answer::art("\n#..\n###\n"))),
+            Verification::Pass
+        );
+        assert!(store.record(3, Part::B, &Answer::art(" #\n##")).unwrap());
+        assert_eq!(
+            store.verify(3, Part::B, Some(&Answer::art(" #\n##"))),
+            Verification::Pass
+        );
+        assert_ne!(
+            store.verify(3, Part::B, Some(&Answer::art("#\n##"))),
+            Verification::Pass
+        );
+
+        assert_eq!(
+            store.verify(1, Part::A, Some(&Answer::from(24000u64))),
+            Verification::Pass
+        );
+        assert_eq!(
+            store.verify(1, Part::A, Some(&Answer::from(1))),
+            Verification::Fail {
+                expected: String::from("24000")
+            }
+        );
+        assert_eq!(
+            store.verify(1, Part::A, None),
+            Verification::Fail {
+                expected: String::from("24000")
+            }
+        );
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
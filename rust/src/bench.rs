@@ -0,0 +1,277 @@
+use std::{
+    collections::BTreeMap,
+    fs, io,
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::input::InputSource;
+use crate::report::{csv_field, day_title, Format, Part};
+use crate::runner;
+use crate::years;
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Stats {
+    pub min: Duration,
+    pub median: Duration,
+    pub mean: Duration,
+    pub stddev: Duration,
+}
+
+impl Stats {
+    pub fn from_samples(samples: &[Duration]) -> Option<Self> {
+        if samples.is_empty() {
+            return None;
+        }
+        let mut sorted = samples.to_vec();
+        sorted.sort();
+        let count = sorted.len();
+        let median = (sorted[(count - 1) / 2] + sorted[count / 2]) / 2;
+        let mean = sorted.iter().sum::<Duration>() / count as u32;
+        let variance = sorted
+            .iter()
+            .map(|sample| (sample.as_secs_f64() - mean.as_secs_f64()).powi(2))
+            .sum::<f64>()
+            / count as f64;
+        Some(Self {
+            min: sorted[0],
+            median,
+            mean,
+            stddev: Duration::from_secs_f64(variance.sqrt()),
+        })
+    }
+}
+
+/// Statistics per day, input and part, keyed by the day number, the input name (`puzzle` for the
+/// puzzle input) and the part id (or `parse`) so it serializes as a readable map.
+pub type Baseline = BTreeMap<u8, BTreeMap<String, BTreeMap<String, Stats>>>;
+
+/// Name of the puzzle input in a [`Baseline`]
+const PUZZLE_INPUT: &str = "puzzle";
+
+pub struct Bench {
+    iterations: usize,
+    warmup: usize,
+    format: Format,
+    baseline: Option<Baseline>,
+    results: Baseline,
+}
+
+#[derive(Serialize)]
+struct Record<'a> {
+    day: u8,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    input: Option<&'a str>,
+    /// `parse`, `a` or `b`
+    part: &'static str,
+    iterations: usize,
+    min_ms: f64,
+    median_ms: f64,
+    mean_ms: f64,
+    stddev_ms: f64,
+    baseline_delta_pct: Option<f64>,
+}
+
+const CSV_HEADER: &str =
+    "day,part,iterations,min_ms,median_ms,mean_ms,stddev_ms,baseline_delta_pct,input";
+
+fn to_ms(duration: Duration) -> f64 {
+    duration.as_secs_f64() * 1000.0
+}
+
+/// A named baseline of a year in a profile's data directory.
+pub fn baseline_path(data_dir: &Path, year: u16, name: &str) -> PathBuf {
+    data_dir
+        .join("benchmarks")
+        .join(year.to_string())
+        .join(format!("{}.json", name))
+}
+
+pub fn load_baseline(path: &Path) -> Result<Baseline, String> {
+    let data = fs::read_to_string(path)
+        .map_err(|e| format!("Reading baseline {} failed: {}", path.display(), e))?;
+    serde_json::from_str(&data).map_err(|e| format!("Invalid baseline {}: {}", path.display(), e))
+}
+
+impl Bench {
+    pub fn new(
+        iterations: usize,
+        warmup: usize,
+        format: Format,
+        baseline: Option<Baseline>,
+    ) -> Self {
+        if format == Format::Csv {
+            println!("{}", CSV_HEADER);
+        }
+        Self {
+            iterations,
+            warmup,
+            format,
+            baseline,
+            results: Baseline::new(),
+        }
+    }
+
+    /// Runs the selected parts of the day, every sample on a fresh `Puzzle` so no state carries over.
+    /// Parsing is timed once per sample, separately from the parts. Every sample is isolated the
+    /// same way as a normal run, a sample that panics or times out ends the day's benchmark.
+    pub async fn run_day(
+        &mut self,
+        year: u16,
+        day: u8,
+        source: &InputSource,
+        parts: &[Part],
+        input: Arc<str>,
+        timeout: Option<Duration>,
+    ) {
+        let label = source.label();
+        let mut samples: BTreeMap<&'static str, Vec<Duration>> = BTreeMap::new();
+        for iteration in 0..(self.warmup + self.iterations) {
+            let (input, sample_parts) = (input.clone(), parts.to_vec());
+            let work = move || time_day(year, day, &sample_parts, &input);
+            let outcome = runner::isolate(work, timeout).await.0;
+            let times = match outcome
+                .map_err(|failure| failure.to_string())
+                .and_then(|r| r)
+            {
+                Ok(times) => times,
+                Err(error) => {
+                    eprintln!("{} failed! {}", day_title(day, label.as_deref()), error);
+                    return;
+                }
+            };
+            if iteration >= self.warmup {
+                for (step, time) in times {
+                    samples.entry(step).or_default().push(time);
+                }
+            }
+        }
+        let input = label.as_deref().unwrap_or(PUZZLE_INPUT);
+        for step in std::iter::once("parse").chain(parts.iter().map(Part::id)) {
+            if let Some(stats) = samples.get(step).and_then(|s| Stats::from_samples(s)) {
+                self.report(day, label.as_deref(), step, &stats);
+                self.results
+                    .entry(day)
+                    .or_default()
+                    .entry(input.to_string())
+                    .or_default()
+                    .insert(step.to_string(), stats);
+            }
+        }
+    }
+
+    fn report(&self, day: u8, label: Option<&str>, step: &'static str, stats: &Stats) {
+        let delta = self
+            .baseline
+            .as_ref()
+            .and_then(|baseline| {
+                baseline
+                    .get(&day)?
+                    .get(label.unwrap_or(PUZZLE_INPUT))?
+                    .get(step)
+            })
+            .map(|previous| {
+                (stats.median.as_secs_f64() / previous.median.as_secs_f64() - 1.0) * 100.0
+            });
+        match self.format {
+            Format::Text => println!(
+                "{}, {}: min {:.2?}, median {:.2?}, mean {:.2?}, stddev {:.2?} ({} runs){}",
+                day_title(day, label),
+                match step {
+                    "a" => "Part A",
+                    "b" => "Part B",
+                    _ => "Parse",
+                },
+                stats.min,
+                stats.median,
+                stats.mean,
+                stats.stddev,
+                self.iterations,
+                delta
+                    .map(|d| format!(", {:+.1}% vs baseline", d))
+                    .unwrap_or_default()
+            ),
+            Format::Json => {
+                let record = Record {
+                    day,
+                    input: label,
+                    part: step,
+                    iterations: self.iterations,
+                    min_ms: to_ms(stats.min),
+                    median_ms: to_ms(stats.median),
+                    mean_ms: to_ms(stats.mean),
+                    stddev_ms: to_ms(stats.stddev),
+                    baseline_delta_pct: delta,
+                };
+                println!(
+                    "{}",
+                    serde_json::to_string(&record).expect("Serializable record")
+                );
+            }
+            Format::Csv => println!(
+                "{},{},{},{:.3},{:.3},{:.3},{:.3},{},{}",
+                day,
+                step,
+                self.iterations,
+                to_ms(stats.min),
+                to_ms(stats.median),
+                to_ms(stats.mean),
+                to_ms(stats.stddev),
+                delta.map(|d| format!("{:.1}", d)).unwrap_or_default(),
+                label.map(csv_field).unwrap_or_default()
+            ),
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let data = serde_json::to_string_pretty(&self.results).expect("Serializable baseline");
+        fs::write(path, data)
+    }
+}
+
+/// Times parsing and the parts on a fresh puzzle; parts that fail are still timed, an input that
+/// does not parse is an error.
+fn time_day(
+    year: u16,
+    day: u8,
+    parts: &[Part],
+    input: &str,
+) -> Result<Vec<(&'static str, Duration)>, String> {
+    let mut solution = years::get_day(year, day)?;
+    let start = Instant::now();
+    let parsed = solution.parse(input).map_err(|error| error.to_string())?;
+    let mut times = vec![("parse", start.elapsed())];
+    for part in parts {
+        let start = Instant::now();
+        let _ = match part {
+            Part::A => solution.solve_a(&parsed),
+            Part::B => solution.solve_b(&parsed),
+        };
+        times.push((part.id(), start.elapsed()));
+    }
+    Ok(times)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::Stats;
+
+    #[test]
+    fn stats() {
+        let samples = [4, 1, 3, 2].map(Duration::from_millis);
+        let stats = Stats::from_samples(&samples).unwrap();
+        assert_eq!(stats.min, Duration::from_millis(1));
+        assert_eq!(stats.median, Duration::from_micros(2500));
+        assert_eq!(stats.mean, Duration::from_micros(2500));
+        assert_eq!(stats.stddev.as_micros(), 1118);
+        assert_eq!(Stats::from_samples(&[]), None);
+    }
+}
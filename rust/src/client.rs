@@ -0,0 +1,195 @@
+use std::time::Duration;
+
+use lazy_static::lazy_static;
+use regex::Regex;
+
+use crate::fetch::DEFAULT_USER_AGENT;
+use crate::report::Part;
+
+/// Response of the answer endpoint.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Outcome {
+    Correct,
+    TooHigh,
+    TooLow,
+    /// Wrong, without a hint in which direction
+    Wrong,
+    /// Submitted too soon after a previous answer; the time left to wait
+    RateLimited(Duration),
+    /// The part was solved before, or is not unlocked yet
+    WrongLevel,
+}
+
+impl Outcome {
+    pub fn id(&self) -> &'static str {
+        match self {
+            Outcome::Correct => "correct",
+            Outcome::TooHigh => "too-high",
+            Outcome::TooLow => "too-low",
+            Outcome::Wrong => "wrong",
+            Outcome::RateLimited(_) => "rate-limited",
+            Outcome::WrongLevel => "wrong-level",
+        }
+    }
+
+    pub fn from_id(id: &str) -> Option<Self> {
+        match id {
+            "correct" => Some(Outcome::Correct),
+            "too-high" => Some(Outcome::TooHigh),
+            "too-low" => Some(Outcome::TooLow),
+            "wrong" => Some(Outcome::Wrong),
+            "wrong-level" => Some(Outcome::WrongLevel),
+            _ => None,
+        }
+    }
+}
+
+impl std::fmt::Display for Outcome {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Outcome::Correct => write!(f, "correct"),
+            Outcome::TooHigh => write!(f, "wrong, too high"),
+            Outcome::TooLow => write!(f, "wrong, too low"),
+            Outcome::Wrong => write!(f, "wrong"),
+            Outcome::RateLimited(wait) => write!(f, "rate limited, wait {:?}", wait),
+            Outcome::WrongLevel => write!(f, "part already solved or not unlocked yet"),
+        }
+    }
+}
+
+/// HTTP access to the Advent of Code site; the base URL can point to a local stand-in server.
+pub struct AocClient {
+    base_url: String,
+    session: String,
+    http: reqwest::Client,
+}
+
+impl AocClient {
+    pub fn with_base_url(base_url: &str, session: String) -> Self {
+        Self {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            session,
+            http: http_client(DEFAULT_USER_AGENT),
+        }
+    }
+
+    pub fn user_agent(mut self, user_agent: &str) -> Self {
+        self.http = http_client(user_agent);
+        self
+    }
+
+    pub async fn submit(
+        &self,
+        year: u16,
+        day: u8,
+        part: Part,
+        answer: &str,
+    ) -> Result<Outcome, String> {
+        let url = format!("{}/{}/day/{}/answer", self.base_url, year, day);
+        let level = match part {
+            Part::A => "1",
+            Part::B => "2",
+        };
+        let response = self
+            .http
+            .post(url)
+            .header("cookie", format!("session={}", self.session))
+            .form(&[("level", level), ("answer", answer)])
+            .send()
+            .await
+            .map_err(|e| e.to_string())?;
+        let status = response.status();
+        let text = response.text().await.map_err(|e| e.to_string())?;
+        if !status.is_success() {
+            return Err(format!("Submitting answer failed: {}; {}", status, text));
+        }
+        parse_outcome(&text)
+    }
+}
+
+fn http_client(user_agent: &str) -> reqwest::Client {
+    reqwest::Client::builder()
+        .user_agent(user_agent)
+        .build()
+        .expect("HTTP client should build")
+}
+
+/// Reads the outcome from the `<article>` of the answer page.
+fn parse_outcome(page: &str) -> Result<Outcome, String> {
+    lazy_static! {
+        static ref WAIT: Regex = Regex::new(r"You have (?:(\d+)m )?(\d+)s left to wait").unwrap();
+    }
+    if page.contains("That's the right answer") {
+        return Ok(Outcome::Correct);
+    }
+    if page.contains("You gave an answer too recently") {
+        let wait = WAIT
+            .captures(page)
+            .map(|captures| {
+                let minutes: u64 = captures
+                    .get(1)
+                    .map_or(0, |m| m.as_str().parse().unwrap_or(0));
+                let seconds: u64 = captures[2].parse().unwrap_or(0);
+                Duration::from_secs(minutes * 60 + seconds)
+            })
+            .unwrap_or(Duration::from_secs(60));
+        return Ok(Outcome::RateLimited(wait));
+    }
+    if page.contains("That's not the right answer") {
+        if page.contains("your answer is too high") {
+            return Ok(Outcome::TooHigh);
+        }
+        if page.contains("your answer is too low") {
+            return Ok(Outcome::TooLow);
+        }
+        return Ok(Outcome::Wrong);
+    }
+    if page.contains("You don't seem to be solving the right level") {
+        return Ok(Outcome::WrongLevel);
+    }
+    Err(String::from(
+        "Unrecognized response to the submitted answer",
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::{parse_outcome, Outcome};
+
+    #[test]
+    fn outcomes() {
+        assert_eq!(
+            parse_outcome(
+                "<article><p>That's the right answer! You are one gold star closer.</p></article>"
+            ),
+            Ok(Outcome::Correct)
+        );
+        assert_eq!(
+            parse_outcome("<p>That's not the right answer; your answer is too high.</p>"),
+            Ok(Outcome::TooHigh)
+        );
+        assert_eq!(
+            parse_outcome("<p>That's not the right answer; your answer is too low.</p>"),
+            Ok(Outcome::TooLow)
+        );
+        assert_eq!(
+            parse_outcome("<p>That's not the right answer. If you're stuck, ...</p>"),
+            Ok(Outcome::Wrong)
+        );
+        assert_eq!(
+            parse_outcome("<p>You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 4m 35s left to wait.</p>"),
+            Ok(Outcome::RateLimited(Duration::from_secs(275)))
+        );
+        assert_eq!(
+            parse_outcome("<p>You gave an answer too recently. You have 12s left to wait.</p>"),
+            Ok(Outcome::RateLimited(Duration::from_secs(12)))
+        );
+        assert_eq!(
+            parse_outcome("<p>You don't seem to be solving the right level.  Did you already complete it?</p>"),
+            Ok(Outcome::WrongLevel)
+        );
+        assert!(parse_outcome("<html></html>").is_err());
+    }
+}
@@ -1,5 +1,6 @@
-use async_trait::async_trait;
-use common::{Answer, Solution};
+use common::{Answer, Example, SolveError, Solution};
+
+use crate::registry::Day;
 
 pub struct Puzzle {}
 
@@ -9,39 +10,52 @@ impl Default for Puzzle {
     }
 }
 
-#[async_trait]
+inventory::submit! {
+    Day::new::<Puzzle>(2022, 0, "", &[])
+}
+
 impl Solution for Puzzle {
-    async fn solve_a(&mut self, input: String) -> Result<Answer, String> {
-        Answer::from("").into()
+    type Parsed = String;
+
+    fn parse(&self, input: &str) -> Result<Self::Parsed, SolveError> {
+        Ok(input.to_string())
+    }
+
+    fn solve_a(&mut self, parsed: &Self::Parsed) -> Result<Answer, SolveError> {
+        Answer::none().into()
+    }
+
+    fn solve_b(&mut self, parsed: &Self::Parsed) -> Result<Answer, SolveError> {
+        Answer::none().into()
     }
 
-    async fn solve_b(&mut self, input: String) -> Result<Answer, String> {
-        Answer::from("").into()
+    fn examples(&self) -> Vec<Example> {
+        vec![Example::new(TEST_INPUT)]
     }
 }
 
+const TEST_INPUT: &str = "";
+
 #[cfg(test)]
 mod tests {
-    use super::Puzzle;
+    use super::{Puzzle, TEST_INPUT};
     use common::{Answer, Solution};
 
-    const TEST_INPUT: &str = "";
-
-    #[tokio::test]
-    async fn part_a() {
+    #[test]
+    fn part_a() {
         let mut puzzle = Puzzle::default();
         assert_eq!(
-            puzzle.solve_a(String::from(TEST_INPUT)).await,
-            Ok(Answer::from(""))
+            puzzle.solve_a(&puzzle.parse(TEST_INPUT).unwrap()),
+            Ok(Answer::none())
         )
     }
 
-    #[tokio::test]
-    async fn part_b() {
+    #[test]
+    fn part_b() {
         let mut puzzle = Puzzle::default();
         assert_eq!(
-            puzzle.solve_b(String::from(TEST_INPUT)).await,
-            Ok(Answer::from(""))
+            puzzle.solve_b(&puzzle.parse(TEST_INPUT).unwrap()),
+            Ok(Answer::none())
         )
     }
 }
\ No newline at end of file
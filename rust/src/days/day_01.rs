@@ -1,44 +1,61 @@
-use async_trait::async_trait;
-use common::{Answer, Solution};
+use common::{Answer, Example, Solution, SolveError};
+
+use crate::registry::Day;
 
 #[derive(Default)]
 pub struct Puzzle {}
 
-#[async_trait]
+inventory::submit! {
+    Day::new::<Puzzle>(2022, 1, "Calorie Counting", &["parsing"])
+}
+
 impl Solution for Puzzle {
-    async fn solve_a(&mut self, input: String) -> Result<Answer, String> {
-        let amount_per_elf: Vec<i32> = input_to_amount_per_elf(input);
-        Ok(amount_per_elf[0].into())
+    type Parsed = Vec<i32>;
+
+    fn parse(&self, input: &str) -> Result<Self::Parsed, SolveError> {
+        input_to_amount_per_elf(input)
     }
 
-    async fn solve_b(&mut self, input: String) -> Result<Answer, String> {
-        let amount_per_elf = input_to_amount_per_elf(input);
-        let top3 = &amount_per_elf[0..3];
+    fn solve_a(&mut self, amount_per_elf: &Self::Parsed) -> Result<Answer, SolveError> {
+        let most = amount_per_elf
+            .first()
+            .ok_or_else(|| SolveError::unsupported("No elves"))?;
+        Ok(most.into())
+    }
+
+    fn solve_b(&mut self, amount_per_elf: &Self::Parsed) -> Result<Answer, SolveError> {
+        let top3 = amount_per_elf
+            .get(0..3)
+            .ok_or_else(|| SolveError::unsupported("Fewer than 3 elves"))?;
         Ok(top3.iter().sum::<i32>().into())
     }
+
+    fn examples(&self) -> Vec<Example> {
+        vec![Example::new(TEST_INPUT).part_a(24000).part_b(45000)]
+    }
 }
 
-fn input_to_amount_per_elf(input: String) -> Vec<i32> {
+fn input_to_amount_per_elf(input: &str) -> Result<Vec<i32>, SolveError> {
     let elves: std::str::Split<&str> = input.split("\n\n");
     let mut amount_per_elf: Vec<i32> = elves
         .map(|e| {
-            e.trim()
-                .split('\n')
-                .filter_map(|v| v.parse::<i32>().ok())
+            e.lines()
+                .map(str::trim)
+                .filter(|line| !line.is_empty())
+                .map(|line| {
+                    line.parse::<i32>().map_err(|_| {
+                        SolveError::parse(input, line, "Calories are expected to be numbers")
+                    })
+                })
                 .sum()
         })
-        .collect();
+        .collect::<Result<_, _>>()?;
     amount_per_elf.sort();
     amount_per_elf.reverse();
-    amount_per_elf
+    Ok(amount_per_elf)
 }
 
-#[cfg(test)]
-mod tests {
-    use super::Puzzle;
-    use common::{Answer, Solution};
-
-    const TEST_INPUT: &str = "1000
+const TEST_INPUT: &str = "1000
 2000
 3000
 
@@ -53,21 +70,31 @@ mod tests {
 
 10000";
 
-    #[tokio::test]
-    async fn part_a() {
+#[cfg(test)]
+mod tests {
+    use super::{Puzzle, TEST_INPUT};
+    use common::{Answer, Solution};
+
+    #[test]
+    fn part_a() {
         let mut puzzle = Puzzle::default();
         assert_eq!(
-            puzzle.solve_a(String::from(TEST_INPUT)).await,
+            puzzle.solve_a(&puzzle.parse(TEST_INPUT).unwrap()),
             Ok(Answer::from(24000))
         )
     }
 
-    #[tokio::test]
-    async fn part_b() {
+    #[test]
+    fn part_b() {
         let mut puzzle = Puzzle::default();
         assert_eq!(
-            puzzle.solve_b(String::from(TEST_INPUT)).await,
+            puzzle.solve_b(&puzzle.parse(TEST_INPUT).unwrap()),
             Ok(Answer::from(45000))
         )
     }
+
+    #[test]
+    fn rejects_other_lines() {
+        assert!(Puzzle::default().parse("1000\n2000x\n\n3000").is_err());
+    }
 }
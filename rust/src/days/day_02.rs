@@ -1,28 +1,50 @@
-use async_trait::async_trait;
-use common::{Answer, Solution};
+use common::{Answer, Example, Solution, SolveError};
+
+use crate::registry::Day;
 
 #[derive(Default)]
 pub struct Puzzle {}
 
-#[async_trait]
+inventory::submit! {
+    Day::new::<Puzzle>(2022, 2, "Rock Paper Scissors", &["parsing"])
+}
+
 impl Solution for Puzzle {
-    async fn solve_a(&mut self, input: String) -> Result<Answer, String> {
-        let scores: Vec<i32> = input
+    /// The opponent's play and the second column of every round
+    type Parsed = Vec<(String, String)>;
+
+    fn parse(&self, input: &str) -> Result<Self::Parsed, SolveError> {
+        input
             .trim()
             .split('\n')
-            .filter_map(get_round_score)
+            .map(|round| {
+                round
+                    .split_once(' ')
+                    .map(|(opponent, second)| (opponent.to_string(), second.to_string()))
+                    .ok_or_else(|| SolveError::parse(input, round, "Expected two columns"))
+            })
+            .collect()
+    }
+
+    fn solve_a(&mut self, rounds: &Self::Parsed) -> Result<Answer, SolveError> {
+        let scores: Vec<i32> = rounds
+            .iter()
+            .filter_map(|(opponent, response)| get_round_score(opponent, response))
             .collect();
         Ok(scores.iter().sum::<i32>().into())
     }
 
-    async fn solve_b(&mut self, input: String) -> Result<Answer, String> {
-        let scores: Vec<i32> = input
-            .trim()
-            .split('\n')
-            .filter_map(get_round_score_by_result)
+    fn solve_b(&mut self, rounds: &Self::Parsed) -> Result<Answer, SolveError> {
+        let scores: Vec<i32> = rounds
+            .iter()
+            .filter_map(|(opponent, result)| get_round_score_by_result(opponent, result))
             .collect();
         Ok(scores.iter().sum::<i32>().into())
     }
+
+    fn examples(&self) -> Vec<Example> {
+        vec![Example::new(TEST_INPUT).part_a(15).part_b(12)]
+    }
 }
 
 const LOSS_SCORE: i32 = 0;
@@ -33,9 +55,7 @@ const ROCK: i32 = 1;
 const PAPER: i32 = 2;
 const SCISSORS: i32 = 3;
 
-fn get_round_score(round: &str) -> Option<i32> {
-    let (opponent_play, response_play) = round.split_once(' ')?;
-
+fn get_round_score(opponent_play: &str, response_play: &str) -> Option<i32> {
     let opponent = opponent_play_to_value(opponent_play)?;
     let response = response_play_to_value(response_play)?;
 
@@ -48,9 +68,7 @@ fn get_round_score(round: &str) -> Option<i32> {
     }
 }
 
-fn get_round_score_by_result(round: &str) -> Option<i32> {
-    let (opponent_play, result_play) = round.split_once(' ')?;
-
+fn get_round_score_by_result(opponent_play: &str, result_play: &str) -> Option<i32> {
     let opponent = opponent_play_to_value(opponent_play)?;
     let result = result_to_value(result_play)?;
 
@@ -88,27 +106,27 @@ fn result_to_value(play: &str) -> Option<i32> {
     }
 }
 
+const TEST_INPUT: &str = "A Y\nB X\nC Z\n";
+
 #[cfg(test)]
 mod tests {
-    use super::Puzzle;
+    use super::{Puzzle, TEST_INPUT};
     use common::{Answer, Solution};
 
-    const TEST_INPUT: &str = "A Y\nB X\nC Z\n";
-
-    #[tokio::test]
-    async fn part_a() {
+    #[test]
+    fn part_a() {
         let mut puzzle = Puzzle::default();
         assert_eq!(
-            puzzle.solve_a(String::from(TEST_INPUT)).await,
+            puzzle.solve_a(&puzzle.parse(TEST_INPUT).unwrap()),
             Ok(Answer::from(15))
         )
     }
 
-    #[tokio::test]
-    async fn part_b() {
+    #[test]
+    fn part_b() {
         let mut puzzle = Puzzle::default();
         assert_eq!(
-            puzzle.solve_b(String::from(TEST_INPUT)).await,
+            puzzle.solve_b(&puzzle.parse(TEST_INPUT).unwrap()),
             Ok(Answer::from(12))
         )
     }
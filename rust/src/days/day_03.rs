@@ -1,15 +1,25 @@
-use async_trait::async_trait;
-use common::{Answer, Solution};
+use common::{Answer, Example, Solution, SolveError};
+
+use crate::registry::Day;
 
 #[derive(Default)]
 pub struct Puzzle {}
 
-#[async_trait]
+inventory::submit! {
+    Day::new::<Puzzle>(2022, 3, "Rucksack Reorganization", &["sets"])
+}
+
 impl Solution for Puzzle {
-    async fn solve_a(&mut self, input: String) -> Result<Answer, String> {
-        let bags = input.trim().split('\n');
+    type Parsed = Vec<String>;
+
+    fn parse(&self, input: &str) -> Result<Self::Parsed, SolveError> {
+        check_items(input)?;
+        Ok(input.trim().split('\n').map(String::from).collect())
+    }
+
+    fn solve_a(&mut self, bags: &Self::Parsed) -> Result<Answer, SolveError> {
         let bags_with_compartments = bags
-            .to_owned()
+            .iter()
             .map(|r| r.split_at(r.len() / 2))
             .map(|bag| (bag.0.to_owned(), bag.1.to_owned()));
 
@@ -31,10 +41,14 @@ impl Solution for Puzzle {
         Ok(priority_sum.unwrap_or(0).into())
     }
 
-    async fn solve_b(&mut self, input: String) -> Result<Answer, String> {
-        let bags = input.trim().split('\n');
+    fn solve_b(&mut self, bags: &Self::Parsed) -> Result<Answer, SolveError> {
+        if bags.len() % 3 != 0 {
+            return Err(SolveError::unsupported(format!(
+                "{} rucksacks can not be split in groups of 3",
+                bags.len()
+            )));
+        }
         let group_priority_sum: Option<u32> = bags
-            .collect::<Vec<&str>>()
             .chunks(3)
             .map(|group| {
                 group[0].chars().find(|char| {
@@ -47,6 +61,23 @@ impl Solution for Puzzle {
 
         Ok(group_priority_sum.unwrap_or(0).into())
     }
+
+    fn examples(&self) -> Vec<Example> {
+        vec![Example::new(TEST_INPUT).part_a(157).part_b(70)]
+    }
+}
+
+fn check_items(input: &str) -> Result<(), SolveError> {
+    for line in input.trim().lines() {
+        if let Some((position, c)) = line.char_indices().find(|(_, c)| !c.is_ascii_alphabetic()) {
+            return Err(SolveError::parse(
+                input,
+                &line[position..position + c.len_utf8()],
+                "Items are expected to be letters",
+            ));
+        }
+    }
+    Ok(())
 }
 
 static ALPHABET_SIZE: u32 = 26;
@@ -63,33 +94,43 @@ fn get_item_priority(c: &Option<char>) -> Option<u32> {
     Some(char_value - char_upper_a + ALPHABET_SIZE + 1)
 }
 
-#[cfg(test)]
-mod tests {
-    use super::Puzzle;
-    use common::{Answer, Solution};
-
-    const TEST_INPUT: &str = "vJrwpWtwJgWrhcsFMMfFFhFp
+const TEST_INPUT: &str = "vJrwpWtwJgWrhcsFMMfFFhFp
 jqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL
 PmmdzqPrVvPwwTWBwg
 wMqvLMZHhHMvwLHjbvcjnnSBnvTQFn
 ttgJtRGJQctTZtZT
 CrZsJsPPZsGzwwsLwLmpwMDw";
 
-    #[tokio::test]
-    async fn part_a() {
+#[cfg(test)]
+mod tests {
+    use super::{Puzzle, TEST_INPUT};
+    use common::{Answer, Solution};
+
+    #[test]
+    fn part_a() {
         let mut puzzle = Puzzle::default();
         assert_eq!(
-            puzzle.solve_a(String::from(TEST_INPUT)).await,
+            puzzle.solve_a(&puzzle.parse(TEST_INPUT).unwrap()),
             Ok(Answer::from(157))
         )
     }
 
-    #[tokio::test]
-    async fn part_b() {
+    #[test]
+    fn part_b() {
         let mut puzzle = Puzzle::default();
         assert_eq!(
-            puzzle.solve_b(String::from(TEST_INPUT)).await,
+            puzzle.solve_b(&puzzle.parse(TEST_INPUT).unwrap()),
             Ok(Answer::from(70))
         )
     }
+
+    #[test]
+    fn rejects_other_characters() {
+        assert!(Puzzle::default()
+            .parse(
+                "abcé
+abcd"
+            )
+            .is_err());
+    }
 }
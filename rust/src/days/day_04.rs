@@ -1,35 +1,47 @@
-use async_trait::async_trait;
-use common::{Answer, Solution};
+use common::{Answer, Example, Solution, SolveError};
+
+use crate::registry::Day;
 
 #[derive(Default)]
 pub struct Puzzle {}
 
-#[async_trait]
+inventory::submit! {
+    Day::new::<Puzzle>(2022, 4, "Camp Cleanup", &["ranges"])
+}
+
 impl Solution for Puzzle {
-    async fn solve_a(&mut self, input: String) -> Result<Answer, String> {
-        let fully_overlapping_sections = input
-            .trim()
-            .split('\n')
-            .filter_map(row_to_ranges)
-            .filter(|(range_a, range_b)| range_a.contains(range_b) || range_b.contains(range_a))
-            .count();
-        Ok(fully_overlapping_sections.into())
-    }
+    /// The two section ranges of every pair
+    type Parsed = Vec<((i32, i32), (i32, i32))>;
 
-    async fn solve_b(&mut self, input: String) -> Result<Answer, String> {
-        let overlapping_sections = input
+    fn parse(&self, input: &str) -> Result<Self::Parsed, SolveError> {
+        input
             .trim()
             .split('\n')
-            .filter_map(|row| {
+            .map(|row| {
                 let ranges = row
                     .split(',')
                     .filter_map(parse_range)
                     .collect::<Vec<(i32, i32)>>();
                 match &ranges[..] {
-                    &[first, second, ..] => Some((first, second)),
-                    _ => None,
+                    &[first, second] => Ok((first, second)),
+                    _ => Err(SolveError::parse(input, row, "Expected two ranges")),
                 }
             })
+            .collect()
+    }
+
+    fn solve_a(&mut self, pairs: &Self::Parsed) -> Result<Answer, SolveError> {
+        let fully_overlapping_sections = pairs
+            .iter()
+            .map(row_to_ranges)
+            .filter(|(range_a, range_b)| range_a.contains(range_b) || range_b.contains(range_a))
+            .count();
+        Ok(fully_overlapping_sections.into())
+    }
+
+    fn solve_b(&mut self, pairs: &Self::Parsed) -> Result<Answer, SolveError> {
+        let overlapping_sections = pairs
+            .iter()
             .filter(
                 |((start_a, end_a), (start_b, end_b))| {
                     (start_a <= start_b) && (end_a >= start_b) || // left boundary
@@ -42,20 +54,18 @@ impl Solution for Puzzle {
 
         Ok(overlapping_sections.into())
     }
+
+    fn examples(&self) -> Vec<Example> {
+        vec![Example::new(TEST_INPUT).part_a(2).part_b(4)]
+    }
 }
 
-fn row_to_ranges(row: &str) -> Option<(String, String)> {
-    let values: Vec<String> = row
-        .split(',')
-        .filter_map(parse_range)
+fn row_to_ranges(&(first, second): &((i32, i32), (i32, i32))) -> (String, String) {
+    let [first, second] = [first, second]
         .map(|(start, end)| start..(end + 1))
         .map(|digits| digits.map(|d| d.to_string()).collect::<Vec<String>>())
-        .map(|str_digits| format!(",{},", str_digits.join(",")))
-        .collect();
-    match values {
-        val if val.len() == 2 => Some((val[0].to_owned(), val[1].to_owned())),
-        _ => None,
-    }
+        .map(|str_digits| format!(",{},", str_digits.join(",")));
+    (first, second)
 }
 
 fn parse_range(range_str: &str) -> Option<(i32, i32)> {
@@ -69,32 +79,32 @@ fn parse_range(range_str: &str) -> Option<(i32, i32)> {
     }
 }
 
-#[cfg(test)]
-mod tests {
-    use super::Puzzle;
-    use common::{Answer, Solution};
-
-    const TEST_INPUT: &str = "2-4,6-8
+const TEST_INPUT: &str = "2-4,6-8
 2-3,4-5
 5-7,7-9
 2-8,3-7
 6-6,4-6
 2-6,4-8";
 
-    #[tokio::test]
-    async fn part_a() {
+#[cfg(test)]
+mod tests {
+    use super::{Puzzle, TEST_INPUT};
+    use common::{Answer, Solution};
+
+    #[test]
+    fn part_a() {
         let mut puzzle = Puzzle::default();
         assert_eq!(
-            puzzle.solve_a(String::from(TEST_INPUT)).await,
+            puzzle.solve_a(&puzzle.parse(TEST_INPUT).unwrap()),
             Ok(Answer::from(2))
         )
     }
 
-    #[tokio::test]
-    async fn part_b() {
+    #[test]
+    fn part_b() {
         let mut puzzle = Puzzle::default();
         assert_eq!(
-            puzzle.solve_b(String::from(TEST_INPUT)).await,
+            puzzle.solve_b(&puzzle.parse(TEST_INPUT).unwrap()),
             Ok(Answer::from(4))
         )
     }
@@ -1,7 +1,8 @@
-use async_trait::async_trait;
-use common::{Answer, Solution};
+use common::{Answer, Example, Solution, SolveError};
 use regex::Regex;
 
+use crate::registry::Day;
+
 pub struct Puzzle {
     move_regex: Regex,
 }
@@ -15,101 +16,128 @@ impl Default for Puzzle {
     }
 }
 
-#[async_trait]
+inventory::submit! {
+    Day::new::<Puzzle>(2022, 5, "Supply Stacks", &["stacks", "parsing"])
+}
+
+/// Number of crates, source stack and target stack, both counting from 1
+type Move = (usize, usize, usize);
+
 impl Solution for Puzzle {
-    async fn solve_a(&mut self, input: String) -> Result<Answer, String> {
-        let (stacks_str, moves) = input
-            .split_once("\n\n")
-            .ok_or("Input not matching expected format")?;
-        let mut stacks = parse_stacks(stacks_str).ok_or("Expected to parse into stacks")?;
-        moves
+    type Parsed = (Vec<Vec<char>>, Vec<Move>);
+
+    fn parse(&self, input: &str) -> Result<Self::Parsed, SolveError> {
+        let (stacks_str, moves) = input.split_once("\n\n").ok_or_else(|| {
+            SolveError::parse(
+                input,
+                input,
+                "Expected stacks and moves separated by a blank line",
+            )
+        })?;
+        let stacks = parse_stacks(input, stacks_str)?;
+        let moves = moves
             .split('\n')
-            .try_for_each(|move_str| self.make_move(&mut stacks, move_str, false))
-            .map_err(|e| e.to_string())?;
-        let top_stacks: Vec<String> = stacks
-            .into_iter()
-            .map(|stack| stack[stack.len() - 1].to_string())
-            .collect();
-
-        Ok(top_stacks.join("").into())
+            .map(|move_str| self.parse_move(input, stacks.len(), move_str))
+            .collect::<Result<_, _>>()?;
+        Ok((stacks, moves))
     }
 
-    async fn solve_b(&mut self, input: String) -> Result<Answer, String> {
-        let (stacks_str, moves) = input
-            .split_once("\n\n")
-            .ok_or("Input not matching expected format")?;
-        let mut stacks = parse_stacks(stacks_str).ok_or("Expected to parse into stacks")?;
+    fn solve_a(&mut self, (stacks, moves): &Self::Parsed) -> Result<Answer, SolveError> {
+        let mut stacks = stacks.clone();
         moves
-            .split('\n')
-            .try_for_each(|move_str| self.make_move(&mut stacks, move_str, true))
-            .map_err(|e| e.to_string())?;
-        let top_stacks: Vec<String> = stacks
-            .into_iter()
-            .map(|stack| stack[stack.len() - 1].to_string())
-            .collect();
-
-        Ok(top_stacks.join("").into())
+            .iter()
+            .try_for_each(|step| make_move(&mut stacks, step, false))?;
+        Ok(top_crates(&stacks).into())
+    }
+
+    fn solve_b(&mut self, (stacks, moves): &Self::Parsed) -> Result<Answer, SolveError> {
+        let mut stacks = stacks.clone();
+        moves
+            .iter()
+            .try_for_each(|step| make_move(&mut stacks, step, true))?;
+        Ok(top_crates(&stacks).into())
+    }
+
+    fn examples(&self) -> Vec<Example> {
+        vec![Example::new(TEST_INPUT).part_a("CMZ").part_b("MCD")]
     }
 }
 
 impl Puzzle {
-    fn make_move(
+    fn parse_move(
         &self,
-        stacks: &mut [Vec<char>],
+        input: &str,
+        stack_count: usize,
         move_str: &str,
-        is_cratemover_9001: bool,
-    ) -> Result<(), Box<dyn std::error::Error>> {
+    ) -> Result<Move, SolveError> {
         let captures = self
             .move_regex
             .captures(move_str)
-            .ok_or(format!("Failed to match regex: {}", move_str))?;
-        let count: usize = captures.get(1).map_or("", |m| m.as_str()).parse()?;
-        let src: usize = captures.get(2).map_or("0", |m| m.as_str()).parse()?;
-        let target: usize = captures.get(3).map_or("0", |m| m.as_str()).parse()?;
-        if is_cratemover_9001 {
-            let stack_length = stacks[src - 1].len();
-            let values: Vec<_> = stacks[src - 1].drain((stack_length - count)..).collect();
-            stacks[target - 1].extend(values.iter());
-        } else {
-            for _ in 0..count {
-                let value = stacks[src - 1].pop().unwrap();
-                stacks[target - 1].push(value);
-            }
+            .ok_or_else(|| SolveError::parse(input, move_str, "Expected a move"))?;
+        let number = |index: usize| -> Result<usize, SolveError> {
+            let matched = captures.get(index).map_or("", |m| m.as_str());
+            matched
+                .parse()
+                .map_err(|_| SolveError::parse(input, move_str, "Number out of range"))
+        };
+        let (count, src, target) = (number(1)?, number(2)?, number(3)?);
+        if src == 0 || src > stack_count || target == 0 || target > stack_count {
+            return Err(SolveError::parse(input, move_str, "No such stack"));
         }
-        Ok(())
+        Ok((count, src, target))
+    }
+}
+
+fn make_move(
+    stacks: &mut [Vec<char>],
+    &(count, src, target): &Move,
+    is_cratemover_9001: bool,
+) -> Result<(), SolveError> {
+    let stack_length = stacks[src - 1].len();
+    if count > stack_length {
+        return Err(SolveError::unsupported(format!(
+            "Moving {} crates from stack {} holding {}",
+            count, src, stack_length
+        )));
     }
+    let values: Vec<_> = stacks[src - 1].drain((stack_length - count)..).collect();
+    if is_cratemover_9001 {
+        stacks[target - 1].extend(values.iter());
+    } else {
+        stacks[target - 1].extend(values.iter().rev());
+    }
+    Ok(())
+}
+
+fn top_crates(stacks: &[Vec<char>]) -> String {
+    stacks.iter().filter_map(|stack| stack.last()).collect()
 }
 
-fn parse_stacks(stacks_str: &str) -> Option<Vec<Vec<char>>> {
+fn parse_stacks(input: &str, stacks_str: &str) -> Result<Vec<Vec<char>>, SolveError> {
     let mut stacks: Vec<Vec<char>> = Vec::new();
     let mut rows: Vec<&str> = stacks_str.split('\n').collect();
 
-    let column_header = rows.pop()?;
+    let column_header = rows.pop().unwrap_or_default();
 
     for _char in column_header.chars().skip(1).step_by(4) {
         stacks.push(Vec::new());
     }
-    rows.into_iter().rev().for_each(|row| {
-        row.chars()
-            .skip(1)
-            .step_by(4)
-            .enumerate()
-            .for_each(|(i, c)| {
-                if !c.is_whitespace() {
-                    stacks[i].push(c);
-                }
-            });
-    });
-
-    Some(stacks)
-}
+    for row in rows.into_iter().rev() {
+        for (i, c) in row.chars().skip(1).step_by(4).enumerate() {
+            if c.is_whitespace() {
+                continue;
+            }
+            stacks
+                .get_mut(i)
+                .ok_or_else(|| SolveError::parse(input, row, "More crates than stacks"))?
+                .push(c);
+        }
+    }
 
-#[cfg(test)]
-mod tests {
-    use super::Puzzle;
-    use common::{Answer, Solution};
+    Ok(stacks)
+}
 
-    const TEST_INPUT: &str = "    [D]    
+const TEST_INPUT: &str = "    [D]    
 [N] [C]    
 [Z] [M] [P]
  1   2   3 
@@ -119,20 +147,25 @@ move 3 from 1 to 3
 move 2 from 2 to 1
 move 1 from 1 to 2";
 
-    #[tokio::test]
-    async fn part_a() {
+#[cfg(test)]
+mod tests {
+    use super::{Puzzle, TEST_INPUT};
+    use common::{Answer, Solution};
+
+    #[test]
+    fn part_a() {
         let mut puzzle = Puzzle::default();
         assert_eq!(
-            puzzle.solve_a(String::from(TEST_INPUT)).await,
+            puzzle.solve_a(&puzzle.parse(TEST_INPUT).unwrap()),
             Ok(Answer::from("CMZ"))
         )
     }
 
-    #[tokio::test]
-    async fn part_b() {
+    #[test]
+    fn part_b() {
         let mut puzzle = Puzzle::default();
         assert_eq!(
-            puzzle.solve_b(String::from(TEST_INPUT)).await,
+            puzzle.solve_b(&puzzle.parse(TEST_INPUT).unwrap()),
             Ok(Answer::from("MCD"))
         )
     }
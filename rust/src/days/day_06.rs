@@ -1,32 +1,60 @@
 use std::collections::HashSet;
 
-use async_trait::async_trait;
-use common::{Answer, Solution};
+use common::{Answer, Example, Solution, SolveError};
+
+use crate::registry::Day;
 
 #[derive(Default)]
 pub struct Puzzle {}
 
-fn locate_start_marker(input: String, length: usize) -> Option<usize> {
-    input
-        .chars()
-        .collect::<Vec<char>>()
+fn locate_start_marker(signal: &[char], length: usize) -> Option<usize> {
+    signal
         .windows(length)
         .position(|slice| HashSet::<&char>::from_iter(slice).len() == length)
         .map(|pos| pos + length)
 }
 
-#[async_trait]
+inventory::submit! {
+    Day::new::<Puzzle>(2022, 6, "Tuning Trouble", &["strings"])
+}
+
 impl Solution for Puzzle {
-    async fn solve_a(&mut self, input: String) -> Result<Answer, String> {
-        locate_start_marker(input, 4)
+    type Parsed = Vec<char>;
+
+    fn parse(&self, input: &str) -> Result<Self::Parsed, SolveError> {
+        Ok(input.trim().chars().collect())
+    }
+
+    fn solve_a(&mut self, signal: &Self::Parsed) -> Result<Answer, SolveError> {
+        locate_start_marker(signal, 4)
             .map(|v| v.into())
-            .ok_or(String::from("Not found"))
+            .ok_or_else(|| SolveError::unsupported("No start marker found"))
     }
 
-    async fn solve_b(&mut self, input: String) -> Result<Answer, String> {
-        locate_start_marker(input, 14)
+    fn solve_b(&mut self, signal: &Self::Parsed) -> Result<Answer, SolveError> {
+        locate_start_marker(signal, 14)
             .map(|v| v.into())
-            .ok_or(String::from("Not found"))
+            .ok_or_else(|| SolveError::unsupported("No start marker found"))
+    }
+
+    fn examples(&self) -> Vec<Example> {
+        vec![
+            Example::new("mjqjpqmgbljsphdztnvjfqwrcgsmlb")
+                .part_a(7)
+                .part_b(19),
+            Example::new("bvwbjplbgvbhsrlpgdmjqwftvncz")
+                .part_a(5)
+                .part_b(23),
+            Example::new("nppdvjthqldpwncqszvftbrmjlhg")
+                .part_a(6)
+                .part_b(23),
+            Example::new("nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg")
+                .part_a(10)
+                .part_b(29),
+            Example::new("zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw")
+                .part_a(11)
+                .part_b(26),
+        ]
     }
 }
 
@@ -35,66 +63,48 @@ mod tests {
     use super::Puzzle;
     use common::{Answer, Solution};
 
-    #[tokio::test]
-    async fn part_a() {
+    #[test]
+    fn part_a() {
         let mut puzzle = Puzzle::default();
         assert_eq!(
-            puzzle
-                .solve_a(String::from("bvwbjplbgvbhsrlpgdmjqwftvncz"))
-                .await,
+            puzzle.solve_a(&puzzle.parse("bvwbjplbgvbhsrlpgdmjqwftvncz").unwrap()),
             Ok(Answer::from(5))
         );
         assert_eq!(
-            puzzle
-                .solve_a(String::from("nppdvjthqldpwncqszvftbrmjlhg"))
-                .await,
+            puzzle.solve_a(&puzzle.parse("nppdvjthqldpwncqszvftbrmjlhg").unwrap()),
             Ok(Answer::from(6))
         );
         assert_eq!(
-            puzzle
-                .solve_a(String::from("nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg"))
-                .await,
+            puzzle.solve_a(&puzzle.parse("nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg").unwrap()),
             Ok(Answer::from(10))
         );
         assert_eq!(
-            puzzle
-                .solve_a(String::from("zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw"))
-                .await,
+            puzzle.solve_a(&puzzle.parse("zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw").unwrap()),
             Ok(Answer::from(11))
         );
     }
 
-    #[tokio::test]
-    async fn part_b() {
+    #[test]
+    fn part_b() {
         let mut puzzle = Puzzle::default();
         assert_eq!(
-            puzzle
-                .solve_b(String::from("mjqjpqmgbljsphdztnvjfqwrcgsmlb"))
-                .await,
+            puzzle.solve_b(&puzzle.parse("mjqjpqmgbljsphdztnvjfqwrcgsmlb").unwrap()),
             Ok(Answer::from(19))
         );
         assert_eq!(
-            puzzle
-                .solve_b(String::from("bvwbjplbgvbhsrlpgdmjqwftvncz"))
-                .await,
+            puzzle.solve_b(&puzzle.parse("bvwbjplbgvbhsrlpgdmjqwftvncz").unwrap()),
             Ok(Answer::from(23))
         );
         assert_eq!(
-            puzzle
-                .solve_b(String::from("nppdvjthqldpwncqszvftbrmjlhg"))
-                .await,
+            puzzle.solve_b(&puzzle.parse("nppdvjthqldpwncqszvftbrmjlhg").unwrap()),
             Ok(Answer::from(23))
         );
         assert_eq!(
-            puzzle
-                .solve_b(String::from("nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg"))
-                .await,
+            puzzle.solve_b(&puzzle.parse("nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg").unwrap()),
             Ok(Answer::from(29))
         );
         assert_eq!(
-            puzzle
-                .solve_b(String::from("zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw"))
-                .await,
+            puzzle.solve_b(&puzzle.parse("zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw").unwrap()),
             Ok(Answer::from(26))
         );
     }
@@ -1,11 +1,11 @@
 use std::{
     cell::RefCell,
-    cmp::min,
     rc::{Rc, Weak},
 };
 
-use async_trait::async_trait;
-use common::{Answer, Solution};
+use common::{Answer, Example, Solution, SolveError};
+
+use crate::registry::Day;
 
 #[derive(Default)]
 pub struct Puzzle {}
@@ -74,97 +74,91 @@ fn switch_directory(
     cwd
 }
 
-fn read_dir_listing(cwd: Rc<RefCell<Dir>>, cmd: &str) {
-    if cmd.starts_with("dir ") {
-        let dir_name = cmd
-            .split_once(' ')
-            .expect("Dir should consist out of 2 parts")
-            .1;
+fn read_dir_listing(input: &str, cwd: Rc<RefCell<Dir>>, cmd: &str) -> Result<(), SolveError> {
+    if let Some(dir_name) = cmd.strip_prefix("dir ") {
         let child_dir = Dir::from_name(dir_name);
         add_dir_to_parent(cwd, child_dir);
     } else {
         let (file_size, _) = cmd
             .split_once(' ')
-            .expect("File should consist out of 2 bits");
-        {
-            (*cwd).borrow_mut().files.push(File {
-                size: file_size.parse().expect("Invalid file_size"),
-            });
-        }
+            .ok_or_else(|| SolveError::parse(input, cmd, "Expected a file size and name"))?;
+        let size = file_size
+            .parse()
+            .map_err(|_| SolveError::parse(input, file_size, "Invalid file size"))?;
+        (*cwd).borrow_mut().files.push(File { size });
     }
+    Ok(())
 }
 
-fn read_terminal_output(root: Rc<RefCell<Dir>>, input: String) {
+fn read_terminal_output(root: Rc<RefCell<Dir>>, input: &str) -> Result<(), SolveError> {
     let mut cwd = root.clone();
-    input.split('\n').for_each(|cmd| {
+    for cmd in input.split('\n') {
         if cmd.starts_with('$') {
             let cmd = cmd
                 .split_once(' ')
-                .expect("Command should consist at least out of 2 parts")
+                .ok_or_else(|| SolveError::parse(input, cmd, "Expected a command"))?
                 .1;
             if cmd.starts_with("cd") {
-                let new_dir = cmd.split_once(' ').expect("Cd command missing directory").1;
+                let new_dir = cmd
+                    .split_once(' ')
+                    .ok_or_else(|| SolveError::parse(input, cmd, "Cd command missing directory"))?
+                    .1;
                 cwd = switch_directory(cwd.clone(), root.clone(), new_dir);
             }
         } else {
-            read_dir_listing(cwd.clone(), cmd);
+            read_dir_listing(input, cwd.clone(), cmd)?;
         }
-    });
+    }
+    Ok(())
 }
 
-fn sum_all_dirs_with_size_of(size: u64, dir: Rc<RefCell<Dir>>) -> u64 {
-    let mut sum = 0;
-    let dir_size = dir.borrow().get_dir_size();
-    if dir_size <= size {
-        sum += dir_size
+/// Sizes of the directory and everything below it, the directory itself first.
+fn collect_dir_sizes(dir: &Rc<RefCell<Dir>>, sizes: &mut Vec<u64>) {
+    sizes.push(dir.borrow().get_dir_size());
+    for sub_dir in dir.borrow().dirs.iter() {
+        collect_dir_sizes(sub_dir, sizes);
     }
-    sum += dir.borrow().dirs.iter().fold(0, |acc, sub_dir| {
-        acc + sum_all_dirs_with_size_of(size, sub_dir.clone())
-    });
-    sum
 }
 
-fn find_dir_with_closest_size_to(size: u64, dir: Rc<RefCell<Dir>>) -> u64 {
-    let dir_size = dir.borrow().get_dir_size();
-    if dir_size < size {
-        return u64::MAX;
-    }
-    let smallest_size = dir
-        .borrow()
-        .dirs
-        .iter()
-        .map(|subdir| find_dir_with_closest_size_to(size, subdir.clone()))
-        .min()
-        .unwrap_or(u64::MAX);
-    min(dir_size, smallest_size)
+inventory::submit! {
+    Day::new::<Puzzle>(2022, 7, "No Space Left On Device", &["tree"])
 }
 
-#[async_trait]
 impl Solution for Puzzle {
-    async fn solve_a(&mut self, input: String) -> Result<Answer, String> {
+    /// Size of every directory, the root first
+    type Parsed = Vec<u64>;
+
+    fn parse(&self, input: &str) -> Result<Self::Parsed, SolveError> {
         let root = Rc::new(RefCell::new(Dir::root()));
-        read_terminal_output(root.clone(), input);
-        Ok(Answer::from(sum_all_dirs_with_size_of(100_000, root)))
+        read_terminal_output(root.clone(), input)?;
+        let mut sizes = vec![];
+        collect_dir_sizes(&root, &mut sizes);
+        Ok(sizes)
     }
 
-    async fn solve_b(&mut self, input: String) -> Result<Answer, String> {
-        let root = Rc::new(RefCell::new(Dir::root()));
-        read_terminal_output(root.clone(), input);
-        let space_free = 70_000_000 - root.borrow().get_dir_size();
+    fn solve_a(&mut self, dir_sizes: &Self::Parsed) -> Result<Answer, SolveError> {
+        let sum: u64 = dir_sizes.iter().filter(|size| **size <= 100_000).sum();
+        Ok(Answer::from(sum))
+    }
+
+    fn solve_b(&mut self, dir_sizes: &Self::Parsed) -> Result<Answer, SolveError> {
+        let space_free = 70_000_000 - dir_sizes[0];
         let space_required = 30_000_000 - space_free;
-        Ok(Answer::from(find_dir_with_closest_size_to(
-            space_required,
-            root,
-        )))
+        let closest = dir_sizes
+            .iter()
+            .filter(|size| **size >= space_required)
+            .min()
+            .copied()
+            .unwrap_or(u64::MAX);
+        Ok(Answer::from(closest))
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::Puzzle;
-    use common::{Answer, Solution};
+    fn examples(&self) -> Vec<Example> {
+        vec![Example::new(TEST_INPUT).part_a(95437).part_b(24933642)]
+    }
+}
 
-    const TEST_INPUT: &str = "$ cd /
+const TEST_INPUT: &str = "$ cd /
 $ ls
 dir a
 14848514 b.txt
@@ -188,20 +182,25 @@ $ ls
 5626152 d.ext
 7214296 k";
 
-    #[tokio::test]
-    async fn part_a() {
+#[cfg(test)]
+mod tests {
+    use super::{Puzzle, TEST_INPUT};
+    use common::{Answer, Solution};
+
+    #[test]
+    fn part_a() {
         let mut puzzle = Puzzle::default();
         assert_eq!(
-            puzzle.solve_a(String::from(TEST_INPUT.trim())).await,
+            puzzle.solve_a(&puzzle.parse(TEST_INPUT.trim()).unwrap()),
             Ok(Answer::from(95437))
         )
     }
 
-    #[tokio::test]
-    async fn part_b() {
+    #[test]
+    fn part_b() {
         let mut puzzle = Puzzle::default();
         assert_eq!(
-            puzzle.solve_b(String::from(TEST_INPUT.trim())).await,
+            puzzle.solve_b(&puzzle.parse(TEST_INPUT.trim()).unwrap()),
             Ok(Answer::from(24933642))
         )
     }
@@ -1,6 +1,6 @@
-use async_trait::async_trait;
+use common::{Answer, Example, Grid, Solution, SolveError};
 
-use common::{Answer, Solution};
+use crate::registry::Day;
 
 #[derive(Default)]
 pub struct Puzzle {}
@@ -26,28 +26,9 @@ fn set_visibility_for_row(row: &[u32], visibility_row: &mut [bool]) {
     set_visibility_for_range(row, visibility_row, (0..row.len()).rev(), max_height);
 }
 
-fn transpose<T>(value: &Vec<Vec<T>>) -> Vec<Vec<T>>
-where
-    T: Clone,
-{
-    if value.is_empty() {
-        return vec![];
-    }
-    let row_count = value[0].len();
-    let mut output: Vec<Vec<T>> = vec![];
-    for col_index in 0..row_count {
-        let mut row = vec![];
-        for input_row in value {
-            row.push(input_row[col_index].to_owned());
-        }
-        output.push(row);
-    }
-    output
-}
-
-fn get_score(grid: &[Vec<u32>], row_index: usize, col_index: usize, height: u32) -> usize {
-    let row = &grid[row_index];
-    let column: Vec<u32> = grid.iter().map(|r| r[col_index]).collect();
+fn get_score(grid: &Grid<u32>, row_index: usize, col_index: usize, height: u32) -> usize {
+    let row = grid.row(row_index);
+    let column: Vec<u32> = grid.column(col_index).copied().collect();
     let (left, right) = row.split_at(col_index);
     let (top, bottom) = column.split_at(row_index);
     fn find_sight_line_incrementing(input: &[u32], height: u32) -> usize {
@@ -72,90 +53,72 @@ fn get_score(grid: &[Vec<u32>], row_index: usize, col_index: usize, height: u32)
         * find_sight_line_decrementing(top, height)
 }
 
-#[async_trait]
+inventory::submit! {
+    Day::new::<Puzzle>(2022, 8, "Treetop Tree House", &["grid"])
+}
+
 impl Solution for Puzzle {
-    async fn solve_a(&mut self, input: String) -> Result<Answer, String> {
-        let grid: Vec<Vec<u32>> = input
-            .trim()
-            .split('\n')
-            .map(|line| {
-                line.chars()
-                    .map(|c| c.to_digit(10).expect("Invalid cell found"))
-                    .collect()
-            })
-            .collect();
-        let mut visible: Vec<Vec<bool>> = grid
-            .iter()
-            .map(|row| row.iter().map(|_| false).collect())
-            .collect();
-        grid.iter()
-            .enumerate()
-            .for_each(|(index, row)| set_visibility_for_row(row, &mut visible[index]));
-        let grid = transpose(&grid);
-        let mut visible = transpose(&visible);
-        grid.iter()
-            .enumerate()
-            .for_each(|(index, row)| set_visibility_for_row(row, &mut visible[index]));
+    type Parsed = Grid<u32>;
+
+    fn parse(&self, input: &str) -> Result<Self::Parsed, SolveError> {
+        Grid::parse(input.trim(), |c| c.to_digit(10).ok_or("Invalid cell found"))
+    }
+
+    fn solve_a(&mut self, grid: &Self::Parsed) -> Result<Answer, SolveError> {
+        let mut visible = grid.map(|_| false);
+        for index in 0..grid.height() {
+            set_visibility_for_row(grid.row(index), visible.row_mut(index));
+        }
+        let grid = grid.transposed();
+        let mut visible = visible.transposed();
+        for index in 0..grid.height() {
+            set_visibility_for_row(grid.row(index), visible.row_mut(index));
+        }
         Ok(Answer::from(
-            visible
-                .iter()
-                .flatten()
-                .fold(0, |acc, cell| acc + if *cell { 1 } else { 0 }),
+            visible.iter().filter(|(_, cell)| **cell).count(),
         ))
     }
 
-    async fn solve_b(&mut self, input: String) -> Result<Answer, String> {
-        let grid: Vec<Vec<u32>> = input
-            .trim()
-            .split('\n')
-            .map(|line| {
-                line.chars()
-                    .map(|c| c.to_digit(10).expect("Invalid cell found"))
-                    .collect()
-            })
-            .collect();
+    fn solve_b(&mut self, grid: &Self::Parsed) -> Result<Answer, SolveError> {
         let high_score = grid
             .iter()
-            .enumerate()
-            .map(|(row_index, row)| {
-                row.iter()
-                    .enumerate()
-                    .map(|(column_index, height)| {
-                        get_score(&grid, row_index, column_index, *height)
-                    })
-                    .max()
-                    .unwrap_or(0)
+            .map(|((column_index, row_index), height)| {
+                get_score(grid, row_index as usize, column_index as usize, *height)
             })
             .max();
-        Ok(Answer::from(high_score.expect("High score")))
+        Ok(Answer::from(high_score.unwrap_or(0)))
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::Puzzle;
-    use common::{Answer, Solution};
+    fn examples(&self) -> Vec<Example> {
+        vec![Example::new(TEST_INPUT).part_a(21).part_b(8)]
+    }
+}
 
-    const TEST_INPUT: &str = "30373
+const TEST_INPUT: &str = "30373
 25512
 65332
 33549
 35390";
 
-    #[tokio::test]
-    async fn part_a() {
+#[cfg(test)]
+mod tests {
+    use super::{Puzzle, TEST_INPUT};
+    use common::{Answer, Solution};
+
+    #[test]
+    fn part_a() {
         let mut puzzle = Puzzle::default();
         assert_eq!(
-            puzzle.solve_a(String::from(TEST_INPUT)).await,
+            puzzle.solve_a(&puzzle.parse(TEST_INPUT).unwrap()),
             Ok(Answer::from(21))
         )
     }
 
-    #[tokio::test]
-    async fn part_b() {
+    #[test]
+    fn part_b() {
         let mut puzzle = Puzzle::default();
         assert_eq!(
-            puzzle.solve_b(String::from(TEST_INPUT)).await,
+            puzzle.solve_b(&puzzle.parse(TEST_INPUT).unwrap()),
             Ok(Answer::from(8))
         )
     }
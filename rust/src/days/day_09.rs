@@ -1,47 +1,76 @@
-use std::collections::HashMap;
+use common::{Answer, Color, Example, Point, Renderer, Solution, SolveError, SparseGrid};
 
-use async_trait::async_trait;
-use common::{Answer, Solution};
+use crate::registry::Day;
 
-pub struct Puzzle {}
+pub struct Puzzle {
+    renderer: Renderer,
+}
 
 impl Default for Puzzle {
     fn default() -> Self {
-        Self { }
+        Self {
+            renderer: Renderer::default(),
+        }
     }
 }
 
-type Coord = (i32, i32);
-type Grid = HashMap<Coord, bool>;
+impl Puzzle {
+    /// Draws the cells the tail visited and the knots of the rope after a move.
+    fn render(&self, rope: &Rope, grid: &Grid, moves: usize) {
+        if !self.renderer.is_enabled() {
+            return;
+        }
+        // Up is positive in the moves and negative in frames
+        let point = |(x, y): Coord| (x, -y);
+        let knots: Vec<_> = std::iter::once(rope.head)
+            .chain(rope.rope.iter().copied())
+            .map(point)
+            .collect();
+        let top_left = grid
+            .iter()
+            .map(|(cell, _)| point(cell))
+            .chain(knots.iter().copied())
+            .fold((0, 0), |(x, y), (cell_x, cell_y)| {
+                (x.min(cell_x), y.min(cell_y))
+            });
+        self.renderer
+            .points(grid.iter().map(|(cell, _)| point(cell)), Color::GRAY);
+        self.renderer.path(knots, Color::RED);
+        self.renderer.text(
+            (top_left.0, top_left.1 - 3),
+            format!("moves: {}, visited: {}", moves, grid.len()),
+            Color::BLACK,
+        );
+        self.renderer.flush();
+    }
+}
+
+type Coord = Point;
+/// The cells the tail visited
+type Grid = SparseGrid<bool>;
 
 struct Rope {
     head: Coord,
-    rope: Vec<Coord>
+    rope: Vec<Coord>,
 }
 
 impl Rope {
     pub fn with_length(length: usize) -> Self {
         Self {
-            head: (0 ,0),
-            rope: vec![(0, 0); length - 1]
+            head: (0, 0),
+            rope: vec![(0, 0); length - 1],
         }
     }
     pub fn visit_cell(&self, grid: &mut Grid) {
         let tail = self.rope.last().expect("Rope with no length?");
-        grid.insert(tail.to_owned(), true);
+        grid.set(*tail, true);
     }
-    pub fn move_in_direction(&mut self, direction: &str) {
-        match direction {
-            "U" => self.head = (self.head.0, self.head.1 + 1),
-            "D" => self.head = (self.head.0, self.head.1 - 1),
-            "L" => self.head = (self.head.0 - 1, self.head.1),
-            "R" => self.head = (self.head.0 + 1, self.head.1),
-            _ => panic!("Unexpected direction")
-        }
-        let mut prev = self.head.clone();
+    pub fn move_in_direction(&mut self, direction: Coord) {
+        self.head = (self.head.0 + direction.0, self.head.1 + direction.1);
+        let mut prev = self.head;
         for current in self.rope.iter_mut() {
-            if prev == current.clone() {
-                prev = current.clone();
+            if prev == *current {
+                prev = *current;
                 continue;
             }
             let delta_x = if prev.0 < current.0 { -1 } else { 1 };
@@ -53,43 +82,90 @@ impl Rope {
             } else if (prev.0 - current.0).abs() >= 2 {
                 *current = (current.0 + delta_x, prev.1);
             }
-            prev = current.clone();
+            prev = *current;
         }
     }
 }
 
-fn move_rope(rope: &mut Rope, grid: &mut Grid, mv: &str) {
-    let (direction, distance_str) = mv.split_once(' ').expect("Invalid move command");
-    let distance: u32 = distance_str.parse().expect("Invalid move distance");
+fn parse_move(input: &str, mv: &str) -> Result<(Coord, u32), SolveError> {
+    let (direction_str, distance_str) = mv
+        .split_once(' ')
+        .ok_or_else(|| SolveError::parse(input, mv, "Invalid move command"))?;
+    let direction = match direction_str {
+        "U" => (0, 1),
+        "D" => (0, -1),
+        "L" => (-1, 0),
+        "R" => (1, 0),
+        _ => {
+            return Err(SolveError::parse(
+                input,
+                direction_str,
+                "Unexpected direction",
+            ))
+        }
+    };
+    let distance: u32 = distance_str
+        .parse()
+        .map_err(|_| SolveError::parse(input, distance_str, "Invalid move distance"))?;
+    Ok((direction, distance))
+}
+
+fn move_rope(rope: &mut Rope, grid: &mut Grid, &(direction, distance): &(Coord, u32)) {
     for _ in 0..distance {
         rope.move_in_direction(direction);
         rope.visit_cell(grid);
     }
 }
 
-#[async_trait]
+inventory::submit! {
+    Day::new::<Puzzle>(2022, 9, "Rope Bridge", &["grid", "simulation"])
+}
+
 impl Solution for Puzzle {
-    async fn solve_a(&mut self, input: String) -> Result<Answer, String> {
+    /// Direction and distance of every move
+    type Parsed = Vec<(Coord, u32)>;
+
+    fn parse(&self, input: &str) -> Result<Self::Parsed, SolveError> {
+        input
+            .trim()
+            .split('\n')
+            .map(|mv| parse_move(input, mv))
+            .collect()
+    }
+
+    fn solve_a(&mut self, moves: &Self::Parsed) -> Result<Answer, SolveError> {
         let mut grid = Grid::new();
         let mut rope = Rope::with_length(2);
-        input.trim().split('\n').for_each(|mv| move_rope(&mut rope, &mut grid, mv));
+        for (index, mv) in moves.iter().enumerate() {
+            move_rope(&mut rope, &mut grid, mv);
+            self.render(&rope, &grid, index + 1);
+        }
         Ok(Answer::from(grid.len()))
     }
 
-    async fn solve_b(&mut self, input: String) -> Result<Answer, String> {
+    fn solve_b(&mut self, moves: &Self::Parsed) -> Result<Answer, SolveError> {
         let mut grid = Grid::new();
         let mut rope = Rope::with_length(10);
-        input.trim().split('\n').for_each(|mv| move_rope(&mut rope, &mut grid, mv));
+        for (index, mv) in moves.iter().enumerate() {
+            move_rope(&mut rope, &mut grid, mv);
+            self.render(&rope, &grid, index + 1);
+        }
         Ok(Answer::from(grid.len()))
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::Puzzle;
-    use common::{Answer, Solution};
+    fn set_renderer(&mut self, renderer: Renderer) {
+        self.renderer = renderer;
+    }
+
+    fn examples(&self) -> Vec<Example> {
+        vec![
+            Example::new(TEST_INPUT_A).part_a(13).part_b(1),
+            Example::new(TEST_INPUT_B).part_b(36),
+        ]
+    }
+}
 
-    const TEST_INPUT_A: &str = "R 4
+const TEST_INPUT_A: &str = "R 4
 U 4
 L 3
 D 1
@@ -98,16 +174,7 @@ D 1
 L 5
 R 2";
 
-    #[tokio::test]
-    async fn part_a() {
-        let mut puzzle = Puzzle::default();
-        assert_eq!(
-            puzzle.solve_a(String::from(TEST_INPUT_A)).await,
-            Ok(Answer::from(13))
-        )
-    }
-
-    const TEST_INPUT_B: &str = "R 5
+const TEST_INPUT_B: &str = "R 5
 U 8
 L 8
 D 3
@@ -116,12 +183,26 @@ D 10
 L 25
 U 20";
 
-    #[tokio::test]
-    async fn part_b() {
+#[cfg(test)]
+mod tests {
+    use super::{Puzzle, TEST_INPUT_A, TEST_INPUT_B};
+    use common::{Answer, Solution};
+
+    #[test]
+    fn part_a() {
+        let mut puzzle = Puzzle::default();
+        assert_eq!(
+            puzzle.solve_a(&puzzle.parse(TEST_INPUT_A).unwrap()),
+            Ok(Answer::from(13))
+        )
+    }
+
+    #[test]
+    fn part_b() {
         let mut puzzle = Puzzle::default();
         assert_eq!(
-            puzzle.solve_b(String::from(TEST_INPUT_B)).await,
+            puzzle.solve_b(&puzzle.parse(TEST_INPUT_B).unwrap()),
             Ok(Answer::from(36))
         )
     }
-}
\ No newline at end of file
+}
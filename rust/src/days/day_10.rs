@@ -1,40 +1,52 @@
-use async_trait::async_trait;
-use common::{Answer, Solution};
+use common::{Answer, Example, Solution, SolveError};
+
+use crate::registry::Day;
 
 pub struct Puzzle {}
 
 impl Default for Puzzle {
     fn default() -> Self {
-        Self { }
+        Self {}
     }
 }
 
 static SCREEN_WIDTH: i32 = 40;
 
-fn run_program(input: String) -> Vec<i32> {
+fn run_program(input: &str) -> Result<Vec<i32>, SolveError> {
     let mut history = vec![1];
+    let mut x = 1;
 
-    input.lines().for_each(|line| {
-        let mut parts: Vec<&str> = line.split(" ").collect();
-        let command = parts.remove(0);
-        history.push(*history.last().unwrap());
+    for line in input.lines() {
+        let (command, argument) = line.split_once(' ').unwrap_or((line, ""));
+        history.push(x);
         match command {
-            "noop" => return,
+            "noop" => continue,
             "addx" => {
-                history.push(
-                    history.last().unwrap() + parts[0].parse::<i32>().expect("invalid integer")
-                );
-            },
-            _ => panic!("Unknown command: {}", command)
+                x += argument
+                    .parse::<i32>()
+                    .map_err(|_| SolveError::parse(input, argument, "Invalid integer"))?;
+                history.push(x);
+            }
+            _ => return Err(SolveError::parse(input, command, "Unknown command")),
         }
-    });
-    history
+    }
+    Ok(history)
+}
+
+inventory::submit! {
+    Day::new::<Puzzle>(2022, 10, "Cathode-Ray Tube", &["simulation", "ascii-art"])
 }
 
-#[async_trait]
 impl Solution for Puzzle {
-    async fn solve_a(&mut self, input: String) -> Result<Answer, String> {
-        let strength: i32 = run_program(input)
+    /// Value of the X register during every cycle
+    type Parsed = Vec<i32>;
+
+    fn parse(&self, input: &str) -> Result<Self::Parsed, SolveError> {
+        run_program(input)
+    }
+
+    fn solve_a(&mut self, history: &Self::Parsed) -> Result<Answer, SolveError> {
+        let strength: i32 = history
             .iter()
             .enumerate()
             .filter(|(index, _)| (((*index as i32) + 1) % SCREEN_WIDTH) == (SCREEN_WIDTH / 2))
@@ -43,8 +55,8 @@ impl Solution for Puzzle {
         Ok(Answer::from(strength))
     }
 
-    async fn solve_b(&mut self, input: String) -> Result<Answer, String> {
-        let output: String = run_program(input)
+    fn solve_b(&mut self, history: &Self::Parsed) -> Result<Answer, SolveError> {
+        let lines = history
             .iter()
             .enumerate()
             .map(|(index, value)| (((index as i32) % SCREEN_WIDTH) - value).abs() < 2)
@@ -52,18 +64,20 @@ impl Solution for Puzzle {
             .collect::<Vec<_>>()
             .chunks(SCREEN_WIDTH as usize)
             .map(|line| line.join(""))
-            .collect::<Vec<_>>()[0..6]
+            .collect::<Vec<_>>();
+        let output = lines
+            .get(0..6)
+            .ok_or_else(|| SolveError::unsupported("Program too short to draw 6 lines"))?
             .join("\n");
-        Ok(Answer::from(format!("\n{}", output)))
+        Ok(Answer::art(output))
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::Puzzle;
-    use common::{Answer, Solution};
+    fn examples(&self) -> Vec<Example> {
+        vec![Example::new(TEST_INPUT).part_a(13140).part_b(TEST_OUTPUT_B)]
+    }
+}
 
-    const TEST_INPUT: &str = "addx 15
+const TEST_INPUT: &str = "addx 15
 addx -11
 addx 6
 addx -3
@@ -210,27 +224,34 @@ noop
 noop
 noop";
 
-    #[tokio::test]
-    async fn part_a() {
+const TEST_OUTPUT_B: &str = "
+##..##..##..##..##..##..##..##..##..##..
+###...###...###...###...###...###...###.
+####....####....####....####....####....
+#####.....#####.....#####.....#####.....
+######......######......######......####
+#######.......#######.......#######.....";
+
+#[cfg(test)]
+mod tests {
+    use super::{Puzzle, TEST_INPUT, TEST_OUTPUT_B};
+    use common::{Answer, Solution};
+
+    #[test]
+    fn part_a() {
         let mut puzzle = Puzzle::default();
         assert_eq!(
-            puzzle.solve_a(String::from(TEST_INPUT)).await,
+            puzzle.solve_a(&puzzle.parse(TEST_INPUT).unwrap()),
             Ok(Answer::from(13140))
         )
     }
 
-    #[tokio::test]
-    async fn part_b() {
+    #[test]
+    fn part_b() {
         let mut puzzle = Puzzle::default();
         assert_eq!(
-            puzzle.solve_b(String::from(TEST_INPUT)).await,
-            Ok(Answer::from("
-##..##..##..##..##..##..##..##..##..##..
-###...###...###...###...###...###...###.
-####....####....####....####....####....
-#####.....#####.....#####.....#####.....
-######......######......######......####
-#######.......#######.......#######....."))
+            puzzle.solve_b(&puzzle.parse(TEST_INPUT).unwrap()),
+            Ok(Answer::from(TEST_OUTPUT_B))
         )
     }
-}
\ No newline at end of file
+}
@@ -1,7 +1,10 @@
-use async_trait::async_trait;
-use common::{Answer, Solution};
+use std::sync::Arc;
+
+use common::{Answer, Example, Solution, SolveError};
 use regex::Regex;
 
+use crate::registry::Day;
+
 pub struct Puzzle {}
 
 impl Default for Puzzle {
@@ -10,49 +13,55 @@ impl Default for Puzzle {
     }
 }
 
-struct Monkey {
+type Operation = Arc<dyn Fn(i64) -> i64 + Send + Sync>;
+
+#[derive(Clone)]
+pub struct Monkey {
     starting_items: Vec<i64>,
-    operation: Box<dyn Fn(i64) -> i64>,
+    operation: Operation,
     divisible_by: i64,
     valid: usize,
     invalid: usize,
     actions: i64,
 }
 
-fn regex_first_match<'a>(regex: &'a str, input: &'a str) -> &'a str {
+fn regex_first_match<'a>(regex: &str, input: &str, block: &'a str) -> Result<&'a str, SolveError> {
     Regex::new(regex)
         .expect("Valid regex")
-        .captures(input)
-        .expect("At least one match")
-        .get(1)
-        .expect("First match to be defined")
-        .as_str()
+        .captures(block)
+        .and_then(|captures| captures.get(1))
+        .map(|m| m.as_str())
+        .ok_or_else(|| SolveError::parse(input, block, format!("Expected a match for {}", regex)))
 }
 
-fn regex_first_match_as_number<T>(regex: &str, input: &str) -> T
+fn regex_first_match_as_number<T>(regex: &str, input: &str, block: &str) -> Result<T, SolveError>
 where
     T: std::str::FromStr,
-    <T as std::str::FromStr>::Err: std::fmt::Debug,
 {
-    regex_first_match(regex, input)
+    let value = regex_first_match(regex, input, block)?;
+    value
         .parse::<T>()
-        .expect("Parsable as i32")
+        .map_err(|_| SolveError::parse(input, value, "Number out of range"))
 }
 
-fn get_operation(operation: &str, operation_arg: &str) -> Box<dyn Fn(i64) -> i64> {
+fn get_operation(
+    input: &str,
+    operation: &str,
+    operation_arg: &str,
+) -> Result<Operation, SolveError> {
     if operation_arg == "old" {
         return match operation {
-            "+" => Box::new(|v| v + v),
-            "*" => Box::new(|v| v * v),
-            _ => unreachable!(),
+            "+" => Ok(Arc::new(|v| v + v)),
+            _ => Ok(Arc::new(|v| v * v)),
         };
     }
-    let operation_arg = operation_arg.parse::<i64>().expect("Parsable as i64");
-    return match operation {
-        "+" => Box::new(move |v| v + operation_arg),
-        "*" => Box::new(move |v| v * operation_arg),
-        _ => unreachable!(),
-    };
+    let operation_arg = operation_arg
+        .parse::<i64>()
+        .map_err(|_| SolveError::parse(input, operation_arg, "Expected old or a number"))?;
+    match operation {
+        "+" => Ok(Arc::new(move |v| v + operation_arg)),
+        _ => Ok(Arc::new(move |v| v * operation_arg)),
+    }
 }
 
 impl Monkey {
@@ -65,20 +74,23 @@ impl Monkey {
 
     pub fn do_turn(&mut self, divide: bool, overflow_mod: i64) -> Vec<(usize, i64)> {
         let items = self.starting_items.drain(..);
-        items.into_iter().map(|mut item| {
-            item %= overflow_mod;
-            let level: i64 = if divide {
-                (((self.operation)(item) as f64) / 3.0).floor() as i64
-            } else {
-                (self.operation)(item)
-            };
-            self.actions += 1;
-            if level % self.divisible_by == 0 {
-                (self.valid, level)
-            } else {
-                (self.invalid, level)
-            }
-        }).collect()
+        items
+            .into_iter()
+            .map(|mut item| {
+                item %= overflow_mod;
+                let level: i64 = if divide {
+                    (((self.operation)(item) as f64) / 3.0).floor() as i64
+                } else {
+                    (self.operation)(item)
+                };
+                self.actions += 1;
+                if level % self.divisible_by == 0 {
+                    (self.valid, level)
+                } else {
+                    (self.invalid, level)
+                }
+            })
+            .collect()
     }
 
     pub fn catch(&mut self, level: i64) -> () {
@@ -86,40 +98,73 @@ impl Monkey {
     }
 }
 
-impl From<&str> for Monkey {
-    fn from(input: &str) -> Self {
-        let starting_items: Vec<i64> = regex_first_match(r"Starting items: ([\d, ]*)", input)
-            .split(", ")
-            .map(|s| s.parse::<i64>().expect("Parsable as i64"))
-            .collect();
+impl Monkey {
+    /// Parses one monkey; `block` is a slice of `input` so errors point into the whole input.
+    fn parse(input: &str, block: &str) -> Result<Self, SolveError> {
+        let starting_items: Vec<i64> =
+            regex_first_match(r"Starting items: ([\d, ]*)", input, block)?
+                .split(", ")
+                .filter(|s| !s.is_empty())
+                .map(|s| {
+                    s.parse::<i64>()
+                        .map_err(|_| SolveError::parse(input, s, "Invalid worry level"))
+                })
+                .collect::<Result<_, _>>()?;
         let operation_match = Regex::new(r"Operation: new = old (\*|\+) (.+)")
             .expect("Valid regex")
-            .captures(input)
-            .expect("At least one match");
+            .captures(block)
+            .ok_or_else(|| SolveError::parse(input, block, "Expected an operation"))?;
+        let divisible_by = regex_first_match_as_number(r"Test: divisible by (\d+)", input, block)?;
+        if divisible_by == 0 {
+            return Err(SolveError::parse(
+                input,
+                block,
+                "Cannot test for divisibility by 0",
+            ));
+        }
 
-        Self {
+        Ok(Self {
             starting_items,
             operation: get_operation(
-                operation_match
-                    .get(1)
-                    .expect("First match to be defined")
-                    .as_str(),
-                operation_match
-                    .get(2)
-                    .expect("Second match to be defined")
-                    .as_str(),
-            ),
-            divisible_by: regex_first_match_as_number(r"Test: divisible by (\d+)", input),
-            valid: regex_first_match_as_number(r"If true: throw to monkey (\d+)", input),
-            invalid: regex_first_match_as_number(r"If false: throw to monkey (\d+)", input),
+                input,
+                operation_match.get(1).map_or("", |m| m.as_str()),
+                operation_match.get(2).map_or("", |m| m.as_str()),
+            )?,
+            divisible_by,
+            valid: regex_first_match_as_number(r"If true: throw to monkey (\d+)", input, block)?,
+            invalid: regex_first_match_as_number(r"If false: throw to monkey (\d+)", input, block)?,
             actions: 0,
-        }
+        })
     }
 }
 
-fn run(count: i32, input: String, divide: bool) -> Answer {
-    let mut monkeys: Vec<Monkey> = input.split("\n\n").map(|s| Monkey::from(s)).collect();
-    let overflow_modulo = monkeys.iter().fold(1, |modv, monkey| modv * monkey.get_divisible_by());
+fn parse_monkeys(input: &str) -> Result<Vec<Monkey>, SolveError> {
+    let monkeys: Vec<Monkey> = input
+        .split("\n\n")
+        .map(|s| Monkey::parse(input, s))
+        .collect::<Result<_, _>>()?;
+    if monkeys.len() < 2 {
+        return Err(SolveError::unsupported(
+            "Monkey business needs at least 2 monkeys",
+        ));
+    }
+    if let Some(monkey) = monkeys
+        .iter()
+        .position(|m| m.valid.max(m.invalid) >= monkeys.len())
+    {
+        return Err(SolveError::unsupported(format!(
+            "Monkey {} throws to a monkey that does not exist",
+            monkey
+        )));
+    }
+    Ok(monkeys)
+}
+
+fn run(count: i32, monkeys: &[Monkey], divide: bool) -> Result<Answer, SolveError> {
+    let mut monkeys = monkeys.to_vec();
+    let overflow_modulo = monkeys
+        .iter()
+        .fold(1, |modv, monkey| modv * monkey.get_divisible_by());
     for _ in 0..count {
         for i in 0..monkeys.len() {
             let throws = monkeys[i].do_turn(divide, overflow_modulo);
@@ -132,26 +177,36 @@ fn run(count: i32, input: String, divide: bool) -> Answer {
     let mut actions: Vec<i64> = monkeys.iter().map(|m| m.get_amount_of_actions()).collect();
     actions.sort();
     actions.reverse();
-    Answer::from(actions[0] * actions[1])
+    Ok(Answer::from(actions[0] * actions[1]))
+}
+
+inventory::submit! {
+    Day::new::<Puzzle>(2022, 11, "Monkey in the Middle", &["simulation", "modular-arithmetic"])
 }
 
-#[async_trait]
 impl Solution for Puzzle {
-    async fn solve_a(&mut self, input: String) -> Result<Answer, String> {
-        Ok(run(20, input, true))
+    type Parsed = Vec<Monkey>;
+
+    fn parse(&self, input: &str) -> Result<Self::Parsed, SolveError> {
+        parse_monkeys(input)
     }
 
-    async fn solve_b(&mut self, input: String) -> Result<Answer, String> {
-        Ok(run(10000, input, false))
+    fn solve_a(&mut self, monkeys: &Self::Parsed) -> Result<Answer, SolveError> {
+        run(20, monkeys, true)
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::Puzzle;
-    use common::{Answer, Solution};
+    fn solve_b(&mut self, monkeys: &Self::Parsed) -> Result<Answer, SolveError> {
+        run(10000, monkeys, false)
+    }
 
-    const TEST_INPUT: &str = "Monkey 0:
+    fn examples(&self) -> Vec<Example> {
+        vec![Example::new(TEST_INPUT)
+            .part_a(10605)
+            .part_b(2713310158_i64)]
+    }
+}
+
+const TEST_INPUT: &str = "Monkey 0:
   Starting items: 79, 98
   Operation: new = old * 19
   Test: divisible by 23
@@ -179,20 +234,25 @@ Monkey 3:
     If true: throw to monkey 0
     If false: throw to monkey 1";
 
-    #[tokio::test]
-    async fn part_a() {
+#[cfg(test)]
+mod tests {
+    use super::{Puzzle, TEST_INPUT};
+    use common::{Answer, Solution};
+
+    #[test]
+    fn part_a() {
         let mut puzzle = Puzzle::default();
         assert_eq!(
-            puzzle.solve_a(String::from(TEST_INPUT)).await,
+            puzzle.solve_a(&puzzle.parse(TEST_INPUT).unwrap()),
             Ok(Answer::from(10605))
         )
     }
 
-    #[tokio::test]
-    async fn part_b() {
+    #[test]
+    fn part_b() {
         let mut puzzle = Puzzle::default();
         assert_eq!(
-            puzzle.solve_b(String::from(TEST_INPUT)).await,
+            puzzle.solve_b(&puzzle.parse(TEST_INPUT).unwrap()),
             Ok(Answer::from(2713310158 as i64))
         )
     }
@@ -1,7 +1,8 @@
 use std::collections::HashSet;
 
-use async_trait::async_trait;
-use common::{Answer, Solution};
+use common::{Answer, Example, Grid, Point, Solution, SolveError};
+
+use crate::registry::Day;
 
 pub struct Puzzle {}
 
@@ -11,31 +12,35 @@ impl Default for Puzzle {
     }
 }
 
-#[derive(PartialEq)]
-struct Cell {
+#[derive(Clone, PartialEq)]
+pub struct Cell {
     start: bool,
     exit: bool,
     height: u32,
 }
 
-impl From<char> for Cell {
-    fn from(value: char) -> Self {
+impl TryFrom<char> for Cell {
+    type Error = ();
+
+    fn try_from(value: char) -> Result<Self, ()> {
         let bottom_char = 'a' as u32;
-        Self {
+        Ok(Self {
             start: value == 'S',
             exit: value == 'E',
             height: match value {
                 'S' => 0,
                 'E' => 25,
-                _ => value as u32 - bottom_char,
+                'a'..='z' => value as u32 - bottom_char,
+                _ => return Err(()),
             },
-        }
+        })
     }
 }
 
-type CellPos = (usize, usize);
-struct Grid {
-    cells: Vec<Vec<Cell>>,
+type CellPos = Point;
+#[derive(Clone)]
+pub struct HeightMap {
+    cells: Grid<Cell>,
     start: CellPos,
     exit: CellPos,
     longest_distance: u32,
@@ -46,71 +51,44 @@ enum PathMode {
     Down,
 }
 
-fn find_start(cells: &Vec<Vec<Cell>>) -> Option<CellPos> {
-    for (r, row) in cells.iter().enumerate() {
-        for (c, cell) in row.iter().enumerate() {
-            if cell.start {
-                return Some((r, c));
-            }
-        }
-    }
-    None
-}
-fn find_exit(cells: &Vec<Vec<Cell>>) -> Option<CellPos> {
-    for (r, row) in cells.iter().enumerate() {
-        for (c, cell) in row.iter().enumerate() {
-            if cell.exit {
-                return Some((r, c));
-            }
-        }
-    }
-    None
-}
-impl From<String> for Grid {
-    fn from(input: String) -> Self {
-        let cells: Vec<Vec<Cell>> = input
-            .split("\n")
-            .map(|row| row.chars().map(|c| c.into()).collect())
-            .collect();
-        Self {
-            start: find_start(&cells).expect("Should have start"),
-            exit: find_exit(&cells).expect("Should have exit"),
+impl TryFrom<&str> for HeightMap {
+    type Error = SolveError;
+
+    fn try_from(input: &str) -> Result<Self, SolveError> {
+        let cells = Grid::parse(input, |c| Cell::try_from(c).map_err(|_| "Invalid height"))?;
+        Ok(Self {
+            start: cells
+                .find(|cell| cell.start)
+                .ok_or_else(|| SolveError::unsupported("No start (S) found"))?,
+            exit: cells
+                .find(|cell| cell.exit)
+                .ok_or_else(|| SolveError::unsupported("No exit (E) found"))?,
             cells,
             longest_distance: u32::MAX,
-        }
+        })
     }
 }
-impl Grid {
+impl HeightMap {
     fn get_neighbors(&self, pos: &CellPos) -> Vec<CellPos> {
-        let mut list: Vec<(usize, usize)> = vec![];
-        if self.cells.len() > pos.0 + 1 {
-            list.push((pos.0 + 1, pos.1));
-        }
-        if 1 <= pos.0 {
-            list.push((pos.0 - 1, pos.1));
-        }
-        if self.cells[0].len() > pos.1 + 1 {
-            list.push((pos.0, pos.1 + 1));
-        }
-        if 1 <= pos.1 {
-            list.push((pos.0, pos.1 - 1));
-        }
-        list
+        self.cells
+            .neighbors4(*pos)
+            .map(|(neighbor, _)| neighbor)
+            .collect()
     }
 
     fn get(&self, pos: &CellPos) -> &Cell {
-        self.cells.get(pos.0).expect("Row should exist").get(pos.1).expect("Cell should exist")
+        &self.cells[*pos]
     }
 
     fn find_shortest_path(&mut self) -> u32 {
-        let exit = self.exit.clone();
-        let mut visited = HashSet::from([exit.clone()]);
+        let exit = self.exit;
+        let mut visited = HashSet::from([exit]);
         self.fill_paths(HashSet::from([exit]), &mut visited, 1, PathMode::Down)
     }
 
     fn find_path(&mut self) -> u32 {
-        let start = self.start.clone();
-        let mut visited = HashSet::from([start.clone()]);
+        let start = self.start;
+        let mut visited = HashSet::from([start]);
         self.fill_paths(HashSet::from([start]), &mut visited, 1, PathMode::Up)
     }
 
@@ -121,7 +99,7 @@ impl Grid {
         distance: u32,
         path_mode: PathMode,
     ) -> u32 {
-        if distance > self.longest_distance {
+        if distance > self.longest_distance || current.is_empty() {
             return u32::MAX;
         }
         let mut cells_to_check: HashSet<CellPos> = HashSet::new();
@@ -144,9 +122,7 @@ impl Grid {
         });
         let has_end = match path_mode {
             PathMode::Up => cells_to_check.contains(&self.exit),
-            PathMode::Down => cells_to_check
-                .iter()
-                .any(|c| self.get(c).height == 0),
+            PathMode::Down => cells_to_check.iter().any(|c| self.get(c).height == 0),
         };
         if has_end {
             self.longest_distance = self.longest_distance.min(distance);
@@ -156,57 +132,84 @@ impl Grid {
     }
 }
 
-#[async_trait]
+inventory::submit! {
+    Day::new::<Puzzle>(2022, 12, "Hill Climbing Algorithm", &["grid", "bfs"])
+}
+
 impl Solution for Puzzle {
-    async fn solve_a(&mut self, input: String) -> Result<Answer, String> {
-        let mut grid = Grid::from(input);
-        Ok(Answer::from(grid.find_path()))
+    type Parsed = HeightMap;
+
+    fn parse(&self, input: &str) -> Result<Self::Parsed, SolveError> {
+        HeightMap::try_from(input)
     }
 
-    async fn solve_b(&mut self, input: String) -> Result<Answer, String> {
-        let mut grid = Grid::from(input);
-        Ok(Answer::from(grid.find_shortest_path()))
+    fn solve_a(&mut self, grid: &Self::Parsed) -> Result<Answer, SolveError> {
+        let mut grid = grid.clone();
+        match grid.find_path() {
+            u32::MAX => Err(SolveError::unsupported(
+                "No path from the start to the exit",
+            )),
+            distance => Ok(Answer::from(distance)),
+        }
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::Puzzle;
-    use common::{Answer, Solution};
+    fn solve_b(&mut self, grid: &Self::Parsed) -> Result<Answer, SolveError> {
+        let mut grid = grid.clone();
+        match grid.find_shortest_path() {
+            u32::MAX => Err(SolveError::unsupported(
+                "No path from the lowest point to the exit",
+            )),
+            distance => Ok(Answer::from(distance)),
+        }
+    }
 
-    const TEST_INPUT_A: &str = "Sabqponm
+    fn examples(&self) -> Vec<Example> {
+        vec![
+            Example::new(TEST_INPUT_A).part_a(31).part_b(29),
+            Example::new(TEST_INPUT_B).part_a(33).part_b(30),
+        ]
+    }
+}
+
+const TEST_INPUT_A: &str = "Sabqponm
 abcryxxl
 accszExk
 acctuvwj
 abdefghi";
-    const TEST_INPUT_B: &str = "Saaabqponm
+
+const TEST_INPUT_B: &str = "Saaabqponm
 abbbcryxxl
 accccszExk
 acccctuvwj
 abdddefghi";
 
-    #[tokio::test]
-    async fn part_a() {
+#[cfg(test)]
+mod tests {
+    use super::{Puzzle, TEST_INPUT_A, TEST_INPUT_B};
+    use common::{Answer, Solution};
+
+    #[test]
+    fn part_a() {
         let mut puzzle = Puzzle::default();
         assert_eq!(
-            puzzle.solve_a(String::from(TEST_INPUT_A)).await,
+            puzzle.solve_a(&puzzle.parse(TEST_INPUT_A).unwrap()),
             Ok(Answer::from(31))
         );
         assert_eq!(
-            puzzle.solve_a(String::from(TEST_INPUT_B)).await,
+            puzzle.solve_a(&puzzle.parse(TEST_INPUT_B).unwrap()),
             Ok(Answer::from(33))
         );
     }
 
-    #[tokio::test]
-    async fn part_b() {
+    #[test]
+    fn part_b() {
         let mut puzzle = Puzzle::default();
         assert_eq!(
-            puzzle.solve_b(String::from(TEST_INPUT_A)).await,
+            puzzle.solve_b(&puzzle.parse(TEST_INPUT_A).unwrap()),
             Ok(Answer::from(29))
         );
         assert_eq!(
-            puzzle.solve_b(String::from(TEST_INPUT_B)).await,
+            puzzle.solve_b(&puzzle.parse(TEST_INPUT_B).unwrap()),
             Ok(Answer::from(30))
         );
     }
@@ -1,7 +1,8 @@
-use std::{cmp::Ordering, str::FromStr, string::ParseError};
+use std::cmp::Ordering;
 
-use async_trait::async_trait;
-use common::{Answer, Solution};
+use common::{Answer, Example, Solution, SolveError};
+
+use crate::registry::Day;
 
 pub struct Puzzle {}
 
@@ -12,27 +13,32 @@ impl Default for Puzzle {
 }
 
 #[derive(PartialEq, Clone, Debug)]
-enum Msg {
+pub enum Msg {
     Value(u8),
-    List(Vec<Msg>)
+    List(Vec<Msg>),
 }
 
-impl FromStr for Msg {
-    type Err = ParseError;
-
-    fn from_str(input: &str) -> Result<Self, Self::Err> {
-        if let Ok(val) = input.parse::<u8>() {
+impl Msg {
+    /// Parses `text`, a slice of `input` so errors point into the whole input.
+    fn parse(input: &str, text: &str) -> Result<Self, SolveError> {
+        if let Ok(val) = text.parse::<u8>() {
             return Ok(Msg::Value(val));
         }
-        let input = &input[1..input.len() - 1];
+        if !text.starts_with('[') || !text.ends_with(']') || text.len() < 2 {
+            return Err(SolveError::parse(
+                input,
+                text,
+                "Expected a number or a list",
+            ));
+        }
+        let text = &text[1..text.len() - 1];
         let mut out: Vec<Msg> = vec![];
         let mut cur = 0;
         let mut depth = 0;
-        for (i, c) in input.chars().enumerate() {
+        for (i, c) in text.char_indices() {
             match c {
                 ',' if depth == 0 => {
-                    let val = input.get(cur..i).expect("Failed to split str");
-                    out.push(Msg::from_str(&val).unwrap());
+                    out.push(Msg::parse(input, &text[cur..i])?);
                     cur = i + 1;
                 }
                 ']' => depth = depth - 1,
@@ -40,19 +46,27 @@ impl FromStr for Msg {
                 _ => {}
             };
         }
-        let val = input.get(cur..).expect("Failed to split str");
+        let val = &text[cur..];
         if val.len() > 0 {
-            out.push(Msg::from_str(&val).unwrap());
+            out.push(Msg::parse(input, val)?);
         }
         return Ok(Msg::List(out));
     }
 }
 
+fn divider(value: u8) -> Msg {
+    Msg::List(vec![Msg::List(vec![Msg::Value(value)])])
+}
+
 fn is_valid_pair(pair: &(&Msg, &Msg)) -> bool {
     match pair {
         (Msg::Value(left_val), Msg::Value(right_val)) => left_val <= right_val,
-        (Msg::Value(left_val), right) => is_valid_pair(&(&Msg::List(vec![Msg::Value(left_val.clone())]), right)),
-        (left, Msg::Value(right_val)) => is_valid_pair(&(left, &Msg::List(vec![Msg::Value(right_val.clone())]))),
+        (Msg::Value(left_val), right) => {
+            is_valid_pair(&(&Msg::List(vec![Msg::Value(left_val.clone())]), right))
+        }
+        (left, Msg::Value(right_val)) => {
+            is_valid_pair(&(left, &Msg::List(vec![Msg::Value(right_val.clone())])))
+        }
         (Msg::List(left_val), Msg::List(right_val)) => {
             for (i, left_el) in left_val.iter().enumerate() {
                 let right_el = right_val.get(i);
@@ -66,30 +80,40 @@ fn is_valid_pair(pair: &(&Msg, &Msg)) -> bool {
                 return is_valid_pair(&(left_el, right_el));
             }
             return true;
-        },
+        }
     }
 }
 
-#[async_trait]
+inventory::submit! {
+    Day::new::<Puzzle>(2022, 13, "Distress Signal", &["parsing", "recursion"])
+}
+
 impl Solution for Puzzle {
-    async fn solve_a(&mut self, input: String) -> Result<Answer, String> {
-        let pairs: Vec<(Msg, Msg)> = input
+    type Parsed = Vec<(Msg, Msg)>;
+
+    fn parse(&self, input: &str) -> Result<Self::Parsed, SolveError> {
+        input
             .split("\n\n")
-            .map(|pair| pair.split_once("\n").unwrap())
-            .map(|(a, b)| (Msg::from_str(a).unwrap(), Msg::from_str(b).unwrap()))
-            .collect();
+            .map(|pair| {
+                let (a, b) = pair
+                    .split_once("\n")
+                    .ok_or_else(|| SolveError::parse(input, pair, "Expected a pair of packets"))?;
+                Ok((Msg::parse(input, a)?, Msg::parse(input, b)?))
+            })
+            .collect()
+    }
+
+    fn solve_a(&mut self, pairs: &Self::Parsed) -> Result<Answer, SolveError> {
         let result = pairs.iter().enumerate().fold(0, |sum, (i, (a, b))| {
             sum + if is_valid_pair(&(&a, &b)) { i + 1 } else { 0 }
         });
         Answer::from(result).into()
     }
 
-    async fn solve_b(&mut self, input: String) -> Result<Answer, String> {
-        let input = input.trim().replace("\n\n", "\n");
-        let messages: Vec<Msg> = input.split("\n").map(|s| Msg::from_str(s).unwrap()).collect();
-        let mut messages_refs: Vec<&Msg> = messages.iter().collect();
-        let div_1 = Msg::from_str("[[2]]").unwrap();
-        let div_2 = Msg::from_str("[[6]]").unwrap();
+    fn solve_b(&mut self, pairs: &Self::Parsed) -> Result<Answer, SolveError> {
+        let mut messages_refs: Vec<&Msg> = pairs.iter().flat_map(|(a, b)| [a, b]).collect();
+        let div_1 = divider(2);
+        let div_2 = divider(6);
         messages_refs.push(&div_1);
         messages_refs.push(&div_2);
         messages_refs.sort_by(|a, b| match is_valid_pair(&(a, b)) {
@@ -108,14 +132,13 @@ impl Solution for Puzzle {
             + 1;
         Answer::from(div_1_index * div_2_index).into()
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::Puzzle;
-    use common::{Answer, Solution};
+    fn examples(&self) -> Vec<Example> {
+        vec![Example::new(TEST_INPUT).part_a(13).part_b(140)]
+    }
+}
 
-    const TEST_INPUT: &str = "[1,1,3,1,1]
+const TEST_INPUT: &str = "[1,1,3,1,1]
 [1,1,5,1,1]
 
 [[1],[2,3,4]]
@@ -139,20 +162,25 @@ mod tests {
 [1,[2,[3,[4,[5,6,7]]]],8,9]
 [1,[2,[3,[4,[5,6,0]]]],8,9]";
 
-    #[tokio::test]
-    async fn part_a() {
+#[cfg(test)]
+mod tests {
+    use super::{Puzzle, TEST_INPUT};
+    use common::{Answer, Solution};
+
+    #[test]
+    fn part_a() {
         let mut puzzle = Puzzle::default();
         assert_eq!(
-            puzzle.solve_a(String::from(TEST_INPUT)).await,
+            puzzle.solve_a(&puzzle.parse(TEST_INPUT).unwrap()),
             Ok(Answer::from(13))
         )
     }
-    
-    #[tokio::test]
-    async fn part_b() {
+
+    #[test]
+    fn part_b() {
         let mut puzzle = Puzzle::default();
         assert_eq!(
-            puzzle.solve_b(String::from(TEST_INPUT)).await,
+            puzzle.solve_b(&puzzle.parse(TEST_INPUT).unwrap()),
             Ok(Answer::from(140))
         )
     }
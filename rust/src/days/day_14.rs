@@ -1,19 +1,23 @@
-use async_trait::async_trait;
-use common::{Answer, Solution};
-use ndarray::{Array2, s};
+use common::{Answer, Color, Example, Grid, GridCell, Point, Renderer, Solution, SolveError};
 
-pub struct Puzzle {}
+use crate::registry::Day;
+
+pub struct Puzzle {
+    renderer: Renderer,
+}
 
 impl Default for Puzzle {
     fn default() -> Self {
-        Self {}
+        Self {
+            renderer: Renderer::default(),
+        }
     }
 }
 
-type PixelGrid = Array2<Pixel>;
+type PixelGrid = Grid<Pixel>;
 
 #[derive(Clone, PartialEq)]
-enum Pixel {
+pub enum Pixel {
     Empty,
     Wall,
     Floor,
@@ -25,132 +29,184 @@ impl Default for Pixel {
         Self::Empty
     }
 }
-impl std::fmt::Debug for Pixel {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl GridCell for Pixel {
+    const PALETTE: &'static [(char, Color)] = &[
+        ('█', Color::DARK_GRAY),
+        ('▔', Color::GRAY),
+        ('+', Color::RED),
+        ('.', Color::ORANGE),
+    ];
+
+    fn symbol(&self) -> char {
         match self {
-            Self::Empty => write!(f, " "),
-            Self::Wall => write!(f, "█"),
-            Self::Floor => write!(f, "▔"),
-            Self::SandSrc => write!(f, "+"),
-            Self::Sand => write!(f, "."),
+            Self::Empty => ' ',
+            Self::Wall => '█',
+            Self::Floor => '▔',
+            Self::SandSrc => '+',
+            Self::Sand => '.',
         }
     }
 }
-
-#[allow(dead_code)]
-fn render(a: &PixelGrid) {
-    let row_count = a.nrows();
-    for row_index in 0..row_count {
-        let row: ndarray::ArrayBase<ndarray::ViewRepr<&Pixel>, ndarray::Dim<[usize; 1]>> = a.row(row_index);
-        let row_slice = row.slice(s!(400..600));
-        for col_index in 0..row_slice.len() {
-            let x = row_slice.get(col_index).unwrap();
-            print!("{:?}", x);
-        }
-        print!("|");
-        println!();
+impl std::fmt::Debug for Pixel {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.symbol())
     }
 }
 
+/// Grains of sand dropped between frames of part B, which drops a lot more of them
+const FRAME_EVERY_B: usize = 25;
+
+/// Draws the part of the cave sand can reach: the triangle below the source, down to the floor.
+fn render(renderer: &Renderer, sim: &PixelGrid, sand: usize) {
+    if !renderer.is_enabled() {
+        return;
+    }
+    let floor = (0..sim.height())
+        .find(|row| sim.row(*row)[0] == Pixel::Floor)
+        .unwrap_or(sim.height() - 1);
+    let left = 500_usize.saturating_sub(floor + 1);
+    let right = (500 + floor + 2).min(sim.width());
+    renderer.cells(
+        (left as i64, 0),
+        (0..=floor).map(|row| &sim.row(row)[left..right]),
+    );
+    renderer.text((left as i64, -3), format!("sand: {}", sand), Color::BLACK);
+    renderer.flush();
+}
 
-fn ascending_range<T>(r: std::ops::Range<T>) -> std::ops::Range<T> where T: std::cmp::PartialOrd {
-    if r.start < r.end {
-        r
-    } else {
-        r.end..r.start
+fn parse_coord(
+    input: &str,
+    coord: &str,
+    (rows, cols): (usize, usize),
+) -> Result<(usize, usize), SolveError> {
+    let (col, row) = coord
+        .split_once(",")
+        .ok_or_else(|| SolveError::parse(input, coord, "Expected x,y"))?;
+    let row = row
+        .parse::<usize>()
+        .map_err(|_| SolveError::parse(input, row, "Invalid coordinate"))?;
+    let col = col
+        .parse::<usize>()
+        .map_err(|_| SolveError::parse(input, col, "Invalid coordinate"))?;
+    // room for the floor below, and for sand to fall past the sides
+    if row + 2 >= rows || col == 0 || col + 1 >= cols {
+        return Err(SolveError::unsupported(format!(
+            "Rock at {} lies outside the simulation",
+            coord
+        )));
     }
+    Ok((row, col))
 }
 
-fn load_input(sim: &mut PixelGrid, input: String)  {
+fn load_input(sim: &mut PixelGrid, input: &str) -> Result<(), SolveError> {
     let mut lowest_point: usize = 0;
-    input.lines().into_iter().for_each(|line| {
-        line
-            .split(" -> ")
-            .map(|c| c.split_once(",").unwrap())
-            .map(|c| (c.1.parse::<usize>().unwrap(), c.0.parse::<usize>().unwrap()))
-            .collect::<Vec<(usize, usize)>>()
+    for line in input.lines() {
+        line.split(" -> ")
+            .map(|c| parse_coord(input, c, (sim.height(), sim.width())))
+            .collect::<Result<Vec<(usize, usize)>, _>>()?
             .windows(2)
             .for_each(|coords| {
-                if let [(start_x, start_y), (end_x, end_y)] = coords {
-                    if start_x == end_x {
-                        sim.slice_mut(s![*start_x, ascending_range(*start_y..*end_y)]).fill(Pixel::Wall);
-                    } else {
-                        sim.slice_mut(s![ascending_range(*start_x..*end_x), *start_y]).fill(Pixel::Wall);
+                if let [(start_row, start_col), (end_row, end_col)] = coords {
+                    for row in *start_row.min(end_row)..=*start_row.max(end_row) {
+                        for col in *start_col.min(end_col)..=*start_col.max(end_col) {
+                            sim[(col as i64, row as i64)] = Pixel::Wall;
+                        }
                     }
-                    sim[[*end_x, *end_y]] = Pixel::Wall;
-                    lowest_point = *start_x.max(end_x).max(&lowest_point);
+                    lowest_point = *start_row.max(end_row).max(&lowest_point);
                 }
             });
-    });
-    sim[[0, 500]] = Pixel::SandSrc;
-    sim.slice_mut(s!(lowest_point + 2, 0..sim.dim().1)).fill(Pixel::Floor);
+    }
+    sim[(500, 0)] = Pixel::SandSrc;
+    sim.row_mut(lowest_point + 2).fill(Pixel::Floor);
+    Ok(())
 }
 
-fn drop_sand(sim: &mut PixelGrid, (x, y): (usize, usize)) -> bool {
-    match sim[[y + 1, x]] {
+fn drop_sand(sim: &mut PixelGrid, (x, y): Point) -> bool {
+    match sim[(x, y + 1)] {
         Pixel::Floor => {
-            sim[[y, x]] = Pixel::Sand; 
+            sim[(x, y)] = Pixel::Sand;
             false // into the abyss
-        },
+        }
         Pixel::Empty => drop_sand(sim, (x, y + 1)),
-        _ if sim[[y + 1, x - 1]] == Pixel::Empty => drop_sand(sim, (x - 1, y + 1)),
-        _ if sim[[y + 1, x + 1]] == Pixel::Empty => drop_sand(sim, (x + 1, y + 1)),
+        _ if sim[(x - 1, y + 1)] == Pixel::Empty => drop_sand(sim, (x - 1, y + 1)),
+        _ if sim[(x + 1, y + 1)] == Pixel::Empty => drop_sand(sim, (x + 1, y + 1)),
         _ => {
-            sim[[y, x]] = Pixel::Sand; 
+            sim[(x, y)] = Pixel::Sand;
             true // rested
         }
     }
 }
 
-#[async_trait]
+inventory::submit! {
+    Day::new::<Puzzle>(2022, 14, "Regolith Reservoir", &["grid", "simulation"])
+}
+
 impl Solution for Puzzle {
-    async fn solve_a(&mut self, input: String) -> Result<Answer, String> {
-        let mut sim: PixelGrid = PixelGrid::default((300, 1000));
+    /// The rocks and floor, without any sand
+    type Parsed = PixelGrid;
 
-        load_input(&mut sim, input);
+    fn parse(&self, input: &str) -> Result<Self::Parsed, SolveError> {
+        let mut sim: PixelGrid = PixelGrid::new(1000, 300, Pixel::default());
+        load_input(&mut sim, input)?;
+        Ok(sim)
+    }
+
+    fn solve_a(&mut self, sim: &Self::Parsed) -> Result<Answer, SolveError> {
+        let mut sim = sim.clone();
         let mut sand_turns = 0;
         while drop_sand(&mut sim, (500, 0)) {
             sand_turns += 1;
+            render(&self.renderer, &sim, sand_turns);
         }
-        render(&sim);
+        render(&self.renderer, &sim, sand_turns);
         Answer::from(sand_turns).into()
     }
 
-    async fn solve_b(&mut self, input: String) -> Result<Answer, String> {
-        let mut sim: PixelGrid = PixelGrid::default((300, 1000));
-        load_input(&mut sim, input);
+    fn solve_b(&mut self, sim: &Self::Parsed) -> Result<Answer, SolveError> {
+        let mut sim = sim.clone();
         let mut sand_turns = 0;
-        while sim[[0, 500]] != Pixel::Sand {
+        while sim[(500, 0)] != Pixel::Sand {
             drop_sand(&mut sim, (500, 0));
             sand_turns += 1;
+            if sand_turns % FRAME_EVERY_B == 0 {
+                render(&self.renderer, &sim, sand_turns);
+            }
         }
-        // render(&sim);
+        render(&self.renderer, &sim, sand_turns);
         Answer::from(sand_turns).into()
     }
+
+    fn set_renderer(&mut self, renderer: Renderer) {
+        self.renderer = renderer;
+    }
+
+    fn examples(&self) -> Vec<Example> {
+        vec![Example::new(TEST_INPUT).part_a(24).part_b(93)]
+    }
 }
 
+const TEST_INPUT: &str = "498,4 -> 498,6 -> 496,6
+503,4 -> 502,4 -> 502,9 -> 494,9";
+
 #[cfg(test)]
 mod tests {
-    use super::Puzzle;
+    use super::{Puzzle, TEST_INPUT};
     use common::{Answer, Solution};
 
-    const TEST_INPUT: &str = "498,4 -> 498,6 -> 496,6
-503,4 -> 502,4 -> 502,9 -> 494,9";
-
-    #[tokio::test]
-    async fn part_a() {
+    #[test]
+    fn part_a() {
         let mut puzzle = Puzzle::default();
         assert_eq!(
-            puzzle.solve_a(String::from(TEST_INPUT)).await,
-            Ok(Answer::from(""))
+            puzzle.solve_a(&puzzle.parse(TEST_INPUT).unwrap()),
+            Ok(Answer::from(24))
         )
     }
 
-    #[tokio::test]
-    async fn part_b() {
+    #[test]
+    fn part_b() {
         let mut puzzle = Puzzle::default();
         assert_eq!(
-            puzzle.solve_b(String::from(TEST_INPUT)).await,
+            puzzle.solve_b(&puzzle.parse(TEST_INPUT).unwrap()),
             Ok(Answer::from(93))
         )
     }
@@ -1,9 +1,10 @@
 use std::collections::HashSet;
 
-use async_trait::async_trait;
-use common::{Answer, Solution};
+use common::{Answer, Example, Solution, SolveError};
 use regex::Regex;
 
+use crate::registry::Day;
+
 const TUNING_FREQUENCY_MULTIPLIER: i64 = 4_000_000;
 pub struct Puzzle {
     y_row: i64,
@@ -19,13 +20,13 @@ impl Default for Puzzle {
     }
 }
 
-struct Sensor {
+pub struct Sensor {
     position: (i64, i64),
     beacon: (i64, i64),
     distance: i64,
 }
 
-fn parse_input(input: String) -> Vec<Sensor> {
+fn parse_input(input: &str) -> Result<Vec<Sensor>, SolveError> {
     let regex =
         Regex::new(r"Sensor at x=(-?\d+), y=(-?\d+): closest beacon is at x=(-?\d+), y=(-?\d+)")
             .expect("Valid regex");
@@ -33,22 +34,25 @@ fn parse_input(input: String) -> Vec<Sensor> {
         .split('\n')
         .into_iter()
         .map(|line| {
-            let captures = regex.captures(line).expect(&format!("At least one match: {}", line));
+            let captures = regex
+                .captures(line)
+                .ok_or_else(|| SolveError::parse(input, line, "Expected a sensor"))?;
             let captures = captures
                 .iter()
                 .skip(1)
                 .map(|c| {
-                    let str = c.expect("Match").as_str();
-                    str.parse().expect(&format!("Invalid integer: {}", str))
+                    let str = c.map_or("", |c| c.as_str());
+                    str.parse()
+                        .map_err(|_| SolveError::parse(input, str, "Invalid integer"))
                 })
-                .collect::<Vec<i64>>();
+                .collect::<Result<Vec<i64>, _>>()?;
             let (x1, y1, x2, y2) = (captures[0], captures[1], captures[2], captures[3]);
             let distance = (x1 - x2).abs() + (y1 - y2).abs();
-            Sensor {
+            Ok(Sensor {
                 position: (x1, y1),
                 beacon: (x2, y2),
                 distance,
-            }
+            })
         })
         .collect()
 }
@@ -92,7 +96,7 @@ fn find_empty_spots_on_row(coords: &[Sensor], y_row: i64) -> i64 {
 fn find_tuning_frequency(coords: &[Sensor], limit: i64, tuning_frequency_multiplier: i64) -> i64 {
     for coord in coords {
         let delta = coord.distance + 1;
-         for d in -delta..=delta {
+        for d in -delta..=delta {
             let y = coord.position.1 + d;
             let dx = delta - d.abs();
             let left = (coord.position.0 - dx, y);
@@ -113,34 +117,44 @@ fn find_tuning_frequency(coords: &[Sensor], limit: i64, tuning_frequency_multipl
 }
 
 fn is_in_bounds(coord: (i64, i64), bounds: (i64, i64)) -> bool {
-    coord.0 >= bounds.0 && coord.0 <= bounds.1 && 
-    coord.1 >= bounds.0 && coord.1 <= bounds.1
+    coord.0 >= bounds.0 && coord.0 <= bounds.1 && coord.1 >= bounds.0 && coord.1 <= bounds.1
+}
+
+inventory::submit! {
+    Day::new::<Puzzle>(2022, 15, "Beacon Exclusion Zone", &["geometry"])
 }
 
-#[async_trait]
 impl Solution for Puzzle {
-    async fn solve_a(&mut self, input: String) -> Result<Answer, String> {
-        let coords = parse_input(input);
-        Answer::from(find_empty_spots_on_row(&coords, self.y_row)).into()
+    type Parsed = Vec<Sensor>;
+
+    fn parse(&self, input: &str) -> Result<Self::Parsed, SolveError> {
+        parse_input(input)
     }
 
-    async fn solve_b(&mut self, input: String) -> Result<Answer, String> {
-        let coords = parse_input(input);
+    fn solve_a(&mut self, coords: &Self::Parsed) -> Result<Answer, SolveError> {
+        Answer::from(find_empty_spots_on_row(coords, self.y_row)).into()
+    }
+
+    fn solve_b(&mut self, coords: &Self::Parsed) -> Result<Answer, SolveError> {
         Answer::from(find_tuning_frequency(
-            &coords,
+            coords,
             self.scan_range,
             TUNING_FREQUENCY_MULTIPLIER,
         ))
         .into()
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::Puzzle;
-    use common::{Answer, Solution};
+    fn example_mode(&mut self) {
+        self.y_row = 10;
+        self.scan_range = 20;
+    }
+
+    fn examples(&self) -> Vec<Example> {
+        vec![Example::new(TEST_INPUT).part_a(26).part_b(56_000_011)]
+    }
+}
 
-    const TEST_INPUT: &str = "Sensor at x=2, y=18: closest beacon is at x=-2, y=15
+const TEST_INPUT: &str = "Sensor at x=2, y=18: closest beacon is at x=-2, y=15
 Sensor at x=9, y=16: closest beacon is at x=10, y=16
 Sensor at x=13, y=2: closest beacon is at x=15, y=3
 Sensor at x=12, y=14: closest beacon is at x=10, y=16
@@ -155,26 +169,31 @@ Sensor at x=16, y=7: closest beacon is at x=15, y=3
 Sensor at x=14, y=3: closest beacon is at x=15, y=3
 Sensor at x=20, y=1: closest beacon is at x=15, y=3";
 
-    #[tokio::test]
-    async fn part_a() {
+#[cfg(test)]
+mod tests {
+    use super::{Puzzle, TEST_INPUT};
+    use common::{Answer, Solution};
+
+    #[test]
+    fn part_a() {
         let mut puzzle = Puzzle {
             y_row: 10,
             scan_range: 20,
         };
         assert_eq!(
-            puzzle.solve_a(String::from(TEST_INPUT)).await,
+            puzzle.solve_a(&puzzle.parse(TEST_INPUT).unwrap()),
             Ok(Answer::from(26))
         )
     }
 
-    #[tokio::test]
-    async fn part_b() {
+    #[test]
+    fn part_b() {
         let mut puzzle = Puzzle {
             y_row: 10,
             scan_range: 20,
         };
         assert_eq!(
-            puzzle.solve_b(String::from(TEST_INPUT)).await,
+            puzzle.solve_b(&puzzle.parse(TEST_INPUT).unwrap()),
             Ok(Answer::from(56_000_011))
         )
     }
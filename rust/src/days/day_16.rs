@@ -4,16 +4,14 @@ use std::{
     rc::{Rc, Weak},
 };
 
-use async_trait::async_trait;
-use common::{Answer, Solution};
+use common::{Answer, Example, Progress, Solution, SolveError};
 use regex::Regex;
 
-pub struct Puzzle {}
+use crate::registry::Day;
 
-impl Default for Puzzle {
-    fn default() -> Self {
-        Self {}
-    }
+#[derive(Default)]
+pub struct Puzzle {
+    progress: Progress,
 }
 
 #[derive(Debug)]
@@ -31,39 +29,73 @@ type NodeRef = Rc<RefCell<Node>>;
 type NeighborRef = Weak<RefCell<Node>>;
 type NodeMap = HashMap<String, NodeRef>;
 
-fn parse_input(data: String) -> NodeMap {
-    let mut nodes: NodeMap = NodeMap::new();
+/// A valve as listed in the input; the graph of [`Node`]s is built from these per part.
+pub struct Valve {
+    name: String,
+    flow_rate: u32,
+    tunnels: Vec<String>,
+}
+
+fn parse_input(data: &str) -> Result<Vec<Valve>, SolveError> {
     let regex =
         Regex::new(r"Valve ([A-Z]{2}) has flow rate=(\d+); tunnels? leads? to valves? ([A-Z, ]+)")
             .expect("Valid regex");
-    let rows: Vec<(&str, &str, &str)> = data
+    let valves: Vec<Valve> = data
         .split("\n")
         .map(|row| {
-            let captures = regex.captures(row).expect(&format!("At least one match: {}", row));
-            (
-                captures.get(1).expect("Valve name").as_str(),
-                captures.get(2).expect("Flow rate").as_str(),
-                captures.get(3).expect("Neighbors").as_str(),
-            )
+            let captures = regex
+                .captures(row)
+                .ok_or_else(|| SolveError::parse(data, row, "Expected a valve"))?;
+            let flow_rate = captures.get(2).map_or("", |m| m.as_str());
+            Ok(Valve {
+                name: captures.get(1).map_or("", |m| m.as_str()).into(),
+                flow_rate: flow_rate
+                    .parse()
+                    .map_err(|_| SolveError::parse(data, flow_rate, "Flow rate out of range"))?,
+                tunnels: captures
+                    .get(3)
+                    .map_or("", |m| m.as_str())
+                    .split(", ")
+                    .map(String::from)
+                    .collect(),
+            })
         })
-        .collect();
-    for (name, flow_rate, _) in rows.clone() {
+        .collect::<Result<_, SolveError>>()?;
+    for tunnel in valves.iter().flat_map(|valve| valve.tunnels.iter()) {
+        if !valves.iter().any(|valve| &valve.name == tunnel) {
+            return Err(SolveError::parse(
+                data,
+                tunnel,
+                "Tunnel to an unknown valve",
+            ));
+        }
+    }
+    if !valves.iter().any(|valve| valve.name == "AA") {
+        return Err(SolveError::unsupported("No valve AA to start from"));
+    }
+    Ok(valves)
+}
+
+fn build_nodes(valves: &[Valve]) -> NodeMap {
+    let mut nodes: NodeMap = NodeMap::new();
+    for valve in valves {
         nodes.insert(
-            name.into(),
+            valve.name.clone(),
             Rc::new(RefCell::new(Node {
-                name: name.into(),
-                flow_rate: flow_rate.parse().expect("Flow rate as number"),
+                name: valve.name.clone(),
+                flow_rate: valve.flow_rate,
                 neighbors: vec![],
             })),
         );
     }
-    for (name, _, neighbors) in rows {
-        let neighbors = neighbors
-            .split(", ")
-            .map(|n| Rc::downgrade(nodes.get(n).expect("Neighbor should be in map")))
+    for valve in valves {
+        let neighbors = valve
+            .tunnels
+            .iter()
+            .map(|n| Rc::downgrade(nodes.get(n).expect("Tunnels were checked while parsing")))
             .collect::<Vec<NeighborRef>>();
         nodes
-            .get(name)
+            .get(&valve.name)
             .expect("Node should be in map")
             .borrow_mut()
             .neighbors = neighbors;
@@ -94,7 +126,10 @@ fn calculate_travel_distances(nodes: &NodeMap) -> HashMap<String, HashMap<String
             }
             visited.push(current.clone());
             let current = current.borrow();
-            let current_distance = distance_map.get(&current.name).expect("Node should be in distance_map").to_owned();
+            let current_distance = distance_map
+                .get(&current.name)
+                .expect("Node should be in distance_map")
+                .to_owned();
 
             current
                 .neighbors
@@ -119,14 +154,19 @@ struct DFS {
     memo: HashMap<u32, HashMap<String, HashMap<String, u32>>>,
     node_map: NodeMap,
     time_limit: u32,
+    states: u64,
+    progress: Progress,
 }
 
 impl DFS {
-    fn new(node_map: NodeMap) -> Self {
+    fn new(node_map: NodeMap, progress: Progress) -> Self {
+        progress.phase("states", None);
         Self {
             memo: HashMap::new(),
             node_map,
             time_limit: 30,
+            states: 0,
+            progress,
         }
     }
 
@@ -162,6 +202,8 @@ impl DFS {
             .entry(memo_key.1)
             .or_insert(HashMap::new())
             .insert(memo_key.2, value);
+        self.states += 1;
+        self.progress.update(self.states);
     }
 
     pub fn run(&mut self, current: &NodeRef, open_nodes: &[&NodeRef], minutes: u32) -> u32 {
@@ -198,7 +240,8 @@ impl DFS {
     }
 }
 
-fn find_all_possible_paths(node_map: NodeMap) -> Vec<(u32, Vec<NodeRef>)> {
+fn find_all_possible_paths(node_map: NodeMap, progress: &Progress) -> Vec<(u32, Vec<NodeRef>)> {
+    progress.phase("paths", None);
     let travel_distances = calculate_travel_distances(&node_map);
     let time_limit: u32 = 26;
     let mut queue = vec![(
@@ -224,9 +267,10 @@ fn find_all_possible_paths(node_map: NodeMap) -> Vec<(u32, Vec<NodeRef>)> {
         let next_visits = next
             .iter()
             .filter(|n| {
-                time as i64 - *distances
-                    .get(&n.borrow().name)
-                    .expect("Node should be in distances") as i64
+                time as i64
+                    - *distances
+                        .get(&n.borrow().name)
+                        .expect("Node should be in distances") as i64
                     > 1
             })
             .map(|n| n.clone())
@@ -256,40 +300,57 @@ fn find_all_possible_paths(node_map: NodeMap) -> Vec<(u32, Vec<NodeRef>)> {
             ));
             paths.push((next_pressure, next_road));
         }
+        progress.update(paths.len() as u64);
     }
     paths
 }
 
-fn find_best_result_with_2(mut paths: Vec<(u32, Vec<NodeRef>)>) -> u32 {
+fn find_best_result_with_2(mut paths: Vec<(u32, Vec<NodeRef>)>, progress: &Progress) -> u32 {
     paths.sort_by(|a, b| b.0.cmp(&a.0));
+    progress.phase("pairing path", Some(paths.len() as u64));
 
     let single_max = paths[0].0;
     let mut max = u32::MIN;
     for (index, path) in paths.iter().enumerate() {
+        progress.update(index as u64 + 1);
         if path.0 + single_max < max {
             continue; // this trick safes more than 9 seconds, or 90% of this function
-        } 
-        let first_path_not_reaching_max = paths.iter().position(|p| (path.0 + p.0) < max).unwrap_or(paths.len());
+        }
+        let first_path_not_reaching_max = paths
+            .iter()
+            .position(|p| (path.0 + p.0) < max)
+            .unwrap_or(paths.len());
         if first_path_not_reaching_max < index + 1 {
             continue;
         }
-        paths[index + 1..first_path_not_reaching_max].iter().for_each(|path_b| {
-            let combined_pressure = path.0 + path_b.0;
-            if combined_pressure > max {
-                if path.1.iter().all(|s| !path_b.1.contains(s)) {
-                    max = combined_pressure;
+        paths[index + 1..first_path_not_reaching_max]
+            .iter()
+            .for_each(|path_b| {
+                let combined_pressure = path.0 + path_b.0;
+                if combined_pressure > max {
+                    if path.1.iter().all(|s| !path_b.1.contains(s)) {
+                        max = combined_pressure;
+                    }
                 }
-            }
-        });
+            });
     }
     max
 }
 
-#[async_trait]
+inventory::submit! {
+    Day::new::<Puzzle>(2022, 16, "Proboscidea Volcanium", &["graph", "search", "slow"])
+}
+
 impl Solution for Puzzle {
-    async fn solve_a(&mut self, input: String) -> Result<Answer, String> {
-        let nodes: NodeMap = parse_input(input);
-        let mut dfs = DFS::new(nodes.clone());
+    type Parsed = Vec<Valve>;
+
+    fn parse(&self, input: &str) -> Result<Self::Parsed, SolveError> {
+        parse_input(input)
+    }
+
+    fn solve_a(&mut self, valves: &Self::Parsed) -> Result<Answer, SolveError> {
+        let nodes: NodeMap = build_nodes(valves);
+        let mut dfs = DFS::new(nodes.clone(), self.progress.clone());
 
         let start_node = nodes.get("AA").expect("Starting node should be present");
         let open_nodes = nodes
@@ -300,20 +361,23 @@ impl Solution for Puzzle {
         Answer::from(result).into()
     }
 
-    async fn solve_b(&mut self, input: String) -> Result<Answer, String> {
-        let nodes: NodeMap = parse_input(input);
-        let paths = find_all_possible_paths(nodes);
+    fn solve_b(&mut self, valves: &Self::Parsed) -> Result<Answer, SolveError> {
+        let nodes: NodeMap = build_nodes(valves);
+        let paths = find_all_possible_paths(nodes, &self.progress);
 
-        Answer::from(find_best_result_with_2(paths)).into()
+        Answer::from(find_best_result_with_2(paths, &self.progress)).into()
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::Puzzle;
-    use common::{Answer, Solution};
+    fn set_progress(&mut self, progress: Progress) {
+        self.progress = progress;
+    }
+
+    fn examples(&self) -> Vec<Example> {
+        vec![Example::new(TEST_INPUT).part_a(1651).part_b(1707)]
+    }
+}
 
-    const TEST_INPUT: &str = "Valve AA has flow rate=0; tunnels lead to valves DD, II, BB
+const TEST_INPUT: &str = "Valve AA has flow rate=0; tunnels lead to valves DD, II, BB
 Valve BB has flow rate=13; tunnels lead to valves CC, AA
 Valve CC has flow rate=2; tunnels lead to valves DD, BB
 Valve DD has flow rate=20; tunnels lead to valves CC, AA, EE
@@ -324,20 +388,25 @@ Valve HH has flow rate=22; tunnel leads to valve GG
 Valve II has flow rate=0; tunnels lead to valves AA, JJ
 Valve JJ has flow rate=21; tunnel leads to valve II";
 
-    #[tokio::test]
-    async fn part_a() {
+#[cfg(test)]
+mod tests {
+    use super::{Puzzle, TEST_INPUT};
+    use common::{Answer, Solution};
+
+    #[test]
+    fn part_a() {
         let mut puzzle = Puzzle::default();
         assert_eq!(
-            puzzle.solve_a(String::from(TEST_INPUT)).await,
+            puzzle.solve_a(&puzzle.parse(TEST_INPUT).unwrap()),
             Ok(Answer::from(1651))
         )
     }
 
-    #[tokio::test]
-    async fn part_b() {
+    #[test]
+    fn part_b() {
         let mut puzzle = Puzzle::default();
         assert_eq!(
-            puzzle.solve_b(String::from(TEST_INPUT)).await,
+            puzzle.solve_b(&puzzle.parse(TEST_INPUT).unwrap()),
             Ok(Answer::from(1707))
         )
     }
@@ -1,29 +1,26 @@
 use std::collections::HashMap;
 
-use async_trait::async_trait;
-use common::{Answer, Solution};
+use common::{Answer, Color, Example, Progress, Renderer, Solution, SolveError};
+
+use crate::registry::Day;
 
 const WIDTH: usize = 7;
+/// Rows at the top of the tower shown in a frame
+const VIEW_HEIGHT: usize = 40;
 type Row = [bool; WIDTH];
 
 type Position = (usize, usize);
 
-
 type BlockShape = Vec<Vec<bool>>;
 type Block = (usize, BlockShape);
 
-struct Minus { }
+struct Minus {}
 impl Minus {
     fn get() -> Block {
-        (
-            4,
-            vec![
-                vec![true, true, true, true],
-            ]
-        )
+        (4, vec![vec![true, true, true, true]])
     }
 }
-struct Plus { }
+struct Plus {}
 impl Plus {
     fn get() -> Block {
         (
@@ -32,11 +29,11 @@ impl Plus {
                 vec![false, true, false],
                 vec![true, true, true],
                 vec![false, true, false],
-            ]
+            ],
         )
     }
 }
-struct Corner { }
+struct Corner {}
 impl Corner {
     fn get() -> Block {
         (
@@ -45,63 +42,61 @@ impl Corner {
                 vec![true, true, true],
                 vec![false, false, true],
                 vec![false, false, true],
-            ]
+            ],
         )
     }
 }
-struct Pipe { }
+struct Pipe {}
 impl Pipe {
     fn get() -> Block {
-        (
-            1,
-            vec![
-                vec![true],
-                vec![true],
-                vec![true],
-                vec![true],
-            ]
-        )
+        (1, vec![vec![true], vec![true], vec![true], vec![true]])
     }
 }
-struct Square { }
+struct Square {}
 impl Square {
     fn get() -> Block {
-        (
-            2,
-            vec![
-                vec![true, true],
-                vec![true, true],
-            ]
-        )
+        (2, vec![vec![true, true], vec![true, true]])
     }
 }
 
-#[derive(Debug)]
-enum Jet {
+#[derive(Debug, Clone)]
+pub enum Jet {
     Left,
-    Right
+    Right,
 }
 
-
 #[derive(Default)]
-pub struct Puzzle {}
+pub struct Puzzle {
+    progress: Progress,
+    renderer: Renderer,
+}
 
 pub struct TetrisPuzzle {
     rows: Vec<Row>,
     blocks: Vec<Block>,
     jets: Vec<Jet>,
     jet_index: usize,
-    cycle_cache: HashMap<usize, (usize, usize)>
+    cycle_cache: HashMap<usize, (usize, usize)>,
+    progress: Progress,
+    renderer: Renderer,
 }
 
 impl TetrisPuzzle {
-    pub fn new(input: String) -> Self {
-        Self { 
+    fn new(jets: Vec<Jet>, progress: Progress, renderer: Renderer) -> Self {
+        Self {
             rows: Default::default(),
-            blocks: vec![Minus::get(), Plus::get(), Corner::get(), Pipe::get(), Square::get()],
-            jets: input_to_jets(input),
+            blocks: vec![
+                Minus::get(),
+                Plus::get(),
+                Corner::get(),
+                Pipe::get(),
+                Square::get(),
+            ],
+            jets,
             jet_index: 0,
-            cycle_cache: Default::default()
+            cycle_cache: Default::default(),
+            progress,
+            renderer,
         }
     }
 
@@ -110,7 +105,7 @@ impl TetrisPuzzle {
         row.get(x)
     }
     fn activate_cell(&mut self, x: usize, y: usize) {
-        while self.rows.len() <= y  {
+        while self.rows.len() <= y {
             self.rows.push([false; WIDTH]);
         }
         let row = self.rows.get_mut(y).expect("Row not found");
@@ -119,18 +114,25 @@ impl TetrisPuzzle {
 
     fn drop_blocks(&mut self, indexes: std::ops::Range<usize>) -> usize {
         let end = indexes.end;
+        self.progress.phase("rock", Some(end as u64));
         for i in indexes {
+            self.progress.update(i as u64);
             let cycle_id = self.cycle_id_for_index(i);
             if i < 2500 || !self.cycle_cache.contains_key(&cycle_id) {
                 self.drop_block(i);
+                self.render(i + 1);
                 continue;
             }
             // Possible cycle detected
-            let (previous_index, bottom) = self.cycle_cache.get(&cycle_id).expect("Cycle should be present");
+            let (previous_index, bottom) = self
+                .cycle_cache
+                .get(&cycle_id)
+                .expect("Cycle should be present");
             let cycle_length: usize = i - previous_index;
             if i % cycle_length != end % cycle_length {
                 // Can't calculate the end of the cycle, so just drop the block
                 self.drop_block(i);
+                self.render(i + 1);
                 continue;
             }
             let current_bottom = self.rows.len();
@@ -174,8 +176,10 @@ impl TetrisPuzzle {
     fn apply_jet(&self, pos: Position, jet: &Jet, block: &Block) -> Position {
         match jet {
             Jet::Left if pos.0 >= 1 && self.can_go_left(pos, block) => (pos.0 - 1, pos.1),
-            Jet::Right if pos.0 + block.0 < WIDTH && self.can_go_right(pos, block) => (pos.0 + 1, pos.1),
-            _ => pos
+            Jet::Right if pos.0 + block.0 < WIDTH && self.can_go_right(pos, block) => {
+                (pos.0 + 1, pos.1)
+            }
+            _ => pos,
         }
     }
 
@@ -183,10 +187,7 @@ impl TetrisPuzzle {
         for (y, row) in block.1.iter().enumerate() {
             for (x, cell) in row.iter().enumerate() {
                 if *cell {
-                    if self.get_cell(
-                        (pos.0 - 1) + x, 
-                        pos.1 + y
-                    ) == Some(&true) {
+                    if self.get_cell((pos.0 - 1) + x, pos.1 + y) == Some(&true) {
                         return false;
                     }
                     break;
@@ -195,7 +196,7 @@ impl TetrisPuzzle {
         }
         return true;
     }
-    
+
     fn can_go_right(&self, pos: Position, block: &Block) -> bool {
         for (y, row) in block.1.iter().enumerate() {
             for (x, cell) in row.iter().enumerate().rev() {
@@ -209,7 +210,7 @@ impl TetrisPuzzle {
         }
         return true;
     }
-    
+
     fn can_go_down(&self, pos: Position, block: &Block) -> bool {
         if pos.1 <= 0 {
             return false;
@@ -227,6 +228,41 @@ impl TetrisPuzzle {
         return true;
     }
 
+    /// Draws the top of the tower, between the walls of the chamber.
+    fn render(&self, rocks: usize) {
+        if !self.renderer.is_enabled() {
+            return;
+        }
+        let height = self.rows.len() as i64;
+        let rows = self.rows.iter().rev().take(VIEW_HEIGHT).map(|r| {
+            r.map(|c| if c { '#' } else { '.' })
+                .iter()
+                .collect::<String>()
+        });
+        self.renderer
+            .grid((0, -height), rows, &[('#', Color::BLUE)]);
+        let (left, right) = (-1, WIDTH as i64);
+        let (top, bottom) = (-height - 3, (VIEW_HEIGHT as i64 - height).min(0));
+        if bottom == 0 {
+            self.renderer.path(
+                [(left, top), (left, bottom), (right, bottom), (right, top)],
+                Color::GRAY,
+            );
+        } else {
+            // The floor is out of view
+            self.renderer
+                .path([(left, top), (left, bottom)], Color::GRAY);
+            self.renderer
+                .path([(right, top), (right, bottom)], Color::GRAY);
+        }
+        self.renderer.text(
+            (left, top - 5),
+            format!("rocks: {}, height: {}", rocks, height),
+            Color::BLACK,
+        );
+        self.renderer.flush();
+    }
+
     fn cycle_id_for_index(&self, index: usize) -> usize {
         (self.jet_index << self.blocks.len()) + (index % self.blocks.len())
     }
@@ -234,55 +270,99 @@ impl TetrisPuzzle {
 
 impl std::fmt::Display for TetrisPuzzle {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let mut row_strs = self.rows.iter().map(|r| r.map(|c| if c { '#' } else { '.' }).iter().collect::<String>()).collect::<Vec<_>>();
+        let mut row_strs = self
+            .rows
+            .iter()
+            .map(|r| {
+                r.map(|c| if c { '#' } else { '.' })
+                    .iter()
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>();
         row_strs.reverse();
         f.write_str(&row_strs.join("\n"))
     }
 }
 
-fn input_to_jets(input: String) -> Vec<Jet> {
-    input.chars().map(|c| match c {
-        '<' => Jet::Left,
-        '>' => Jet::Right,
-        _ => panic!("Invalid jet: {}", c),
-    }).collect::<Vec<_>>()
+fn input_to_jets(input: &str) -> Result<Vec<Jet>, SolveError> {
+    let jets = input
+        .char_indices()
+        .map(|(i, c)| match c {
+            '<' => Ok(Jet::Left),
+            '>' => Ok(Jet::Right),
+            _ => Err(SolveError::parse(
+                input,
+                &input[i..i + c.len_utf8()],
+                "Invalid jet",
+            )),
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    if jets.is_empty() {
+        return Err(SolveError::unsupported("No jets to push the rocks"));
+    }
+    Ok(jets)
+}
+
+inventory::submit! {
+    Day::new::<Puzzle>(2022, 17, "Pyroclastic Flow", &["simulation", "cycle-detection"])
 }
 
-#[async_trait]
 impl Solution for Puzzle {
-    async fn solve_a(&mut self, input: String) -> Result<Answer, String> {
-        let mut tetris: TetrisPuzzle = TetrisPuzzle::new(input);
+    type Parsed = Vec<Jet>;
+
+    fn parse(&self, input: &str) -> Result<Self::Parsed, SolveError> {
+        input_to_jets(input)
+    }
+
+    fn solve_a(&mut self, jets: &Self::Parsed) -> Result<Answer, SolveError> {
+        let mut tetris: TetrisPuzzle =
+            TetrisPuzzle::new(jets.clone(), self.progress.clone(), self.renderer.clone());
         Answer::from(tetris.drop_blocks(0..2022)).into()
     }
 
-    async fn solve_b(&mut self, input: String) -> Result<Answer, String> {
-        let mut tetris: TetrisPuzzle = TetrisPuzzle::new(input);
+    fn solve_b(&mut self, jets: &Self::Parsed) -> Result<Answer, SolveError> {
+        let mut tetris: TetrisPuzzle =
+            TetrisPuzzle::new(jets.clone(), self.progress.clone(), self.renderer.clone());
         Answer::from(tetris.drop_blocks(0..1000000000000)).into()
     }
+
+    fn set_progress(&mut self, progress: Progress) {
+        self.progress = progress;
+    }
+
+    fn set_renderer(&mut self, renderer: Renderer) {
+        self.renderer = renderer;
+    }
+
+    fn examples(&self) -> Vec<Example> {
+        vec![Example::new(TEST_INPUT)
+            .part_a(3068)
+            .part_b(1514285714288_i64)]
+    }
 }
 
+const TEST_INPUT: &str = ">>><<><>><<<>><>>><<<>>><<<><<<>><>><<>>";
+
 #[cfg(test)]
 mod tests {
-    use super::Puzzle;
+    use super::{Puzzle, TEST_INPUT};
     use common::{Answer, Solution};
 
-    const TEST_INPUT: &str = ">>><<><>><<<>><>>><<<>>><<<><<<>><>><<>>";
-
-    #[tokio::test]
-    async fn part_a() {
+    #[test]
+    fn part_a() {
         let mut puzzle = Puzzle::default();
         assert_eq!(
-            puzzle.solve_a(String::from(TEST_INPUT)).await,
+            puzzle.solve_a(&puzzle.parse(TEST_INPUT).unwrap()),
             Ok(Answer::from(3068))
         )
     }
 
-    #[tokio::test]
-    async fn part_b() {
+    #[test]
+    fn part_b() {
         let mut puzzle = Puzzle::default();
         assert_eq!(
-            puzzle.solve_b(String::from(TEST_INPUT)).await,
+            puzzle.solve_b(&puzzle.parse(TEST_INPUT).unwrap()),
             Ok(Answer::from(1514285714288 as i64))
         )
     }
-}
\ No newline at end of file
+}
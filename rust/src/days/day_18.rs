@@ -1,7 +1,8 @@
 use std::collections::{HashMap, HashSet, VecDeque};
 
-use async_trait::async_trait;
-use common::{Answer, Solution};
+use common::{Answer, Example, Solution, SolveError};
+
+use crate::registry::Day;
 
 type Int = isize;
 type Coord = (Int, Int, Int);
@@ -129,39 +130,52 @@ fn get_neighbors_of(c: &Coord) -> Vec<Coord> {
     return neighbors;
 }
 
-fn coords_from_input(str: &str) -> Vec<Coord> {
+fn coords_from_input(str: &str) -> Result<Vec<Coord>, SolveError> {
     str.trim()
         .split('\n')
-        .map(|s| coord_from_str(s))
-        .collect::<Vec<_>>()
+        .map(|s| coord_from_str(str, s))
+        .collect::<Result<Vec<_>, _>>()
 }
 
-fn coord_from_str(str: &str) -> Coord {
+fn coord_from_str(input: &str, str: &str) -> Result<Coord, SolveError> {
     let values = str
         .split(',')
-        .map(|s| s.parse::<Int>().expect("Valid usize"))
-        .collect::<Vec<_>>();
-    (values[0], values[1], values[2])
+        .map(|s| {
+            s.parse::<Int>()
+                .map_err(|_| SolveError::parse(input, s, "Invalid coordinate"))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    match values[..] {
+        [x, y, z] => Ok((x, y, z)),
+        _ => Err(SolveError::parse(input, str, "Expected x,y,z")),
+    }
+}
+
+inventory::submit! {
+    Day::new::<Puzzle>(2022, 18, "Boiling Boulders", &["3d", "flood-fill"])
 }
 
-#[async_trait]
 impl Solution for Puzzle {
-    async fn solve_a(&mut self, input: String) -> Result<Answer, String> {
-        let coords = coords_from_input(&input);
-        self.add_coords(&coords);
-        Answer::from(self.locate_sides(&coords).len()).into()
+    type Parsed = Vec<Coord>;
+
+    fn parse(&self, input: &str) -> Result<Self::Parsed, SolveError> {
+        coords_from_input(input)
     }
 
-    async fn solve_b(&mut self, input: String) -> Result<Answer, String> {
-        let coords = coords_from_input(&input);
-        self.add_coords(&coords);
+    fn solve_a(&mut self, coords: &Self::Parsed) -> Result<Answer, SolveError> {
+        self.add_coords(coords);
+        Answer::from(self.locate_sides(coords).len()).into()
+    }
+
+    fn solve_b(&mut self, coords: &Self::Parsed) -> Result<Answer, SolveError> {
+        self.add_coords(coords);
         let x_range = coords
             .iter()
             .map(|c| c.0)
             .fold((Int::MAX, Int::MIN), |range, c| {
                 (range.0.min(c), range.1.max(c))
             });
-        let sides = self.locate_sides(&coords);
+        let sides = self.locate_sides(coords);
 
         let mut inside_sides = sides.clone();
         inside_sides.sort();
@@ -180,14 +194,13 @@ impl Solution for Puzzle {
         )
         .into()
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::Puzzle;
-    use common::{Answer, Solution};
+    fn examples(&self) -> Vec<Example> {
+        vec![Example::new(TEST_INPUT).part_a(64).part_b(58)]
+    }
+}
 
-    const TEST_INPUT: &str = "2,2,2
+const TEST_INPUT: &str = "2,2,2
 1,2,2
 3,2,2
 2,1,2
@@ -201,20 +214,25 @@ mod tests {
 2,1,5
 2,3,5";
 
-    #[tokio::test]
-    async fn part_a() {
+#[cfg(test)]
+mod tests {
+    use super::{Puzzle, TEST_INPUT};
+    use common::{Answer, Solution};
+
+    #[test]
+    fn part_a() {
         let mut puzzle = Puzzle::default();
         assert_eq!(
-            puzzle.solve_a(String::from(TEST_INPUT)).await,
+            puzzle.solve_a(&puzzle.parse(TEST_INPUT).unwrap()),
             Ok(Answer::from(64))
         )
     }
 
-    #[tokio::test]
-    async fn part_b() {
+    #[test]
+    fn part_b() {
         let mut puzzle = Puzzle::default();
         assert_eq!(
-            puzzle.solve_b(String::from(TEST_INPUT)).await,
+            puzzle.solve_b(&puzzle.parse(TEST_INPUT).unwrap()),
             Ok(Answer::from(58))
         )
     }
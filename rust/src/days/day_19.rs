@@ -1,11 +1,13 @@
-use std::{str::FromStr, collections::{HashMap, HashSet, VecDeque}};
+use std::collections::{HashMap, HashSet, VecDeque};
 
-use async_trait::async_trait;
-use common::{Answer, Solution};
+use common::{Answer, Example, Progress, Solution, SolveError};
 use regex::Regex;
 
+use crate::registry::Day;
+
 pub struct Puzzle {
     max_geode_increment: Vec<i32>,
+    progress: Progress,
 }
 
 type Costs = [i32; 4];
@@ -13,47 +15,38 @@ type OreStash = [i32; 4];
 type BotCount = [i32; 4];
 type QueueItem = (OreStash, BotCount, i32);
 
-struct Blueprint {
+pub struct Blueprint {
     id: i32,
     bot_costs: [Costs; 4],
-    max_cost: Costs
+    max_cost: Costs,
 }
-impl FromStr for Blueprint {
-    type Err = String;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
+impl Blueprint {
+    /// Parses `line`, a slice of `input` so errors point into the whole input.
+    fn parse(input: &str, line: &str) -> Result<Self, SolveError> {
         let captures = Regex::new(
-            r"Blueprint (\d+): Each ore robot costs (\d+) ore. Each clay robot costs (\d+) ore. Each obsidian robot costs (\d+) ore and (\d+) clay. Each geode robot costs (\d+) ore and (\d+) obsidian.").expect("Valid regex").captures(s)
-        .expect("At least one match");
-        fn get_capture_as_i32(captures: &regex::Captures, index: usize) -> i32 {
-            captures
-                .get(index)
-                .expect(&format!("Should have an match at index: {}", index))
-                .as_str()
+            r"Blueprint (\d+): Each ore robot costs (\d+) ore. Each clay robot costs (\d+) ore. Each obsidian robot costs (\d+) ore and (\d+) clay. Each geode robot costs (\d+) ore and (\d+) obsidian.").expect("Valid regex").captures(line)
+        .ok_or_else(|| SolveError::parse(input, line, "Expected a blueprint"))?;
+        let mut values = [0; 7];
+        for (index, value) in values.iter_mut().enumerate() {
+            let capture = captures.get(index + 1).map_or("", |c| c.as_str());
+            *value = capture
                 .parse()
-                .expect(&format!("Parsable as i32 at index: {}", index))
+                .map_err(|_| SolveError::parse(input, capture, "Number out of range"))?;
         }
+        let [id, ore_ore, clay_ore, obsidian_ore, obsidian_clay, geode_ore, geode_obsidian] =
+            values;
         Ok(Self {
-            id: get_capture_as_i32(&captures, 1),
+            id,
             bot_costs: [
-            [get_capture_as_i32(&captures, 2), 0, 0, 0],
-            [get_capture_as_i32(&captures, 3), 0, 0, 0],
-            [
-                get_capture_as_i32(&captures, 4),
-                get_capture_as_i32(&captures, 5),
-                0,
-                0,
+                [ore_ore, 0, 0, 0],
+                [clay_ore, 0, 0, 0],
+                [obsidian_ore, obsidian_clay, 0, 0],
+                [geode_ore, 0, geode_obsidian, 0],
             ],
-            [
-                get_capture_as_i32(&captures, 6),
-                0,
-                get_capture_as_i32(&captures, 7),
-                0,
-            ]],
             max_cost: [
-                get_capture_as_i32(&captures, 2).max(get_capture_as_i32(&captures, 3).max(get_capture_as_i32(&captures, 4).max(get_capture_as_i32(&captures, 6)))),
-                get_capture_as_i32(&captures, 5),
-                get_capture_as_i32(&captures, 7),
+                ore_ore.max(clay_ore.max(obsidian_ore.max(geode_ore))),
+                obsidian_clay,
+                geode_obsidian,
                 0,
             ],
         })
@@ -67,36 +60,39 @@ impl Default for Puzzle {
             max_geode_increment[m] = max_geode_increment[m - 1] + (m as i32);
         }
         Self {
-            max_geode_increment
+            max_geode_increment,
+            progress: Progress::default(),
         }
     }
 }
 
 impl Puzzle {
     fn find_best_outcome_for_blueprint(&self, bp: &Blueprint, max_time: i32) -> i32 {
-        let mut queue: VecDeque<QueueItem> = vec![(
-            [0, 0, 0, 0] as OreStash,
-            [1, 0, 0, 0] as BotCount,
-            0
-        )].into();
-        let mut visits: Vec<HashMap<BotCount, HashSet<OreStash>>> = vec![HashMap::new(); (max_time + 1) as usize];
+        let mut queue: VecDeque<QueueItem> =
+            vec![([0, 0, 0, 0] as OreStash, [1, 0, 0, 0] as BotCount, 0)].into();
+        let mut visits: Vec<HashMap<BotCount, HashSet<OreStash>>> =
+            vec![HashMap::new(); (max_time + 1) as usize];
         let mut max_geode_count = 0;
         while let Some(current) = queue.pop_back() {
             if current.2 >= max_time {
                 max_geode_count = max_geode_count.max(current.0[3]);
-                continue
+                continue;
             }
             let time_left = max_time - current.2;
 
-            let best_case_max = self.max_geode_increment[time_left as usize] + current.0[3] + (current.1[3] * time_left);
+            let best_case_max = self.max_geode_increment[time_left as usize]
+                + current.0[3]
+                + (current.1[3] * time_left);
             if max_geode_count > best_case_max {
-                continue
+                continue;
             }
 
-            let time_cache = visits.get_mut(current.2 as usize).expect("Time cache exists");
+            let time_cache = visits
+                .get_mut(current.2 as usize)
+                .expect("Time cache exists");
             if let Some(bot_cache) = time_cache.get_mut(&current.1) {
                 if bot_cache.contains(&current.0) {
-                    continue
+                    continue;
                 }
                 bot_cache.insert(current.0);
             } else {
@@ -109,86 +105,119 @@ impl Puzzle {
     }
 }
 
-fn find_bot_purchase_options((ores, bots, time): &QueueItem, bp: &Blueprint) -> VecDeque<QueueItem> {
+fn find_bot_purchase_options(
+    (ores, bots, time): &QueueItem,
+    bp: &Blueprint,
+) -> VecDeque<QueueItem> {
     let mut options: VecDeque<QueueItem> = vec![].into();
     for bot in (0..=3).rev() {
-        if bot != 3 && (bp.max_cost[bot] <= bots[bot]) { // we don't need more of these!
-            continue
+        if bot != 3 && (bp.max_cost[bot] <= bots[bot]) {
+            // we don't need more of these!
+            continue;
         }
         let cost = bp.bot_costs[bot];
         if ores.iter().enumerate().all(|(i, o)| o >= &cost[i]) {
             let mut new_bots = bots.clone();
             new_bots[bot] = new_bots[bot] + 1;
             options.push_back((
-                ores.iter().enumerate().map(|(i, o)| (o + bots[i]) - cost[i]).collect::<Vec<_>>().try_into().unwrap(),
+                ores.iter()
+                    .enumerate()
+                    .map(|(i, o)| (o + bots[i]) - cost[i])
+                    .collect::<Vec<_>>()
+                    .try_into()
+                    .unwrap(),
                 new_bots,
-                time + 1
+                time + 1,
             ));
-            if bot == 3 { // we can buy a geode bot, ignore all other options.
-                return options
+            if bot == 3 {
+                // we can buy a geode bot, ignore all other options.
+                return options;
             }
         }
     }
     if ores[0] <= bp.max_cost[0] {
         options.push_front((
-            ores.iter().enumerate().map(|(i, o)| o + bots[i]).collect::<Vec<_>>().try_into().unwrap(),
+            ores.iter()
+                .enumerate()
+                .map(|(i, o)| o + bots[i])
+                .collect::<Vec<_>>()
+                .try_into()
+                .unwrap(),
             bots.clone(),
-            time + 1
+            time + 1,
         ))
     }
     options
 }
 
-#[async_trait]
+inventory::submit! {
+    Day::new::<Puzzle>(2022, 19, "Not Enough Minerals", &["search", "slow"])
+}
+
 impl Solution for Puzzle {
-    async fn solve_a(&mut self, input: String) -> Result<Answer, String> {
-        let blueprints = input
+    type Parsed = Vec<Blueprint>;
+
+    fn parse(&self, input: &str) -> Result<Self::Parsed, SolveError> {
+        input
             .lines()
-            .map(|line| line.parse::<Blueprint>().expect("Parsable as Blueprint"))
-            .collect::<Vec<_>>();
+            .map(|line| Blueprint::parse(input, line))
+            .collect()
+    }
 
-        Answer::from(blueprints.into_iter().fold(0, |sum, bp| {
-            let output = self.find_best_outcome_for_blueprint(&bp, 24);
+    fn solve_a(&mut self, blueprints: &Self::Parsed) -> Result<Answer, SolveError> {
+        self.progress
+            .phase("blueprint", Some(blueprints.len() as u64));
+        Answer::from(blueprints.iter().enumerate().fold(0, |sum, (index, bp)| {
+            let output = self.find_best_outcome_for_blueprint(bp, 24);
+            self.progress.update(index as u64 + 1);
             sum + (output * bp.id)
-        })).into()
+        }))
+        .into()
     }
 
-    async fn solve_b(&mut self, input: String) -> Result<Answer, String> {
-        let blueprints = input
-            .lines()
-            .map(|line| line.parse::<Blueprint>().expect("Parsable as Blueprint"))
-            .take(3)
-            .collect::<Vec<_>>();
-
-        Answer::from(blueprints.into_iter().fold(1, |sum, bp| {
-            let output = self.find_best_outcome_for_blueprint(&bp, 32);
+    fn solve_b(&mut self, blueprints: &Self::Parsed) -> Result<Answer, SolveError> {
+        let blueprints = &blueprints[..blueprints.len().min(3)];
+        self.progress
+            .phase("blueprint", Some(blueprints.len() as u64));
+        Answer::from(blueprints.iter().enumerate().fold(1, |sum, (index, bp)| {
+            let output = self.find_best_outcome_for_blueprint(bp, 32);
+            self.progress.update(index as u64 + 1);
             sum * output
-        })).into()
+        }))
+        .into()
+    }
+
+    fn set_progress(&mut self, progress: Progress) {
+        self.progress = progress;
+    }
+
+    fn examples(&self) -> Vec<Example> {
+        vec![Example::new(TEST_INPUT).part_a(33).part_b(56 * 62)]
     }
 }
 
+const TEST_INPUT: &str = "Blueprint 1: Each ore robot costs 4 ore. Each clay robot costs 2 ore. Each obsidian robot costs 3 ore and 14 clay. Each geode robot costs 2 ore and 7 obsidian.
+Blueprint 2: Each ore robot costs 2 ore. Each clay robot costs 3 ore. Each obsidian robot costs 3 ore and 8 clay. Each geode robot costs 3 ore and 12 obsidian.";
+
 #[cfg(test)]
 mod tests {
-    use super::Puzzle;
+    use super::{Puzzle, TEST_INPUT};
     use common::{Answer, Solution};
 
-    const TEST_INPUT: &str = "Blueprint 1: Each ore robot costs 4 ore. Each clay robot costs 2 ore. Each obsidian robot costs 3 ore and 14 clay. Each geode robot costs 2 ore and 7 obsidian.
-Blueprint 2: Each ore robot costs 2 ore. Each clay robot costs 3 ore. Each obsidian robot costs 3 ore and 8 clay. Each geode robot costs 3 ore and 12 obsidian.";
-
-    #[tokio::test]
-    async fn part_a() {
+    #[test]
+    fn part_a() {
         let mut puzzle = Puzzle::default();
         assert_eq!(
-            puzzle.solve_a(String::from(TEST_INPUT)).await,
+            puzzle.solve_a(&puzzle.parse(TEST_INPUT).unwrap()),
             Ok(Answer::from(33))
         )
     }
 
-    #[tokio::test]
-    async fn part_b() {
+    #[test]
+    fn part_b() {
         let mut puzzle = Puzzle::default();
         assert_eq!(
-            puzzle.solve_b(String::from(TEST_INPUT)).await,
+            puzzle.solve_b(&puzzle.parse(TEST_INPUT).unwrap()),
             Ok(Answer::from(56 * 62))
         )
     }
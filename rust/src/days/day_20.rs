@@ -1,7 +1,8 @@
-use std::{cell::RefCell, rc::Rc, str::FromStr};
+use std::{cell::RefCell, rc::Rc};
 
-use async_trait::async_trait;
-use common::{Answer, Solution};
+use common::{Answer, Example, Solution, SolveError};
+
+use crate::registry::Day;
 
 pub struct Puzzle {}
 
@@ -18,18 +19,6 @@ struct Digit {
     prev: Link<Digit>,
 }
 
-impl FromStr for Digit {
-    type Err = String;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        Ok(Self {
-            value: s.parse::<i64>().unwrap(),
-            next: None,
-            prev: None,
-        })
-    }
-}
-
 impl Digit {
     pub fn mix(&mut self, digit_count: i64) {
         if self.value == 0 {
@@ -96,18 +85,39 @@ impl Digit {
     }
 }
 
-// Convert input list to custom double linked list
-fn parse_input(input: String) -> Vec<Rc<RefCell<Digit>>> {
-    let dummy_start: Rc<RefCell<Digit>> =
-        Rc::new(RefCell::new(Digit::from_str("0").unwrap().into()));
-    let mut prev = dummy_start.clone();
-    let list: Vec<Rc<RefCell<Digit>>> = input
+fn parse_input(input: &str) -> Result<Vec<i64>, SolveError> {
+    let numbers: Vec<i64> = input
         .trim()
         .split("\n")
-        .into_iter()
         .map(|d| {
+            d.parse::<i64>()
+                .map_err(|_| SolveError::parse(input, d, "Invalid number"))
+        })
+        .collect::<Result<_, SolveError>>()?;
+    if numbers.len() < 2 {
+        return Err(SolveError::unsupported("Mixing needs at least 2 numbers"));
+    }
+    if !numbers.contains(&0) {
+        return Err(SolveError::unsupported(
+            "No 0 to count the grove coordinates from",
+        ));
+    }
+    Ok(numbers)
+}
+
+// Convert input list to custom double linked list
+fn link_digits(numbers: &[i64]) -> Vec<Rc<RefCell<Digit>>> {
+    let dummy_start: Rc<RefCell<Digit>> = Rc::new(RefCell::new(Digit {
+        value: 0,
+        next: None,
+        prev: None,
+    }));
+    let mut prev = dummy_start.clone();
+    let list: Vec<Rc<RefCell<Digit>>> = numbers
+        .iter()
+        .map(|value| {
             let digit = Digit {
-                value: d.parse::<i64>().unwrap(),
+                value: *value,
                 next: None,
                 prev: Some(Rc::clone(&prev)),
             };
@@ -121,13 +131,22 @@ fn parse_input(input: String) -> Vec<Rc<RefCell<Digit>>> {
     let last_node = list.last().unwrap();
     first_node.as_ref().borrow_mut().prev = Some(Rc::clone(last_node));
     last_node.as_ref().borrow_mut().next = Some(Rc::clone(first_node));
-    return list;
+    list
+}
+
+inventory::submit! {
+    Day::new::<Puzzle>(2022, 20, "Grove Positioning System", &["list"])
 }
 
-#[async_trait]
 impl Solution for Puzzle {
-    async fn solve_a(&mut self, input: String) -> Result<Answer, String> {
-        let list = parse_input(input);
+    type Parsed = Vec<i64>;
+
+    fn parse(&self, input: &str) -> Result<Self::Parsed, SolveError> {
+        parse_input(input)
+    }
+
+    fn solve_a(&mut self, numbers: &Self::Parsed) -> Result<Answer, SolveError> {
+        let list = link_digits(numbers);
         let list_len = list.len() as i64;
         for digit in list.iter() {
             digit.as_ref().borrow_mut().mix(list_len);
@@ -143,8 +162,8 @@ impl Solution for Puzzle {
         Answer::from(result).into()
     }
 
-    async fn solve_b(&mut self, input: String) -> Result<Answer, String> {
-        let list = parse_input(input);
+    fn solve_b(&mut self, numbers: &Self::Parsed) -> Result<Answer, SolveError> {
+        let list = link_digits(numbers);
         let list_len = list.len() as i64;
         let decipher_key = 811589153;
         for digit in list.iter() {
@@ -166,14 +185,13 @@ impl Solution for Puzzle {
             + _3000th_digit.borrow().value;
         Answer::from(result).into()
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::Puzzle;
-    use common::{Answer, Solution};
+    fn examples(&self) -> Vec<Example> {
+        vec![Example::new(TEST_INPUT).part_a(3).part_b(1623178306)]
+    }
+}
 
-    const TEST_INPUT: &str = "1
+const TEST_INPUT: &str = "1
 2
 -3
 3
@@ -181,20 +199,25 @@ mod tests {
 0
 4";
 
-    #[tokio::test]
-    async fn part_a() {
+#[cfg(test)]
+mod tests {
+    use super::{Puzzle, TEST_INPUT};
+    use common::{Answer, Solution};
+
+    #[test]
+    fn part_a() {
         let mut puzzle = Puzzle::default();
         assert_eq!(
-            puzzle.solve_a(String::from(TEST_INPUT)).await,
+            puzzle.solve_a(&puzzle.parse(TEST_INPUT).unwrap()),
             Ok(Answer::from(3))
         )
     }
 
-    #[tokio::test]
-    async fn part_b() {
+    #[test]
+    fn part_b() {
         let mut puzzle = Puzzle::default();
         assert_eq!(
-            puzzle.solve_b(String::from(TEST_INPUT)).await,
+            puzzle.solve_b(&puzzle.parse(TEST_INPUT).unwrap()),
             Ok(Answer::from(1623178306))
         )
     }
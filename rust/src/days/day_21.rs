@@ -1,7 +1,8 @@
 use std::{cell::RefCell, collections::HashMap, rc::Rc, str::FromStr};
 
-use async_trait::async_trait;
-use common::{Answer, Solution};
+use common::{Answer, Example, Solution, SolveError};
+
+use crate::registry::Day;
 
 pub struct Puzzle {}
 
@@ -24,32 +25,32 @@ struct Monkey {
 }
 
 impl Monkey {
-    pub fn get_value(&self) -> i64 {
+    pub fn get_value(&self) -> Result<i64, SolveError> {
         if let Some(value) = self.value {
-            value
+            Ok(value)
         } else {
             let left_monkey = self.left.as_ref().unwrap().borrow();
             let right_monkey = self.right.as_ref().unwrap().borrow();
-            let left_value = left_monkey.get_value();
-            let right_value = right_monkey.get_value();
+            let left_value = left_monkey.get_value()?;
+            let right_value = right_monkey.get_value()?;
             match self.operation.as_ref().unwrap() {
-                Operation::Add => left_value + right_value,
-                Operation::Sub => left_value - right_value,
-                Operation::Mul => left_value * right_value,
-                Operation::Div => left_value / right_value,
+                Operation::Add => Ok(left_value + right_value),
+                Operation::Sub => Ok(left_value - right_value),
+                Operation::Mul => Ok(left_value * right_value),
+                Operation::Div => divide(&self.name, left_value, right_value),
             }
         }
     }
 
-    pub fn find_human_value(&mut self, desired_outcome: i64) -> i64 {
+    pub fn find_human_value(&mut self, desired_outcome: i64) -> Result<i64, SolveError> {
         if self.name == HUMAN {
-            return desired_outcome;
+            return Ok(desired_outcome);
         }
         if self.value.is_some() {
-            return -1;
+            return Ok(-1);
         }
         if self.left.as_ref().unwrap().borrow_mut().has_human() {
-            let right = self.right.as_ref().unwrap().borrow().get_value();
+            let right = self.right.as_ref().unwrap().borrow().get_value()?;
             if self.name == ROOT {
                 self.left
                     .as_ref()
@@ -60,7 +61,7 @@ impl Monkey {
                 let target = match self.operation.as_ref().unwrap() {
                     Operation::Add => desired_outcome - right,
                     Operation::Sub => desired_outcome + right,
-                    Operation::Mul => desired_outcome / right,
+                    Operation::Mul => divide(&self.name, desired_outcome, right)?,
                     Operation::Div => desired_outcome * right,
                 };
                 return self
@@ -71,7 +72,7 @@ impl Monkey {
                     .find_human_value(target);
             }
         } else {
-            let left = self.left.as_ref().unwrap().borrow().get_value();
+            let left = self.left.as_ref().unwrap().borrow().get_value()?;
             if self.name == ROOT {
                 self.right
                     .as_ref()
@@ -82,8 +83,8 @@ impl Monkey {
                 let target = match self.operation.as_ref().unwrap() {
                     Operation::Add => desired_outcome - left,
                     Operation::Sub => left - desired_outcome,
-                    Operation::Mul => desired_outcome / left,
-                    Operation::Div => left / desired_outcome,
+                    Operation::Mul => divide(&self.name, desired_outcome, left)?,
+                    Operation::Div => divide(&self.name, left, desired_outcome)?,
                 };
                 self.right
                     .as_ref()
@@ -111,7 +112,8 @@ impl Monkey {
     }
 }
 
-enum Operation {
+#[derive(Clone, Copy)]
+pub enum Operation {
     Add,
     Sub,
     Mul,
@@ -132,15 +134,74 @@ impl FromStr for Operation {
     }
 }
 
-fn input_as_monkeys(input: String) -> HashMap<String, Rc<RefCell<Monkey>>> {
+fn divide(name: &str, left: i64, right: i64) -> Result<i64, SolveError> {
+    left.checked_div(right)
+        .ok_or_else(|| SolveError::unsupported(format!("Monkey {} divides by zero", name)))
+}
+
+/// What a monkey yells: a number, or the outcome of an operation on what two other monkeys yell
+pub enum Job {
+    Value(i64),
+    Operation(String, Operation, String),
+}
+
+fn parse_jobs(input: &str) -> Result<Vec<(String, Job)>, SolveError> {
+    let jobs: Vec<(String, Job)> = input
+        .trim()
+        .lines()
+        .map(|line| {
+            let (name, action) = line
+                .split_once(": ")
+                .ok_or_else(|| SolveError::parse(input, line, "Expected name: job"))?;
+            if let Ok(value) = action.parse::<i64>() {
+                return Ok((name.to_string(), Job::Value(value)));
+            }
+            let (left_monkey_name, operation, right_monkey_name) =
+                match action.split(" ").collect::<Vec<_>>()[..] {
+                    [left, operation, right] => (left, operation, right),
+                    _ => {
+                        return Err(SolveError::parse(
+                            input,
+                            action,
+                            "Expected a number or an operation",
+                        ))
+                    }
+                };
+            let operation: Operation = operation
+                .parse()
+                .map_err(|_| SolveError::parse(input, operation, "Invalid operation"))?;
+            Ok((
+                name.to_string(),
+                Job::Operation(
+                    left_monkey_name.to_string(),
+                    operation,
+                    right_monkey_name.to_string(),
+                ),
+            ))
+        })
+        .collect::<Result<_, SolveError>>()?;
+    for (_, job) in jobs.iter() {
+        if let Job::Operation(left, _, right) = job {
+            for name in [left, right] {
+                if !jobs.iter().any(|(monkey, _)| monkey == name) {
+                    return Err(SolveError::parse(input, name, "Unknown monkey"));
+                }
+            }
+        }
+    }
+    if !jobs.iter().any(|(name, _)| name == ROOT) {
+        return Err(SolveError::unsupported("No root monkey"));
+    }
+    Ok(jobs)
+}
+
+fn link_monkeys(jobs: &[(String, Job)]) -> HashMap<String, Rc<RefCell<Monkey>>> {
     let mut monkeys = HashMap::new();
-    for line in input.trim().lines() {
-        let mut parts = line.split(": ");
-        let name = parts.next().unwrap().to_string();
+    for (name, _) in jobs {
         monkeys.insert(
             name.clone(),
             Rc::new(RefCell::new(Monkey {
-                name,
+                name: name.clone(),
                 is_human_in_tree: None,
                 value: None,
                 left: None,
@@ -149,52 +210,64 @@ fn input_as_monkeys(input: String) -> HashMap<String, Rc<RefCell<Monkey>>> {
             })),
         );
     }
-    for line in input.trim().lines() {
-        let mut parts = line.split(": ");
-        let name = parts.next().unwrap().to_string();
-        let action = parts.next().unwrap().to_string();
-        let value = action.parse::<i64>().ok();
-        if value.is_some() {
-            monkeys.get(&name).unwrap().borrow_mut().value = value;
-        } else {
-            let mut opertion_parts = action.split(" ");
-            let left_monkey_name = opertion_parts.next().unwrap().to_string();
-            let operation: Operation = opertion_parts.next().unwrap().to_string().parse().unwrap();
-            let right_monkey_name = opertion_parts.next().unwrap().to_string();
-            {
-                let mut monkey = monkeys.get(&name).unwrap().borrow_mut();
-                monkey.left = Some(Rc::clone(monkeys.get(&left_monkey_name).unwrap()));
-                monkey.operation = Some(operation);
-                monkey.right = Some(Rc::clone(monkeys.get(&right_monkey_name).unwrap()));
+    let find = |name: &str| {
+        Rc::clone(
+            monkeys
+                .get(name)
+                .expect("Monkeys were checked while parsing"),
+        )
+    };
+    for (name, job) in jobs {
+        let monkey = find(name);
+        let mut monkey = monkey.borrow_mut();
+        match job {
+            Job::Value(value) => monkey.value = Some(*value),
+            Job::Operation(left_monkey_name, operation, right_monkey_name) => {
+                monkey.left = Some(find(left_monkey_name));
+                monkey.operation = Some(*operation);
+                monkey.right = Some(find(right_monkey_name));
             }
-        };
+        }
     }
     monkeys
 }
 
-#[async_trait]
+inventory::submit! {
+    Day::new::<Puzzle>(2022, 21, "Monkey Math", &["expression-tree"])
+}
+
 impl Solution for Puzzle {
-    async fn solve_a(&mut self, input: String) -> Result<Answer, String> {
-        let monkeys = input_as_monkeys(input);
+    /// Every monkey with its job
+    type Parsed = Vec<(String, Job)>;
+
+    fn parse(&self, input: &str) -> Result<Self::Parsed, SolveError> {
+        parse_jobs(input)
+    }
+
+    fn solve_a(&mut self, jobs: &Self::Parsed) -> Result<Answer, SolveError> {
+        let monkeys = link_monkeys(jobs);
         let root_monkey = monkeys.get(ROOT).unwrap();
-        let result = root_monkey.borrow().get_value();
+        let result = root_monkey.borrow().get_value()?;
         Answer::from(result).into()
     }
 
-    async fn solve_b(&mut self, input: String) -> Result<Answer, String> {
-        let monkeys = input_as_monkeys(input);
+    fn solve_b(&mut self, jobs: &Self::Parsed) -> Result<Answer, SolveError> {
+        let monkeys = link_monkeys(jobs);
         let root_monkey = monkeys.get(ROOT).unwrap();
-        let result = root_monkey.borrow_mut().find_human_value(0);
+        let has_value = root_monkey.borrow().value.is_some();
+        if has_value || !root_monkey.borrow_mut().has_human() {
+            return Err(SolveError::unsupported("Root does not depend on the human"));
+        }
+        let result = root_monkey.borrow_mut().find_human_value(0)?;
         Answer::from(result).into()
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::Puzzle;
-    use common::{Answer, Solution};
+    fn examples(&self) -> Vec<Example> {
+        vec![Example::new(TEST_INPUT).part_a(152).part_b(301)]
+    }
+}
 
-    const TEST_INPUT: &str = "root: pppw + sjmn
+const TEST_INPUT: &str = "root: pppw + sjmn
 dbpl: 5
 cczh: sllz + lgvd
 zczc: 2
@@ -210,20 +283,25 @@ lgvd: ljgn * ptdq
 drzm: hmdt - zczc
 hmdt: 32";
 
-    #[tokio::test]
-    async fn part_a() {
+#[cfg(test)]
+mod tests {
+    use super::{Puzzle, TEST_INPUT};
+    use common::{Answer, Solution};
+
+    #[test]
+    fn part_a() {
         let mut puzzle = Puzzle::default();
         assert_eq!(
-            puzzle.solve_a(String::from(TEST_INPUT)).await,
+            puzzle.solve_a(&puzzle.parse(TEST_INPUT).unwrap()),
             Ok(Answer::from(152))
         )
     }
 
-    #[tokio::test]
-    async fn part_b() {
+    #[test]
+    fn part_b() {
         let mut puzzle = Puzzle::default();
         assert_eq!(
-            puzzle.solve_b(String::from(TEST_INPUT)).await,
+            puzzle.solve_b(&puzzle.parse(TEST_INPUT).unwrap()),
             Ok(Answer::from(301))
         )
     }
@@ -1,47 +1,425 @@
-use async_trait::async_trait;
-use common::{Answer, Solution};
+use std::collections::{HashMap, VecDeque};
 
+use common::{Answer, Example, Solution, SolveError};
+
+use crate::registry::Day;
+
+#[derive(Default)]
 pub struct Puzzle {}
 
-impl Default for Puzzle {
-    fn default() -> Self {
-        Self { }
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Tile {
+    Void,
+    Open,
+    Wall,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Instruction {
+    Forward(usize),
+    Left,
+    Right,
+}
+
+type Position = (usize, usize);
+type Vec3 = [i32; 3];
+
+/// Row/column deltas for every facing, in password order: right, down, left, up.
+const DELTAS: [(isize, isize); 4] = [(0, 1), (1, 0), (0, -1), (-1, 0)];
+
+pub struct Board {
+    tiles: Vec<Vec<Tile>>,
+}
+
+impl Board {
+    fn get(&self, row: isize, col: isize) -> Tile {
+        if row < 0 || col < 0 {
+            return Tile::Void;
+        }
+        self.tiles
+            .get(row as usize)
+            .and_then(|r| r.get(col as usize))
+            .copied()
+            .unwrap_or(Tile::Void)
+    }
+
+    fn start(&self) -> Result<Position, SolveError> {
+        self.tiles[0]
+            .iter()
+            .position(|tile| tile == &Tile::Open)
+            .map(|col| (0, col))
+            .ok_or_else(|| SolveError::unsupported("No open tile on the first row"))
+    }
+
+    fn tile_count(&self) -> usize {
+        self.tiles
+            .iter()
+            .map(|row| row.iter().filter(|tile| tile != &&Tile::Void).count())
+            .sum()
+    }
+
+    /// Flat wrap-around: walk back against the facing until the board ends.
+    fn wrap_flat(&self, (row, col): Position, facing: usize) -> (Position, usize) {
+        let (dr, dc) = DELTAS[facing];
+        let (mut row, mut col) = (row as isize, col as isize);
+        while self.get(row - dr, col - dc) != Tile::Void {
+            row -= dr;
+            col -= dc;
+        }
+        ((row as usize, col as usize), facing)
+    }
+}
+
+/// A face of the cube, with the 3D orientation it ended up with after folding the net.
+struct Face {
+    origin: Position,
+    normal: Vec3,
+    right: Vec3,
+    down: Vec3,
+}
+
+impl Face {
+    /// Outward direction (in 3D) of each facing on this face, in password order.
+    fn directions(&self) -> [Vec3; 4] {
+        [self.right, self.down, neg(self.right), neg(self.down)]
+    }
+}
+
+struct Cube {
+    size: usize,
+    faces: HashMap<Position, Face>,
+}
+
+fn neg(v: Vec3) -> Vec3 {
+    [-v[0], -v[1], -v[2]]
+}
+
+fn add(a: Vec3, b: Vec3) -> Vec3 {
+    [a[0] + b[0], a[1] + b[1], a[2] + b[2]]
+}
+
+fn scale(v: Vec3, factor: i32) -> Vec3 {
+    [v[0] * factor, v[1] * factor, v[2] * factor]
+}
+
+fn dot(a: Vec3, b: Vec3) -> i32 {
+    a[0] * b[0] + a[1] * b[1] + a[2] * b[2]
+}
+
+impl Cube {
+    /// Folds the net by walking it breadth-first from the first face, so any of the 11 nets works.
+    fn fold(board: &Board) -> Result<Self, SolveError> {
+        let size = ((board.tile_count() / 6) as f64).sqrt() as usize;
+        if size == 0 || size * size * 6 != board.tile_count() {
+            return Err(SolveError::unsupported(
+                "Board does not consist of 6 square faces",
+            ));
+        }
+        let start_col = board.tiles[0]
+            .iter()
+            .position(|tile| tile != &Tile::Void)
+            .unwrap_or(0);
+        let start = (0, start_col / size);
+        let mut faces: HashMap<Position, Face> = HashMap::new();
+        faces.insert(
+            start,
+            Face {
+                origin: (0, start.1 * size),
+                normal: [0, 0, 1],
+                right: [1, 0, 0],
+                down: [0, 1, 0],
+            },
+        );
+        let mut queue = VecDeque::from([start]);
+        while let Some(current) = queue.pop_front() {
+            let face = &faces[&current];
+            let (normal, right, down) = (face.normal, face.right, face.down);
+            let neighbors = [
+                ((current.0, current.1 + 1), right, neg(normal), down),
+                ((current.0 + 1, current.1), down, right, neg(normal)),
+                (
+                    (current.0, current.1.wrapping_sub(1)),
+                    neg(right),
+                    normal,
+                    down,
+                ),
+                (
+                    (current.0.wrapping_sub(1), current.1),
+                    neg(down),
+                    right,
+                    normal,
+                ),
+            ];
+            for (pos, normal, right, down) in neighbors {
+                if faces.contains_key(&pos) {
+                    continue;
+                }
+                let origin = (pos.0.wrapping_mul(size), pos.1.wrapping_mul(size));
+                if board.get(origin.0 as isize, origin.1 as isize) == Tile::Void {
+                    continue;
+                }
+                faces.insert(
+                    pos,
+                    Face {
+                        origin,
+                        normal,
+                        right,
+                        down,
+                    },
+                );
+                queue.push_back(pos);
+            }
+        }
+        if faces.len() != 6 {
+            return Err(SolveError::unsupported(format!(
+                "Net folds into {} faces instead of 6",
+                faces.len()
+            )));
+        }
+        Ok(Self { size, faces })
+    }
+
+    fn face_at(&self, (row, col): Position) -> &Face {
+        &self.faces[&(row / self.size, col / self.size)]
+    }
+
+    /// Cube wrap-around: map the tile to 3D, step over the edge and project it onto the next face.
+    fn wrap(&self, pos: Position, facing: usize) -> (Position, usize) {
+        let n = self.size as i32;
+        let face = self.face_at(pos);
+        let local = (
+            (pos.0 - face.origin.0) as i32,
+            (pos.1 - face.origin.1) as i32,
+        );
+        // Doubled coordinates keep tile centers on integers: faces sit at +-n, tiles at odd offsets.
+        let point = add(
+            scale(face.normal, n),
+            add(
+                scale(face.right, 2 * local.1 - n + 1),
+                scale(face.down, 2 * local.0 - n + 1),
+            ),
+        );
+        let direction = face.directions()[facing];
+        let point = add(point, add(direction, neg(face.normal)));
+        let travel = neg(face.normal);
+
+        let next = self
+            .faces
+            .values()
+            .find(|f| f.normal == direction)
+            .expect("Every direction of a folded cube has a face");
+        let row = (dot(point, next.down) + n - 1) / 2;
+        let col = (dot(point, next.right) + n - 1) / 2;
+        let facing = next
+            .directions()
+            .iter()
+            .position(|d| d == &travel)
+            .expect("Travel direction lies in the next face");
+        (
+            (next.origin.0 + row as usize, next.origin.1 + col as usize),
+            facing,
+        )
+    }
+}
+
+fn parse_board(input: &str) -> Result<Board, SolveError> {
+    let tiles: Vec<Vec<Tile>> = input
+        .lines()
+        .map(|line| {
+            line.char_indices()
+                .map(|(i, c)| match c {
+                    ' ' => Ok(Tile::Void),
+                    '.' => Ok(Tile::Open),
+                    '#' => Ok(Tile::Wall),
+                    _ => Err(SolveError::parse(
+                        input,
+                        &line[i..i + c.len_utf8()],
+                        "Invalid tile",
+                    )),
+                })
+                .collect()
+        })
+        .collect::<Result<_, _>>()?;
+    if tiles.is_empty() {
+        return Err(SolveError::parse(input, input, "Empty board"));
+    }
+    Ok(Board { tiles })
+}
+
+/// Parses the path, a slice of `input` so errors point into the whole input.
+fn parse_path(input: &str, path: &str) -> Result<Vec<Instruction>, SolveError> {
+    let path = path.trim();
+    let mut instructions = vec![];
+    let mut steps: Option<usize> = None;
+    for (i, c) in path.char_indices() {
+        if let Some(digit) = c.to_digit(10) {
+            steps = Some(steps.unwrap_or(0) * 10 + digit as usize);
+            continue;
+        }
+        if let Some(steps) = steps.take() {
+            instructions.push(Instruction::Forward(steps));
+        }
+        match c {
+            'L' => instructions.push(Instruction::Left),
+            'R' => instructions.push(Instruction::Right),
+            _ => {
+                return Err(SolveError::parse(
+                    input,
+                    &path[i..i + c.len_utf8()],
+                    "Invalid instruction",
+                ))
+            }
+        }
+    }
+    if let Some(steps) = steps {
+        instructions.push(Instruction::Forward(steps));
+    }
+    Ok(instructions)
+}
+
+fn parse_input(input: &str) -> Result<(Board, Vec<Instruction>), SolveError> {
+    let (board, path) = input.split_once("\n\n").ok_or_else(|| {
+        SolveError::parse(
+            input,
+            input,
+            "Expected a board and a path separated by a blank line",
+        )
+    })?;
+    Ok((parse_board(board)?, parse_path(input, path)?))
+}
+
+fn walk<F>(board: &Board, instructions: &[Instruction], wrap: F) -> Result<usize, SolveError>
+where
+    F: Fn(Position, usize) -> (Position, usize),
+{
+    let mut pos = board.start()?;
+    let mut facing = 0;
+    for instruction in instructions {
+        match instruction {
+            Instruction::Left => facing = (facing + 3) % 4,
+            Instruction::Right => facing = (facing + 1) % 4,
+            Instruction::Forward(steps) => {
+                for _ in 0..*steps {
+                    let (dr, dc) = DELTAS[facing];
+                    let (row, col) = (pos.0 as isize + dr, pos.1 as isize + dc);
+                    let (next, next_facing) = match board.get(row, col) {
+                        Tile::Void => wrap(pos, facing),
+                        _ => ((row as usize, col as usize), facing),
+                    };
+                    if board.get(next.0 as isize, next.1 as isize) == Tile::Wall {
+                        break;
+                    }
+                    pos = next;
+                    facing = next_facing;
+                }
+            }
+        }
     }
+    Ok(1000 * (pos.0 + 1) + 4 * (pos.1 + 1) + facing)
+}
+
+inventory::submit! {
+    Day::new::<Puzzle>(2022, 22, "Monkey Map", &["grid", "3d"])
 }
 
-#[async_trait]
 impl Solution for Puzzle {
-    async fn solve_a(&mut self, _input: String) -> Result<Answer, String> {
-        Answer::from("").into()
+    type Parsed = (Board, Vec<Instruction>);
+
+    fn parse(&self, input: &str) -> Result<Self::Parsed, SolveError> {
+        parse_input(input)
+    }
+
+    fn solve_a(&mut self, (board, instructions): &Self::Parsed) -> Result<Answer, SolveError> {
+        let password = walk(board, instructions, |pos, facing| {
+            board.wrap_flat(pos, facing)
+        })?;
+        Answer::from(password).into()
     }
 
-    async fn solve_b(&mut self, _input: String) -> Result<Answer, String> {
-        Answer::from("").into()
+    fn solve_b(&mut self, (board, instructions): &Self::Parsed) -> Result<Answer, SolveError> {
+        let cube = Cube::fold(board)?;
+        let password = walk(board, instructions, |pos, facing| cube.wrap(pos, facing))?;
+        Answer::from(password).into()
+    }
+
+    fn examples(&self) -> Vec<Example> {
+        vec![Example::new(TEST_INPUT).part_a(6032).part_b(5031)]
     }
 }
 
+const TEST_INPUT: &str = "        ...#
+        .#..
+        #...
+        ....
+...#.......#
+........#...
+..#....#....
+..........#.
+        ...#....
+        .....#..
+        .#......
+        ......#.
+
+10R5L5R10L4R5L5";
+
 #[cfg(test)]
 mod tests {
-    use super::Puzzle;
+    use super::{parse_board, parse_path, Cube, Instruction, Puzzle, TEST_INPUT};
     use common::{Answer, Solution};
 
-    const TEST_INPUT: &str = "";
-
-    #[tokio::test]
-    async fn part_a() {
+    #[test]
+    fn part_a() {
         let mut puzzle = Puzzle::default();
         assert_eq!(
-            puzzle.solve_a(String::from(TEST_INPUT)).await,
-            Ok(Answer::from(""))
+            puzzle.solve_a(&puzzle.parse(TEST_INPUT).unwrap()),
+            Ok(Answer::from(6032))
         )
     }
 
-    #[tokio::test]
-    async fn part_b() {
+    #[test]
+    fn part_b() {
         let mut puzzle = Puzzle::default();
         assert_eq!(
-            puzzle.solve_b(String::from(TEST_INPUT)).await,
-            Ok(Answer::from(""))
+            puzzle.solve_b(&puzzle.parse(TEST_INPUT).unwrap()),
+            Ok(Answer::from(5031))
+        )
+    }
+
+    #[test]
+    fn path() {
+        assert_eq!(
+            parse_path("10R5L", "10R5L").unwrap(),
+            vec![
+                Instruction::Forward(10),
+                Instruction::Right,
+                Instruction::Forward(5),
+                Instruction::Left
+            ]
+        );
+    }
+
+    #[test]
+    fn cube_wrap_is_reversible() {
+        // A differently shaped net (a plain cross) to make sure folding is not tied to one layout.
+        let board = parse_board(
+            " .
+....
+ .",
         )
+        .unwrap();
+        let cube = Cube::fold(&board).unwrap();
+        for (pos, facing) in [
+            ((0, 1), 0),
+            ((0, 1), 2),
+            ((0, 1), 3),
+            ((1, 0), 1),
+            ((2, 1), 1),
+        ] {
+            let (next, next_facing) = cube.wrap(pos, facing);
+            assert_eq!(
+                cube.wrap(next, (next_facing + 2) % 4),
+                (pos, (facing + 2) % 4)
+            );
+        }
     }
-}
\ No newline at end of file
+}
@@ -1,225 +1,179 @@
-use std::{collections::{HashMap, VecDeque}, fmt::{Debug, Display}};
+use std::{
+    collections::HashMap,
+    fmt::{Debug, Display},
+};
 
-use async_trait::async_trait;
-use common::{Answer, Solution};
+use common::{Answer, Color, Example, GridCell, Point, Renderer, Solution, SolveError, SparseGrid};
 
-pub struct Puzzle {}
+use crate::registry::Day;
+
+pub struct Puzzle {
+    renderer: Renderer,
+}
 
 #[derive(Clone, PartialEq)]
-enum Cell {
+pub enum Cell {
     Elf,
     Empty,
 }
 
-impl Debug for Cell {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl GridCell for Cell {
+    const PALETTE: &'static [(char, Color)] = &[('#', Color::GREEN)];
+
+    fn symbol(&self) -> char {
         match self {
-            Cell::Elf => write!(f, "#"),
-            Cell::Empty => write!(f, "."),
+            Cell::Elf => '#',
+            Cell::Empty => '.',
         }
     }
 }
 
+impl Debug for Cell {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.symbol())
+    }
+}
+
 impl Display for Cell {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        match self {
-            Cell::Elf => write!(f, "#"),
-            Cell::Empty => write!(f, "."),
-        }
+        write!(f, "{}", self.symbol())
     }
 }
 
 impl Default for Puzzle {
     fn default() -> Self {
-        Self {}
+        Self {
+            renderer: Renderer::default(),
+        }
     }
 }
 
-type Grid = VecDeque<VecDeque<Cell>>;
-
-fn read_cell(grid: &Grid, row: isize, col: isize) -> Cell {
-    if let Some(row) = grid.get(row as usize) {
-        if let Some(cell) = row.get(col as usize) {
-            return cell.clone();
+impl Puzzle {
+    /// Draws the elves after a round, within the box around them.
+    fn render(&self, grid: &Grid, round: usize) {
+        if !self.renderer.is_enabled() {
+            return;
         }
+        let (top_left, _) = grid.bounds().unwrap_or_default();
+        self.renderer
+            .cells(top_left, grid.to_dense(Cell::Empty).rows());
+        self.renderer.text(
+            (top_left.0, top_left.1 - 4),
+            format!("round: {}", round),
+            Color::BLACK,
+        );
+        self.renderer.flush();
     }
-    return Cell::Empty;
 }
 
-fn set_cell(grid: &mut Grid, row: isize, col: isize, cell: Cell) {
-    grid.get_mut(row as usize).unwrap()[col as usize] = cell;
-}
+/// The elves, the only cells that are set
+type Grid = SparseGrid<Cell>;
 
-fn make_moves(grid: &Grid, round_index: isize) -> Option<Grid> {
-    // start new grid, 1 tile slightly bigger
-    let mut next: Grid = grid.iter().map(|row| VecDeque::from(vec![Cell::Empty; row.len() + 2])).collect();
-    next.push_back(next.front().unwrap().clone());
-    next.push_back(next.front().unwrap().clone());
-    let mut move_map: HashMap<isize, (isize, isize, Cell)> = HashMap::new();
-    for (row_index, row) in grid.iter().enumerate() {
-        for (col_index, cell) in row.iter().enumerate() {
-            if cell != &Cell::Elf {
-                continue;
-            }
-            let neighbors = [
-                [
-                    read_cell(&grid, row_index as isize - 1, col_index as isize - 1),
-                    read_cell(&grid, row_index as isize - 1, col_index as isize + 0),
-                    read_cell(&grid, row_index as isize - 1, col_index as isize + 1),
-                ],
-                [
-                    read_cell(&grid, row_index as isize + 0, col_index as isize - 1),
-                    Cell::Empty,
-                    read_cell(&grid, row_index as isize + 0, col_index as isize + 1),
-                ],
-                [
-                    read_cell(&grid, row_index as isize + 1, col_index as isize - 1),
-                    read_cell(&grid, row_index as isize + 1, col_index as isize + 0),
-                    read_cell(&grid, row_index as isize + 1, col_index as isize + 1),
-                ]
-            ];
-            // due to the slightly bigger grid, we need to offset the index
-            let row_index = (row_index + 1) as isize;
-            let col_index = (col_index + 1) as isize;
-            let has_neighbor = neighbors.iter().any(|c| c.iter().any(|c| c == &Cell::Elf));
-            if !has_neighbor {
-                set_cell(&mut next, row_index, col_index, cell.clone());
-                continue;
-            }
+/// The cells that have to be free to move in a direction, with that direction, in the order the
+/// elves consider them in the first round: north, south, west and east.
+const DIRECTIONS: [([Point; 3], Point); 4] = [
+    ([(-1, -1), (0, -1), (1, -1)], (0, -1)),
+    ([(-1, 1), (0, 1), (1, 1)], (0, 1)),
+    ([(-1, -1), (-1, 0), (-1, 1)], (-1, 0)),
+    ([(1, -1), (1, 0), (1, 1)], (1, 0)),
+];
 
-            let moves = [
-                if neighbors[0].iter().all(|c| c == &Cell::Empty) {
-                    Some((row_index - 1, col_index))
-                } else {
-                    None
-                },
-                if neighbors[2].iter().all(|c| c == &Cell::Empty) {
-                    Some((row_index + 1, col_index))
-                } else {
-                    None
-                },
-                if neighbors.iter().all(|c| c[0] == Cell::Empty) {
-                    Some((row_index, col_index - 1))
-                } else {
-                    None
-                },
-                if neighbors.iter().all(|c| c[2] == Cell::Empty) {
-                    Some((row_index, col_index + 1))
-                } else {
-                    None
-                }
-            ];   
-            let mut next_p = (row_index, col_index);
-            for dm in round_index..(round_index + 4) {
-                if let Some(valid_move) = moves[dm as usize % 4] {
-                    next_p = valid_move;
-                    break;
-                }
-            }  
-
-            let move_key: isize = (next_p.0 * 1000 + next_p.1) as isize;
-            let existing_move = move_map.get(&move_key);
-            let existing_move = if let Some(existing_move) = existing_move {
-                existing_move
-            } else {
-                set_cell(&mut next, next_p.0, next_p.1, cell.clone());
-                move_map.insert(move_key, (row_index, col_index, cell.clone()));
-                continue;
-            };
-            let current_cell = read_cell(&next, next_p.0, next_p.1);
-            if current_cell == Cell::Elf {
-                // has clashed, reset position
-                set_cell(&mut next, next_p.0, next_p.1, Cell::Empty);
-                set_cell(&mut next, existing_move.0, existing_move.1, existing_move.2.clone());
-            }
-            set_cell(&mut next, row_index, col_index, cell.clone());
+fn make_moves(grid: &Grid, round_index: isize) -> Option<Grid> {
+    let mut proposals: HashMap<Point, Vec<Point>> = HashMap::new();
+    for (elf, _) in grid.iter() {
+        if grid.neighbors8(elf).next().is_none() {
+            continue;
         }
+        let free = |(dx, dy): &Point| !grid.contains((elf.0 + dx, elf.1 + dy));
+        let next_p = (round_index..(round_index + 4))
+            .map(|dm| &DIRECTIONS[dm as usize % 4])
+            .find(|(checks, _)| checks.iter().all(free))
+            .map_or(elf, |(_, (dx, dy))| (elf.0 + dx, elf.1 + dy));
+        proposals.entry(next_p).or_default().push(elf);
     }
-    if move_map.len() < 1 {
-        return None
+    if proposals.is_empty() {
+        return None;
     }
-    Some(trim(next))
+    let mut next = grid.clone();
+    for (next_p, elves) in proposals {
+        // elves that clashed stay where they are
+        if let [elf] = elves[..] {
+            next.remove(elf);
+            next.set(next_p, Cell::Elf);
+        }
+    }
+    Some(next)
 }
 
-fn read_grid(input: String) -> Grid {
-    input
-        .lines()
-        .map(|line| {
-            line.chars()
-                .map(|c| match c {
-                    '#' => Cell::Elf,
-                    '.' => Cell::Empty,
-                    _ => panic!("Invalid cell"),
-                })
-                .collect()
-        })
-        .collect()
+fn read_grid(input: &str) -> Result<Grid, SolveError> {
+    let grid = SparseGrid::parse(input, |c| match c {
+        '#' => Ok(Some(Cell::Elf)),
+        '.' => Ok(None),
+        _ => Err("Invalid cell"),
+    })?;
+    if grid.is_empty() {
+        return Err(SolveError::unsupported("No elves to spread out"));
+    }
+    Ok(grid)
 }
 
-fn trim(mut grid: Grid) -> Grid {
-    while grid.front().unwrap().iter().all(|cell| cell == &Cell::Empty) {
-        grid.pop_front();
-    }
-    while grid.back().unwrap().iter().all(|cell| cell == &Cell::Empty) {
-        grid.pop_back();
-    }
-    while grid.iter().all(|row| row.front().unwrap() == &Cell::Empty) {
-        grid.iter_mut().for_each(|row| { row.pop_front(); });
-    }
-    while grid.iter().all(|row| row.back().unwrap() == &Cell::Empty) {
-        grid.iter_mut().for_each(|row| { row.pop_back(); });
-    }
-    grid
+inventory::submit! {
+    Day::new::<Puzzle>(2022, 23, "Unstable Diffusion", &["grid", "simulation"])
 }
 
-#[async_trait]
 impl Solution for Puzzle {
-    async fn solve_a(&mut self, input: String) -> Result<Answer, String> {
-        let mut grid = read_grid(input);
+    type Parsed = Grid;
+
+    fn parse(&self, input: &str) -> Result<Self::Parsed, SolveError> {
+        read_grid(input)
+    }
+
+    fn solve_a(&mut self, grid: &Self::Parsed) -> Result<Answer, SolveError> {
+        let mut grid = grid.clone();
         for i in 0..10 {
             grid = make_moves(&grid, i).unwrap_or(grid);
+            self.render(&grid, i as usize + 1);
         }
-        Answer::from(grid.iter().fold(0, |sum, row| {
-            sum + row.iter().filter(|cell| cell == &&Cell::Empty).count()
-        }))
-        .into()
+        let ((min_x, min_y), (max_x, max_y)) = grid.bounds().unwrap_or_default();
+        let area = (max_x - min_x + 1) * (max_y - min_y + 1);
+        Answer::from(area as usize - grid.len()).into()
     }
 
-    async fn solve_b(&mut self, input: String) -> Result<Answer, String> {
-        let mut grid = read_grid(input);
+    fn solve_b(&mut self, grid: &Self::Parsed) -> Result<Answer, SolveError> {
+        let mut grid = grid.clone();
         for i in 0..10000 {
             let next_grid = make_moves(&grid, i);
             if next_grid.is_none() {
                 return Answer::from(i + 1).into();
             }
             grid = next_grid.unwrap();
+            self.render(&grid, i as usize + 1);
         }
-        Answer::from(0).into()
+        Err(SolveError::unsupported(
+            "Elves still moving after 10000 rounds",
+        ))
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::Puzzle;
-    use common::{Answer, Solution};
+    fn set_renderer(&mut self, renderer: Renderer) {
+        self.renderer = renderer;
+    }
+
+    fn examples(&self) -> Vec<Example> {
+        vec![
+            Example::new(SMALL_TEST_INPUT).part_a(25),
+            Example::new(TEST_INPUT).part_a(110).part_b(20),
+        ]
+    }
+}
 
-    const SMALL_TEST_INPUT: &str = ".....
+const SMALL_TEST_INPUT: &str = ".....
 ..##.
 ..#..
 .....
 ..##.
 ......";
 
-    #[tokio::test]
-    async fn part_a_small() {
-        let mut puzzle = Puzzle::default();
-        assert_eq!(
-            puzzle.solve_a(String::from(SMALL_TEST_INPUT)).await,
-            Ok(Answer::from(26))
-        )
-    }
-
-    const TEST_INPUT: &str = "..............
+const TEST_INPUT: &str = "..............
 ..............
 .......#......
 .....###.#....
@@ -232,20 +186,34 @@ mod tests {
 ..............
 ..............";
 
-    #[tokio::test]
-    async fn part_a() {
+#[cfg(test)]
+mod tests {
+    use super::{Puzzle, SMALL_TEST_INPUT, TEST_INPUT};
+    use common::{Answer, Solution};
+
+    #[test]
+    fn part_a_small() {
+        let mut puzzle = Puzzle::default();
+        assert_eq!(
+            puzzle.solve_a(&puzzle.parse(SMALL_TEST_INPUT).unwrap()),
+            Ok(Answer::from(25))
+        )
+    }
+
+    #[test]
+    fn part_a() {
         let mut puzzle = Puzzle::default();
         assert_eq!(
-            puzzle.solve_a(String::from(TEST_INPUT)).await,
+            puzzle.solve_a(&puzzle.parse(TEST_INPUT).unwrap()),
             Ok(Answer::from(110))
         )
     }
 
-    #[tokio::test]
-    async fn part_b() {
+    #[test]
+    fn part_b() {
         let mut puzzle = Puzzle::default();
         assert_eq!(
-            puzzle.solve_b(String::from(TEST_INPUT)).await,
+            puzzle.solve_b(&puzzle.parse(TEST_INPUT).unwrap()),
             Ok(Answer::from(20))
         )
     }
@@ -1,16 +1,17 @@
 use std::{cell::RefCell, collections::HashMap, str::FromStr};
 
-use async_trait::async_trait;
-use common::{Answer, Solution};
+use common::{Answer, Color, Example, Point, Progress, Renderer, Solution, SolveError};
+
+use crate::registry::Day;
 
 type Coord = (usize, usize);
 type Bounds = (Coord, Coord);
 
 #[derive(Debug, Clone)]
-struct Blizzard(Direction, (usize, usize));
+pub struct Blizzard(Direction, (usize, usize));
 
 #[derive(Debug, Clone)]
-enum Direction {
+pub enum Direction {
     Right,
     Down,
     Left,
@@ -33,12 +34,16 @@ impl FromStr for Direction {
 
 pub struct Puzzle {
     blizzards_per_turn: RefCell<HashMap<usize, Vec<Blizzard>>>,
+    progress: Progress,
+    renderer: Renderer,
 }
 
 impl Default for Puzzle {
     fn default() -> Self {
         Self {
             blizzards_per_turn: RefCell::new(HashMap::new()),
+            progress: Progress::default(),
+            renderer: Renderer::default(),
         }
     }
 }
@@ -55,14 +60,14 @@ impl Puzzle {
             .map(|blizzard| {
                 let (row, col) = blizzard.1;
                 let (row, col) = match blizzard.0 {
-                    Direction::Right if col + 1 <= bounds.1.1 => (row, col + 1),
-                    Direction::Right => (row, bounds.1.0),
-                    Direction::Left if col - 1 >= bounds.1.0 => (row, col - 1),
-                    Direction::Left => (row, bounds.1.1),
-                    Direction::Down if row + 1 <= bounds.0.1 => (row + 1, col),
-                    Direction::Down => (bounds.0.0, col),
-                    Direction::Up if row - 1 >= bounds.0.0 => (row - 1, col),
-                    Direction::Up => (bounds.0.1, col),
+                    Direction::Right if col + 1 <= bounds.1 .1 => (row, col + 1),
+                    Direction::Right => (row, bounds.1 .0),
+                    Direction::Left if col - 1 >= bounds.1 .0 => (row, col - 1),
+                    Direction::Left => (row, bounds.1 .1),
+                    Direction::Down if row + 1 <= bounds.0 .1 => (row + 1, col),
+                    Direction::Down => (bounds.0 .0, col),
+                    Direction::Up if row - 1 >= bounds.0 .0 => (row - 1, col),
+                    Direction::Up => (bounds.0 .1, col),
                 };
                 Blizzard(blizzard.0.clone(), (row, col))
             })
@@ -73,11 +78,42 @@ impl Puzzle {
         blizzards
     }
 
+    /// Draws the valley at a turn: its walls, the blizzards and where the expedition can be.
+    fn render(&self, turn: usize, bounds: &Bounds, positions: &[Coord]) {
+        if !self.renderer.is_enabled() {
+            return;
+        }
+        let point = |(row, col): Coord| -> Point { (col as i64, row as i64) };
+        let ((top, bottom), (left, right)) = *bounds;
+        let corners = [
+            (top - 1, left - 1),
+            (top - 1, right + 1),
+            (bottom + 1, right + 1),
+            (bottom + 1, left - 1),
+            (top - 1, left - 1),
+        ];
+        self.renderer.path(corners.map(point), Color::GRAY);
+        let blizzards = self.get_blizzards_for_turn(turn, bounds);
+        self.renderer
+            .points(blizzards.iter().map(|b| point(b.1)), Color::CYAN);
+        self.renderer
+            .points(positions.iter().map(|p| point(*p)), Color::GREEN);
+        self.renderer.text(
+            (left as i64 - 1, top as i64 - 5),
+            format!("turn: {}", turn),
+            Color::BLACK,
+        );
+        self.renderer.flush();
+    }
+
     fn run_bfs(&self, offset: usize, bounds: &Bounds, start: &Coord, end: &Coord) -> usize {
         let mut positions: Vec<Coord> = vec![start.clone()];
-        
+
         for i in (1 + offset)..5000 {
-            let blizzards: Vec<usize> = self.get_blizzards_for_turn(i, bounds)
+            self.progress.update(i as u64);
+            self.render(i - 1, bounds, &positions);
+            let blizzards: Vec<usize> = self
+                .get_blizzards_for_turn(i, bounds)
                 .iter()
                 .map(|b| coord_to_hash(b.1))
                 .collect();
@@ -87,21 +123,22 @@ impl Puzzle {
                 if pos == *end {
                     return i - 1;
                 }
-                let is_in_y_bounds = pos.0 >= bounds.0.0 && pos.0 <= bounds.0.1;
-                if pos.1 > bounds.1.0 && is_in_y_bounds {
+                let is_in_y_bounds = pos.0 >= bounds.0 .0 && pos.0 <= bounds.0 .1;
+                if pos.1 > bounds.1 .0 && is_in_y_bounds {
                     moves.push((pos.0, pos.1 - 1))
                 }
-                if pos.1 < bounds.1.1 && is_in_y_bounds {
+                if pos.1 < bounds.1 .1 && is_in_y_bounds {
                     moves.push((pos.0, pos.1 + 1))
                 }
-                if pos.0 > bounds.0.0 {
+                if pos.0 > bounds.0 .0 {
                     moves.push((pos.0 - 1, pos.1))
                 } else if pos.0 > 0 {
                     if (pos.0 - 1, pos.1) == *end || (pos.0 - 1, pos.1) == *start {
                         moves.push((pos.0 - 1, pos.1))
                     }
                 }
-                if pos.0 < bounds.0.1 || (pos.0 + 1, pos.1) == *end || (pos.0 + 1, pos.1) == *start {
+                if pos.0 < bounds.0 .1 || (pos.0 + 1, pos.1) == *end || (pos.0 + 1, pos.1) == *start
+                {
                     moves.push((pos.0 + 1, pos.1))
                 }
                 moves
@@ -143,64 +180,94 @@ fn get_blizzards(input: &str) -> Vec<Blizzard> {
         .collect()
 }
 
-fn get_bounds(input: &str) -> Bounds {
-    (
-        (1, input.lines().count() - 2),
-        (1, input.lines().next().unwrap().chars().count() - 2),
-    )
+fn get_bounds(input: &str) -> Result<Bounds, SolveError> {
+    let rows = input.lines().count();
+    let cols = input.lines().next().map_or(0, |line| line.chars().count());
+    if rows < 3 || cols < 3 {
+        return Err(SolveError::unsupported(
+            "Valley needs walls around at least one open tile",
+        ));
+    }
+    Ok(((1, rows - 2), (1, cols - 2)))
+}
+
+inventory::submit! {
+    Day::new::<Puzzle>(2022, 24, "Blizzard Basin", &["grid", "bfs"])
 }
 
-#[async_trait]
 impl Solution for Puzzle {
-    async fn solve_a(&mut self, input: String) -> Result<Answer, String> {
-        let blizzards = get_blizzards(&input);
-        self.blizzards_per_turn.borrow_mut().insert(0, blizzards);
-        let bounds = get_bounds(&input);
-        let start = (bounds.0.0 - 1, bounds.1.0);
-        let end = (bounds.0.1 + 1, bounds.1.1);
-        Answer::from(self.run_bfs(0, &bounds, &start, &end)).into()
+    /// The open tiles and the blizzards at the start
+    type Parsed = (Bounds, Vec<Blizzard>);
+
+    fn parse(&self, input: &str) -> Result<Self::Parsed, SolveError> {
+        Ok((get_bounds(input)?, get_blizzards(input)))
     }
 
-    async fn solve_b(&mut self, input: String) -> Result<Answer, String> {
-        let blizzards = get_blizzards(&input);
-        self.blizzards_per_turn.borrow_mut().insert(0, blizzards);
-        let bounds = get_bounds(&input);
-        let start = (bounds.0.0 - 1, bounds.1.0);
-        let end = (bounds.0.1 + 1, bounds.1.1);
+    fn solve_a(&mut self, (bounds, blizzards): &Self::Parsed) -> Result<Answer, SolveError> {
+        self.blizzards_per_turn
+            .borrow_mut()
+            .insert(0, blizzards.clone());
+        let start = (bounds.0 .0 - 1, bounds.1 .0);
+        let end = (bounds.0 .1 + 1, bounds.1 .1);
+        self.progress.phase("turn", None);
+        Answer::from(self.run_bfs(0, bounds, &start, &end)).into()
+    }
+
+    fn solve_b(&mut self, (bounds, blizzards): &Self::Parsed) -> Result<Answer, SolveError> {
+        self.blizzards_per_turn
+            .borrow_mut()
+            .insert(0, blizzards.clone());
+        let start = (bounds.0 .0 - 1, bounds.1 .0);
+        let end = (bounds.0 .1 + 1, bounds.1 .1);
 
-        let first_pass = self.run_bfs(0, &bounds, &start, &end);
-        let second_pass = self.run_bfs(first_pass, &bounds, &end, &start);
-        let third_pass = self.run_bfs(second_pass, &bounds, &start, &end);
+        self.progress.phase("trip 1 of 3, turn", None);
+        let first_pass = self.run_bfs(0, bounds, &start, &end);
+        self.progress.phase("trip 2 of 3, turn", None);
+        let second_pass = self.run_bfs(first_pass, bounds, &end, &start);
+        self.progress.phase("trip 3 of 3, turn", None);
+        let third_pass = self.run_bfs(second_pass, bounds, &start, &end);
         Answer::from(third_pass).into()
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::Puzzle;
-    use common::{Answer, Solution};
+    fn set_progress(&mut self, progress: Progress) {
+        self.progress = progress;
+    }
+
+    fn set_renderer(&mut self, renderer: Renderer) {
+        self.renderer = renderer;
+    }
 
-    const TEST_INPUT: &str = "#.######
+    fn examples(&self) -> Vec<Example> {
+        vec![Example::new(TEST_INPUT).part_a(18).part_b(54)]
+    }
+}
+
+const TEST_INPUT: &str = "#.######
 #>>.<^<#
 #.<..<<#
 #>v.><>#
 #<^v^^>#
 ######.#";
 
-    #[tokio::test]
-    async fn part_a() {
+#[cfg(test)]
+mod tests {
+    use super::{Puzzle, TEST_INPUT};
+    use common::{Answer, Solution};
+
+    #[test]
+    fn part_a() {
         let mut puzzle = Puzzle::default();
         assert_eq!(
-            puzzle.solve_a(String::from(TEST_INPUT)).await,
+            puzzle.solve_a(&puzzle.parse(TEST_INPUT).unwrap()),
             Ok(Answer::from(18))
         )
     }
 
-    #[tokio::test]
-    async fn part_b() {
+    #[test]
+    fn part_b() {
         let mut puzzle = Puzzle::default();
         assert_eq!(
-            puzzle.solve_b(String::from(TEST_INPUT)).await,
+            puzzle.solve_b(&puzzle.parse(TEST_INPUT).unwrap()),
             Ok(Answer::from(54))
         )
     }
@@ -1,5 +1,6 @@
-use async_trait::async_trait;
-use common::{Answer, Solution};
+use common::{Answer, Example, Solution, SolveError};
+
+use crate::registry::Day;
 
 pub struct Puzzle {}
 
@@ -29,7 +30,8 @@ fn decimal_to_snafu(val: i64) -> String {
     let mut remainder = val;
     for i in (0..digits).rev() {
         let lowest_next_digit = snafu_to_decimal(vec!["="; i].join(""));
-        let snafu_power = ((remainder - lowest_next_digit) as f32 / (5 as i64).pow(i as u32) as f32).floor() as i64;
+        let snafu_power = ((remainder - lowest_next_digit) as f32 / (5 as i64).pow(i as u32) as f32)
+            .floor() as i64;
         if snafu_power != 0 || !out.is_empty() {
             if snafu_power == -2 {
                 out.push("=".into());
@@ -44,27 +46,44 @@ fn decimal_to_snafu(val: i64) -> String {
     out.join("")
 }
 
-#[async_trait]
+inventory::submit! {
+    Day::new::<Puzzle>(2022, 25, "Full of Hot Air", &["number-base"])
+}
+
 impl Solution for Puzzle {
-    async fn solve_a(&mut self, input: String) -> Result<Answer, String> {
-        let sum = input
-            .lines()
-            .map(|v| snafu_to_decimal(v.into()))
-            .fold(0, |sum, v| sum + v);
+    /// The fuel requirements as decimal numbers
+    type Parsed = Vec<i64>;
+
+    fn parse(&self, input: &str) -> Result<Self::Parsed, SolveError> {
+        if let Some((line, i, c)) = input.lines().find_map(|line| {
+            line.char_indices()
+                .find(|(_, c)| !"=-012".contains(*c))
+                .map(|(i, c)| (line, i, c))
+        }) {
+            return Err(SolveError::parse(
+                input,
+                &line[i..i + c.len_utf8()],
+                "Invalid SNAFU digit",
+            ));
+        }
+        Ok(input.lines().map(|v| snafu_to_decimal(v.into())).collect())
+    }
+
+    fn solve_a(&mut self, numbers: &Self::Parsed) -> Result<Answer, SolveError> {
+        let sum = numbers.iter().fold(0, |sum, v| sum + v);
         Answer::from(decimal_to_snafu(sum)).into()
     }
 
-    async fn solve_b(&mut self, _input: String) -> Result<Answer, String> {
+    fn solve_b(&mut self, _numbers: &Self::Parsed) -> Result<Answer, SolveError> {
         Answer::from("Merry Christmas").into()
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::Puzzle;
-    use common::{Answer, Solution};
+    fn examples(&self) -> Vec<Example> {
+        vec![Example::new(TEST_INPUT).part_a("2=-1=0")]
+    }
+}
 
-    const TEST_INPUT: &str = "1=-0-2
+const TEST_INPUT: &str = "1=-0-2
 12111
 2=0=
 21
@@ -78,11 +97,16 @@ mod tests {
 1=
 122";
 
-    #[tokio::test]
-    async fn part_a() {
+#[cfg(test)]
+mod tests {
+    use super::{Puzzle, TEST_INPUT};
+    use common::{Answer, Solution};
+
+    #[test]
+    fn part_a() {
         let mut puzzle = Puzzle::default();
         assert_eq!(
-            puzzle.solve_a(String::from(TEST_INPUT)).await,
+            puzzle.solve_a(&puzzle.parse(TEST_INPUT).unwrap()),
             Ok(Answer::from("2=-1=0"))
         )
     }
@@ -0,0 +1,497 @@
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
+
+use reqwest::StatusCode;
+use tokio::sync::Mutex;
+
+use crate::input;
+
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+pub const DEFAULT_USER_AGENT: &str = concat!(
+    "github.com/daanoz/AOC2022 aoc2022/",
+    env!("CARGO_PKG_VERSION")
+);
+/// Minimum time between two requests to the site, also when days run concurrently.
+const MIN_DELAY: Duration = Duration::from_secs(1);
+/// Attempts after the first one when the site answers with a server error.
+const RETRIES: u32 = 3;
+const BACKOFF: Duration = Duration::from_secs(1);
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FetchError {
+    MissingSession,
+    /// The session was rejected, most likely because it expired
+    Unauthorized,
+    NotFound,
+    /// The puzzle unlocks after the given time
+    NotUnlocked(Duration),
+    /// Server error status, after retrying
+    Server(u16),
+    Network(String),
+    /// Reading or writing a local file failed
+    Io(String),
+}
+
+impl std::fmt::Display for FetchError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FetchError::MissingSession => {
+                write!(f, "Cannot download input, AOC_SESSION unavailable")
+            }
+            FetchError::Unauthorized => write!(f, "Session was not accepted, it may have expired"),
+            FetchError::NotFound => write!(f, "Input not found"),
+            FetchError::NotUnlocked(wait) => {
+                let minutes = wait.as_secs().div_ceil(60);
+                write!(
+                    f,
+                    "Puzzle is not unlocked yet, unlocks in {}h {}m",
+                    minutes / 60,
+                    minutes % 60
+                )
+            }
+            FetchError::Server(status) => write!(f, "Server error {}", status),
+            FetchError::Network(error) => write!(f, "Network error: {}", error),
+            FetchError::Io(error) => write!(f, "{}", error),
+        }
+    }
+}
+
+impl std::error::Error for FetchError {}
+
+/// Downloads puzzle inputs into the `inputs` cache. Clones share the time of the last request, so
+/// concurrent days stay `MIN_DELAY` apart.
+#[derive(Clone)]
+pub struct Fetcher {
+    session: Option<String>,
+    data_dir: PathBuf,
+    user_agent: String,
+    base_url: String,
+    min_delay: Duration,
+    backoff: Duration,
+    last_request: Arc<Mutex<Option<Instant>>>,
+}
+
+impl Fetcher {
+    pub fn new(session: Option<String>) -> Self {
+        Self {
+            session,
+            data_dir: env::current_dir().unwrap_or_default(),
+            user_agent: String::from(DEFAULT_USER_AGENT),
+            base_url: String::from(DEFAULT_BASE_URL),
+            min_delay: MIN_DELAY,
+            backoff: BACKOFF,
+            last_request: Arc::new(Mutex::new(None)),
+        }
+    }
+
+    pub fn user_agent(mut self, user_agent: &str) -> Self {
+        self.user_agent = user_agent.to_string();
+        self
+    }
+
+    /// Directory holding the `inputs` cache, which differs per profile.
+    pub fn data_dir(mut self, data_dir: PathBuf) -> Self {
+        self.data_dir = data_dir;
+        self
+    }
+
+    pub fn base_url(mut self, base_url: &str) -> Self {
+        self.base_url = base_url.trim_end_matches('/').to_string();
+        self
+    }
+
+    pub fn session(&self) -> Option<&str> {
+        self.session.as_deref()
+    }
+
+    pub fn get_base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    pub fn get_user_agent(&self) -> &str {
+        &self.user_agent
+    }
+
+    pub fn input_path(&self, year: u16, day: u8) -> PathBuf {
+        input::input_path(&self.data_dir, year, day)
+    }
+
+    /// The cached input of the day, downloading it when it is not cached yet.
+    pub async fn input(&self, year: u16, day: u8) -> Result<String, FetchError> {
+        let input_path = self.input_path(year, day);
+        self.cached_input(&input_path, year, day).await
+    }
+
+    /// Downloads the input of the day again, replacing the cached one.
+    pub async fn refresh(&self, year: u16, day: u8) -> Result<Refresh, FetchError> {
+        let input_path = self.input_path(year, day);
+        self.refresh_input(&input_path, year, day).await
+    }
+
+    async fn cached_input(&self, path: &Path, year: u16, day: u8) -> Result<String, FetchError> {
+        if path.is_dir() {
+            return Err(FetchError::Io(format!(
+                "Input {} is a directory",
+                path.display()
+            )));
+        }
+        if let Ok(data) = fs::read_to_string(path) {
+            let data = input::normalize(&data);
+            match fs::read_to_string(checksum_path(path)) {
+                Ok(stored) => {
+                    if stored.trim() != checksum(&data) {
+                        eprintln!(
+                            "Warning: {} changed since it was downloaded, use --refresh-input to download it again",
+                            path.display()
+                        );
+                    }
+                    return Ok(data);
+                }
+                // An empty input that was never downloaded is the placeholder of `new`
+                Err(_) if data.is_empty() => {}
+                Err(_) => return Ok(data),
+            }
+        }
+        let text = input::normalize(&self.download(year, day).await?);
+        store(path, &text)?;
+        Ok(text)
+    }
+
+    async fn refresh_input(&self, path: &Path, year: u16, day: u8) -> Result<Refresh, FetchError> {
+        let previous = fs::read_to_string(path)
+            .ok()
+            .map(|data| input::normalize(&data));
+        let text = input::normalize(&self.download(year, day).await?);
+        store(path, &text)?;
+        Ok(match previous {
+            None => Refresh::New,
+            Some(previous) if previous == text => Refresh::Unchanged,
+            Some(_) => Refresh::Changed,
+        })
+    }
+
+    async fn download(&self, year: u16, day: u8) -> Result<String, FetchError> {
+        let session = self.session.as_ref().ok_or(FetchError::MissingSession)?;
+        if let Ok(wait) = unlock_time(year, day).duration_since(SystemTime::now()) {
+            return Err(FetchError::NotUnlocked(wait));
+        }
+        let client = self.client()?;
+        let url = format!("{}/{}/day/{}/input", self.base_url, year, day);
+        let mut backoff = self.backoff;
+        let mut retries = 0;
+        loop {
+            self.throttle().await;
+            let result = client
+                .get(&url)
+                .header("cookie", format!("session={}", session))
+                .send()
+                .await;
+            let error = match result {
+                Ok(response) if response.status().is_success() => {
+                    return response
+                        .text()
+                        .await
+                        .map_err(|e| FetchError::Network(e.to_string()))
+                }
+                Ok(response) => match response.status() {
+                    StatusCode::BAD_REQUEST | StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN => {
+                        return Err(FetchError::Unauthorized)
+                    }
+                    StatusCode::NOT_FOUND => return Err(FetchError::NotFound),
+                    status if status.is_server_error() => FetchError::Server(status.as_u16()),
+                    status => return Err(FetchError::Server(status.as_u16())),
+                },
+                Err(error) => FetchError::Network(error.to_string()),
+            };
+            if retries == RETRIES {
+                return Err(error);
+            }
+            retries += 1;
+            tokio::time::sleep(backoff).await;
+            backoff *= 2;
+        }
+    }
+
+    /// Validates the session with the year's start page, which shows the user when logged in.
+    pub async fn check_session(&self, year: u16) -> Result<SessionInfo, FetchError> {
+        let session = self.session.as_ref().ok_or(FetchError::MissingSession)?;
+        self.throttle().await;
+        let response = self
+            .client()?
+            .get(format!("{}/{}", self.base_url, year))
+            .header("cookie", format!("session={}", session))
+            .send()
+            .await
+            .map_err(|e| FetchError::Network(e.to_string()))?;
+        match response.status() {
+            StatusCode::NOT_FOUND => return Err(FetchError::NotFound),
+            status if !status.is_success() => return Err(FetchError::Server(status.as_u16())),
+            _ => {}
+        }
+        let expires = response
+            .headers()
+            .get_all("set-cookie")
+            .iter()
+            .filter_map(|cookie| cookie.to_str().ok())
+            .filter(|cookie| cookie.starts_with("session="))
+            .find_map(cookie_expiry);
+        let page = response
+            .text()
+            .await
+            .map_err(|e| FetchError::Network(e.to_string()))?;
+        let owner = parse_owner(&page).ok_or(FetchError::Unauthorized)?;
+        Ok(SessionInfo { owner, expires })
+    }
+
+    fn client(&self) -> Result<reqwest::Client, FetchError> {
+        reqwest::Client::builder()
+            .user_agent(&self.user_agent)
+            .build()
+            .map_err(|e| FetchError::Network(e.to_string()))
+    }
+
+    async fn throttle(&self) {
+        let mut last_request = self.last_request.lock().await;
+        if let Some(last) = *last_request {
+            let elapsed = last.elapsed();
+            if elapsed < self.min_delay {
+                tokio::time::sleep(self.min_delay - elapsed).await;
+            }
+        }
+        *last_request = Some(Instant::now());
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SessionInfo {
+    pub owner: String,
+    /// Expiry of the cookie, when the server sent it along
+    pub expires: Option<String>,
+}
+
+/// Name of the logged in user from `<div class="user">Daan <span class="star-count">50*</span></div>`.
+fn parse_owner(page: &str) -> Option<String> {
+    let start = page.find("<div class=\"user\">")? + "<div class=\"user\">".len();
+    let end = start + page[start..].find('<')?;
+    let owner = page[start..end].trim();
+    match owner.is_empty() {
+        true => None,
+        false => Some(owner.to_string()),
+    }
+}
+
+fn cookie_expiry(cookie: &str) -> Option<String> {
+    cookie
+        .split(';')
+        .filter_map(|attribute| attribute.trim().split_once('='))
+        .find(|(name, _)| name.eq_ignore_ascii_case("expires"))
+        .map(|(_, value)| value.to_string())
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Refresh {
+    /// There was no cached input before
+    New,
+    Unchanged,
+    Changed,
+}
+
+/// Writes the input to the cache, with its checksum in `{input}.checksum` to detect later edits.
+fn store(path: &Path, text: &str) -> Result<(), FetchError> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|e| FetchError::Io(e.to_string()))?;
+    }
+    fs::write(path, text).map_err(|e| FetchError::Io(e.to_string()))?;
+    fs::write(checksum_path(path), checksum(text)).map_err(|e| FetchError::Io(e.to_string()))
+}
+
+fn checksum_path(path: &Path) -> PathBuf {
+    let mut name = path.as_os_str().to_owned();
+    name.push(".checksum");
+    PathBuf::from(name)
+}
+
+/// 64-bit FNV-1a of the normalized input; enough to notice edits, and stable across Rust versions
+/// unlike `DefaultHasher`.
+fn checksum(text: &str) -> String {
+    let hash = text.bytes().fold(0xcbf29ce484222325u64, |hash, byte| {
+        (hash ^ byte as u64).wrapping_mul(0x100000001b3)
+    });
+    format!("fnv1a64:{:016x}", hash)
+}
+
+/// Puzzles unlock at midnight EST (UTC-5) on their day of December.
+pub fn unlock_time(year: u16, day: u8) -> SystemTime {
+    let days = days_from_civil(year as i64, 12, day as i64);
+    UNIX_EPOCH + Duration::from_secs((days * 86400 + 5 * 3600) as u64)
+}
+
+/// Days since 1970-01-01 of a date in the proleptic Gregorian calendar.
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let day_of_year = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146097 + day_of_era - 719468
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, UNIX_EPOCH};
+
+    use super::{
+        checksum, checksum_path, cookie_expiry, parse_owner, unlock_time, FetchError, Fetcher,
+        Refresh,
+    };
+    use crate::stand_in;
+
+    fn fetcher(url: &str, session: Option<&str>) -> Fetcher {
+        let mut fetcher = Fetcher::new(None).base_url(url).user_agent("aoc2022-tests");
+        fetcher.session = session.map(String::from);
+        fetcher.min_delay = Duration::ZERO;
+        fetcher.backoff = Duration::from_millis(1);
+        fetcher
+    }
+
+    #[test]
+    fn unlocks() {
+        assert_eq!(
+            unlock_time(2022, 1),
+            UNIX_EPOCH + Duration::from_secs(1669870800)
+        );
+        assert_eq!(
+            unlock_time(2022, 25),
+            UNIX_EPOCH + Duration::from_secs(1669870800 + 24 * 86400)
+        );
+    }
+
+    #[tokio::test]
+    async fn retries_server_errors() {
+        let (url, server) = stand_in::serve(vec![
+            (502, "Bad gateway"),
+            (500, "Oops"),
+            (200, "1000\n2000\n"),
+        ]);
+        let fetched = fetcher(&url, Some("secret")).download(2022, 1).await;
+        assert_eq!(fetched, Ok(String::from("1000\n2000\n")));
+        let requests = server.join().unwrap();
+        assert_eq!(requests.len(), 3);
+        assert_eq!(requests[0].line, "GET /2022/day/1/input HTTP/1.1");
+        assert_eq!(requests[0].header("user-agent"), Some("aoc2022-tests"));
+        assert_eq!(requests[0].header("cookie"), Some("session=secret"));
+    }
+
+    #[tokio::test]
+    async fn typed_errors() {
+        let (url, server) = stand_in::serve(vec![
+            (
+                400,
+                "Puzzle inputs differ by user.  Please log in to get your puzzle input.",
+            ),
+            (404, "Not found"),
+        ]);
+        let fetcher = fetcher(&url, Some("expired"));
+        assert_eq!(
+            fetcher.download(2022, 1).await,
+            Err(FetchError::Unauthorized)
+        );
+        assert_eq!(fetcher.download(2022, 2).await, Err(FetchError::NotFound));
+        server.join().unwrap();
+
+        let without_session = self::fetcher(&url, None);
+        assert_eq!(
+            without_session.download(2022, 1).await,
+            Err(FetchError::MissingSession)
+        );
+        assert!(matches!(
+            self::fetcher(&url, Some("secret")).download(9999, 1).await,
+            Err(FetchError::NotUnlocked(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn caches_normalized_input() {
+        let dir = std::env::temp_dir().join(format!("aoc2022-cache-{}", std::process::id()));
+        let path = dir.join("2022").join("input_1.txt");
+        let (url, server) = stand_in::serve(vec![
+            (200, "1000\r\n2000\r\n"),
+            (200, "1000\n2000\n"),
+            (200, "3000\n"),
+        ]);
+        let fetcher = fetcher(&url, Some("secret"));
+
+        let first = fetcher.cached_input(&path, 2022, 1).await.unwrap();
+        assert_eq!(first, "1000\n2000");
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), first);
+        assert_eq!(
+            std::fs::read_to_string(checksum_path(&path)).unwrap(),
+            checksum(&first)
+        );
+        assert_eq!(fetcher.cached_input(&path, 2022, 1).await.unwrap(), first);
+
+        assert_eq!(
+            fetcher.refresh_input(&path, 2022, 1).await,
+            Ok(Refresh::Unchanged)
+        );
+        assert_eq!(
+            fetcher.refresh_input(&path, 2022, 1).await,
+            Ok(Refresh::Changed)
+        );
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "3000");
+        assert_eq!(server.join().unwrap().len(), 3);
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn downloads_over_placeholder() {
+        let dir = std::env::temp_dir().join(format!("aoc2022-placeholder-{}", std::process::id()));
+        let path = dir.join("2022").join("input_1.txt");
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(&path, "").unwrap();
+        let (url, server) = stand_in::serve(vec![(200, "1000\n")]);
+
+        let fetcher = fetcher(&url, Some("secret"));
+        assert_eq!(fetcher.cached_input(&path, 2022, 1).await.unwrap(), "1000");
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "1000");
+        assert_eq!(server.join().unwrap().len(), 1);
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn checks_session() {
+        let (url, server) = stand_in::serve(vec![
+            (
+                200,
+                "<header><div class=\"user\">Daan <span class=\"star-count\">50*</span></div></header>",
+            ),
+            (200, "<a href=\"/2022/auth/login\">[Log In]</a>"),
+        ]);
+        let fetcher = fetcher(&url, Some("secret"));
+        assert_eq!(fetcher.check_session(2022).await.unwrap().owner, "Daan");
+        assert_eq!(
+            fetcher.check_session(2022).await,
+            Err(FetchError::Unauthorized)
+        );
+        assert_eq!(server.join().unwrap()[0].line, "GET /2022 HTTP/1.1");
+
+        assert_eq!(
+            parse_owner("<div class=\"user\">(anonymous user #12345) <span>"),
+            Some(String::from("(anonymous user #12345)"))
+        );
+        assert_eq!(
+            cookie_expiry("session=abc; Expires=Sun, 01 Dec 2030 05:00:00 GMT; Path=/"),
+            Some(String::from("Sun, 01 Dec 2030 05:00:00 GMT"))
+        );
+    }
+
+    #[test]
+    fn checksums() {
+        assert_eq!(checksum(""), "fnv1a64:cbf29ce484222325");
+        assert_ne!(checksum("1000\n2000"), checksum("1000\n2001"));
+    }
+}
@@ -0,0 +1,145 @@
+use std::{
+    fs,
+    io::{self, Read},
+    path::{Path, PathBuf},
+};
+
+use crate::fetch::{FetchError, Fetcher};
+use crate::years::{self, DEFAULT_YEAR};
+
+/// Where the input for a day comes from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InputSource {
+    /// The personal puzzle input, cached in ./inputs and downloaded when missing (see `fetch`)
+    Puzzle,
+    File(PathBuf),
+    Stdin,
+    /// One of the examples from the puzzle statement, by index
+    Example(usize),
+}
+
+impl InputSource {
+    /// Expands an `--input` argument: `-` reads stdin, a directory yields every file in it.
+    pub fn from_arg(path: &Path) -> Result<Vec<InputSource>, String> {
+        if path == Path::new("-") {
+            return Ok(vec![InputSource::Stdin]);
+        }
+        if !path.exists() {
+            return Err(format!("Input {} does not exist", path.display()));
+        }
+        if !path.is_dir() {
+            return Ok(vec![InputSource::File(path.to_path_buf())]);
+        }
+        let mut files = fs::read_dir(path)
+            .map_err(|e| format!("Reading {} failed: {}", path.display(), e))?
+            .filter_map(|entry| entry.ok().map(|e| e.path()))
+            .filter(|path| path.is_file())
+            .collect::<Vec<PathBuf>>();
+        if files.is_empty() {
+            return Err(format!("No input files found in {}", path.display()));
+        }
+        files.sort();
+        Ok(files.into_iter().map(InputSource::File).collect())
+    }
+
+    pub fn examples(year: u16, day: u8) -> Vec<InputSource> {
+        let count = years::get_day(year, day)
+            .map(|solution| solution.examples().len())
+            .unwrap_or(0);
+        (0..count).map(InputSource::Example).collect()
+    }
+
+    /// Name to tell results apart when not running on the puzzle input.
+    pub fn label(&self) -> Option<String> {
+        match self {
+            InputSource::Puzzle => None,
+            InputSource::File(path) => Some(
+                path.file_name()
+                    .map(|name| name.to_string_lossy().to_string())
+                    .unwrap_or_else(|| path.display().to_string()),
+            ),
+            InputSource::Stdin => Some(String::from("stdin")),
+            InputSource::Example(index) => Some(format!("example {}", index + 1)),
+        }
+    }
+
+    /// The input text, normalized the same way whatever its source.
+    pub async fn load(&self, year: u16, day: u8, fetcher: &Fetcher) -> Result<String, FetchError> {
+        let text = match self {
+            InputSource::Puzzle => fetcher.input(year, day).await,
+            InputSource::File(path) => fs::read_to_string(path)
+                .map_err(|e| FetchError::Io(format!("Reading {} failed: {}", path.display(), e))),
+            InputSource::Stdin => {
+                let mut data = String::new();
+                io::stdin()
+                    .read_to_string(&mut data)
+                    .map_err(|e| FetchError::Io(format!("Reading stdin failed: {}", e)))?;
+                Ok(data)
+            }
+            InputSource::Example(index) => years::get_day(year, day)
+                .map_err(FetchError::Io)?
+                .examples()
+                .into_iter()
+                .nth(*index)
+                .map(|example| example.input.to_string())
+                .ok_or_else(|| FetchError::Io(format!("Day {} has no example {}", day, index + 1))),
+        }?;
+        Ok(normalize(&text))
+    }
+}
+
+/// Input normalization policy: `\n` line endings and no trailing newlines. Whitespace within lines
+/// is left alone, as some puzzles (like the stacks of day 5) depend on it.
+pub fn normalize(text: &str) -> String {
+    text.replace("\r\n", "\n")
+        .trim_end_matches('\n')
+        .to_string()
+}
+
+/// Inputs are cached per year in `inputs/{year}` of the profile's data directory; for 2022 a file in `./inputs` itself, where
+/// they were stored before other years could be run, is still picked up.
+pub fn input_path(data_dir: &Path, year: u16, day: u8) -> PathBuf {
+    let filename = format!("input_{}.txt", day);
+    let inputs = data_dir.join("inputs");
+    let legacy_path = inputs.join(&filename);
+    if year == DEFAULT_YEAR && legacy_path.is_file() {
+        return legacy_path;
+    }
+    inputs.join(year.to_string()).join(&filename)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use super::{normalize, InputSource};
+
+    #[test]
+    fn normalizes() {
+        assert_eq!(normalize("1\r\n2\r\n\r\n"), "1\n2");
+        assert_eq!(normalize("    [D]\n1 2  \n"), "    [D]\n1 2  ");
+        assert_eq!(normalize(&normalize("a\n\n")), normalize("a\n\n"));
+    }
+
+    #[test]
+    fn expands_directories() {
+        let dir = std::env::temp_dir().join(format!("aoc2022-inputs-{}", std::process::id()));
+        std::fs::create_dir_all(dir.join("nested")).unwrap();
+        std::fs::write(dir.join("b.txt"), "2").unwrap();
+        std::fs::write(dir.join("a.txt"), "1").unwrap();
+
+        let sources = InputSource::from_arg(&dir).unwrap();
+        let labels: Vec<_> = sources.iter().filter_map(|s| s.label()).collect();
+        assert_eq!(labels, vec!["a.txt", "b.txt"]);
+        assert_eq!(
+            InputSource::from_arg(&dir.join("a.txt")).unwrap(),
+            vec![InputSource::File(dir.join("a.txt"))]
+        );
+        assert!(InputSource::from_arg(&dir.join("c.txt")).is_err());
+        assert_eq!(
+            InputSource::from_arg(Path::new("-")).unwrap(),
+            vec![InputSource::Stdin]
+        );
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -1,114 +1,633 @@
 use clap::ArgAction;
+use clap::ArgGroup;
+use clap::CommandFactory;
 use clap::Parser;
-use std::env;
-use std::fs;
-use std::ops::Add;
-use std::path;
-use std::time::Duration;
+use clap::Subcommand;
+use std::io::IsTerminal;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
 
+use tokio::sync::Semaphore;
+
+use animate::Animator;
+use answers::{AnswerStore, Verification};
+use common::Renderer;
+use fetch::{Fetcher, Refresh};
+use input::InputSource;
+use progress::ProgressBoard;
+use record::Recorder;
+use report::{day_title, DayReport, Format, Part, Reporter};
+use runner::Hooks;
+use selection::{DaySelection, PartSelection};
+use session::{Config, Profile};
+use visualize::Visualizer;
+use watch::{History, Watcher};
+
+mod animate;
+mod answers;
+mod bench;
+mod client;
 mod days;
+mod fetch;
+mod input;
+mod progress;
+mod record;
+mod registry;
+mod report;
+mod runner;
+mod scaffold;
+mod selection;
+mod session;
+#[cfg(test)]
+mod stand_in;
+mod submit;
+mod visualize;
+mod watch;
+mod years;
 
 #[derive(Parser, Debug)]
 #[command(name = "AOC 2022")]
 #[command(author = "Daan Sieben")]
 #[command(version = "1.0")]
 #[command(about, long_about = None)]
+#[command(args_conflicts_with_subcommands = true, subcommand_negates_reqs = true)]
+#[command(group(ArgGroup::new("selection").required(true).args(["day", "days", "all", "list"])))]
 struct Args {
-    /// AOC Session id; if not set uses ENV var AOC_SESSION
-    #[arg(long)]
+    #[command(subcommand)]
+    command: Option<Command>,
+    /// AOC Session id; if not set uses ENV var AOC_SESSION or the config file
+    #[arg(long, global = true)]
     aoc_session: Option<String>,
+    /// Profile from the config file to run as; every profile has its own inputs and answers
+    #[arg(long, global = true)]
+    profile: Option<String>,
+    /// Site to download inputs from and submit answers to, e.g. a local stand-in server
+    #[arg(long, global = true, default_value = fetch::DEFAULT_BASE_URL)]
+    base_url: String,
+    /// User-Agent sent to adventofcode.com
+    #[arg(long, global = true, default_value = fetch::DEFAULT_USER_AGENT)]
+    user_agent: String,
+    /// Puzzle year to run
+    #[arg(short, long, global = true, default_value_t = years::DEFAULT_YEAR, value_parser = years::parse_year)]
+    year: u16,
     /// Puzzle day to run
-    #[arg(short, long)]
+    #[arg(short, long, value_parser = selection::parse_day)]
     day: Option<u8>,
+    /// Puzzle days to run, e.g. 1-5,12,20-25
+    #[arg(long, value_name = "DAYS", value_parser = selection::parse_days)]
+    days: Option<DaySelection>,
     // Run all days
     #[arg(short, long, action = ArgAction::SetTrue)]
-    all: bool
+    all: bool,
+    /// List the registered days of the year with the status of their input and answers
+    #[arg(short, long, action = ArgAction::SetTrue)]
+    list: bool,
+    /// Days to leave out of the selection, e.g. 16,19
+    #[arg(long, value_name = "DAYS", value_parser = selection::parse_days)]
+    skip: Option<DaySelection>,
+    /// Puzzle part(s) to run
+    #[arg(short, long, value_enum, default_value_t = PartSelection::Both)]
+    part: PartSelection,
+    /// Input file to run the day on instead of the puzzle input; `-` reads stdin, a directory runs
+    /// every file in it
+    #[arg(short, long, value_name = "PATH", conflicts_with_all = ["verify", "record"])]
+    input: Option<PathBuf>,
+    /// Run the examples from the puzzle statement and check their expected answers
+    #[arg(short, long, action = ArgAction::SetTrue, conflicts_with_all = ["input", "verify", "record", "bench"])]
+    example: bool,
+    /// Download the puzzle inputs again and report whether they changed
+    #[arg(long, action = ArgAction::SetTrue, conflicts_with_all = ["input", "example"])]
+    refresh_input: bool,
+    /// Output format of the run report
+    #[arg(long, value_enum, default_value_t = Format::Text)]
+    format: Format,
+    /// Compare the answers with the ones stored in ./answers
+    #[arg(long, action = ArgAction::SetTrue)]
+    verify: bool,
+    /// Store the answers in ./answers for parts that have none yet
+    #[arg(long, action = ArgAction::SetTrue)]
+    record: bool,
+    /// Benchmark the selected days by running each part N times
+    #[arg(long, value_name = "N", value_parser = clap::builder::RangedU64ValueParser::<usize>::new().range(1..))]
+    bench: Option<usize>,
+    /// Number of discarded runs before benchmark samples are taken
+    #[arg(long, value_name = "N", default_value_t = 1)]
+    warmup: usize,
+    /// Store the benchmark results as baseline in benchmarks/<YEAR>/<NAME>.json of the profile
+    #[arg(long, value_name = "NAME", num_args = 0..=1, default_missing_value = "baseline")]
+    save_baseline: Option<String>,
+    /// Compare the benchmark results with a baseline stored by --save-baseline
+    #[arg(long, value_name = "NAME", num_args = 0..=1, default_missing_value = "baseline")]
+    baseline: Option<String>,
+    /// Number of days to run concurrently
+    #[arg(short, long, value_name = "N", default_value_t = 1, value_parser = clap::value_parser!(u16).range(1..))]
+    jobs: u16,
+    /// Run again whenever an input file changes, showing how the answers and timings changed; every
+    /// run checks the examples too, and with a directory as --input files added to it are picked up
+    #[arg(long, action = ArgAction::SetTrue, conflicts_with = "bench")]
+    watch: bool,
+    /// Show the days that draw their state in the browser on http://localhost:9898
+    #[arg(long, action = ArgAction::SetTrue, conflicts_with = "bench")]
+    visualize: bool,
+    /// Animate the days that draw their state in the terminal, with controls to pause, step and
+    /// change the speed
+    #[arg(long, action = ArgAction::SetTrue, conflicts_with_all = ["bench", "watch", "visualize"])]
+    animate: bool,
+    /// Record the frames of the days that draw their state into an animated GIF
+    #[arg(long, value_name = "PATH", conflicts_with_all = ["bench", "watch", "visualize", "animate"])]
+    record_frames: Option<PathBuf>,
+    /// Maximum number of seconds a single part may run before it is reported as timed out. A
+    /// timed out part cannot be stopped, it keeps running in the background until it finishes or
+    /// the run ends, and takes a core away from the parts timed after it
+    #[arg(long, value_name = "SECONDS", value_parser = parse_timeout)]
+    timeout: Option<Duration>,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Create a new day from the day_00.rs template
+    New(scaffold::NewArgs),
+    /// Solve a part on the puzzle input and submit the answer
+    Submit(submit::SubmitArgs),
+    /// Manage the session cookie
+    Session(session::SessionArgs),
+}
+
+fn parse_timeout(value: &str) -> Result<Duration, String> {
+    let seconds: f64 = value
+        .parse()
+        .map_err(|_| format!("Invalid number of seconds: {}", value))?;
+    Duration::try_from_secs_f64(seconds).map_err(|e| e.to_string())
 }
 
 #[tokio::main]
 async fn main() {
     let args = Args::parse();
-    if args.day.is_none() && !args.all {
-        panic!("Either select a day with --day <DAY> or run all with --all");
-    }
-    if let Some(day) = args.day {
-        execute_day(day, args.aoc_session.clone()).await;
-    } else {
-        let mut total_duration = Duration::ZERO;
-        for day in 1..=25 {
-            total_duration = total_duration.add(execute_day(day, args.aoc_session.clone()).await);
+    let profile = Config::load()
+        .and_then(|config| {
+            Profile::resolve(&config, args.profile.as_deref(), args.aoc_session.clone())
+        })
+        .unwrap_or_else(|error| {
+            Args::command()
+                .error(clap::error::ErrorKind::InvalidValue, error)
+                .exit()
+        });
+    let data_dir = profile.data_dir();
+    let fetcher = Fetcher::new(profile.session.clone())
+        .data_dir(data_dir.clone())
+        .base_url(&args.base_url)
+        .user_agent(&args.user_agent);
+    match &args.command {
+        Some(Command::New(new)) => {
+            if let Err(error) = scaffold::new_day(new, args.year, &fetcher).await {
+                eprintln!("Creating day failed! {}", error);
+                std::process::exit(1);
+            }
+            return;
         }
-        println!("Total time: {:.2?}", total_duration);
+        Some(Command::Submit(submission)) => {
+            if let Err(error) = submit::submit(submission, args.year, &fetcher, &data_dir).await {
+                eprintln!("Submitting failed! {}", error);
+                std::process::exit(1);
+            }
+            return;
+        }
+        Some(Command::Session(session)) => {
+            if let Err(error) = session::run(session, &profile, &fetcher, args.year).await {
+                eprintln!("Session check failed! {}", error);
+                std::process::exit(1);
+            }
+            return;
+        }
+        None => {}
+    }
+    if args.list {
+        list_days(&fetcher, &data_dir, args.year);
+        return;
+    }
+    let selection = match (&args.day, &args.days) {
+        (Some(day), _) => DaySelection::single(*day),
+        (_, Some(days)) => days.clone(),
+        _ => DaySelection::all(),
+    };
+    let selection = selection.without(&args.skip.clone().unwrap_or_default());
+    let days = selection.days();
+    let inputs = match &args.input {
+        Some(_) if days.len() != 1 => Args::command()
+            .error(
+                clap::error::ErrorKind::ArgumentConflict,
+                "--input can only be used for a single day",
+            )
+            .exit(),
+        Some(path) => InputSource::from_arg(path).unwrap_or_else(|error| {
+            Args::command()
+                .error(clap::error::ErrorKind::InvalidValue, error)
+                .exit()
+        }),
+        None => vec![InputSource::Puzzle],
+    };
+    if args.watch && inputs.contains(&InputSource::Stdin) {
+        Args::command()
+            .error(
+                clap::error::ErrorKind::ArgumentConflict,
+                "--watch cannot watch stdin",
+            )
+            .exit()
+    }
+    if args.animate && !std::io::stdout().is_terminal() {
+        Args::command()
+            .error(
+                clap::error::ErrorKind::ArgumentConflict,
+                "--animate needs a terminal",
+            )
+            .exit()
+    }
+    let gif = Some(std::ffi::OsStr::new("gif"));
+    if matches!(&args.record_frames, Some(path) if path.extension() != gif) {
+        Args::command()
+            .error(
+                clap::error::ErrorKind::InvalidValue,
+                "--record-frames only writes GIF files (.gif)",
+            )
+            .exit()
+    }
+    let jobs = job_list(&args, days, &inputs);
+    if args.refresh_input {
+        refresh_inputs(&fetcher, args.year, days).await;
+    }
+    if let Some(iterations) = args.bench {
+        run_bench(&args, &fetcher, &data_dir, &jobs, iterations).await;
+        return;
+    }
+    let store = AnswerStore::in_data_dir(&data_dir, args.year);
+    let visualizer = match args.visualize {
+        true => Some(start_visualizer().await),
+        false => None,
+    };
+    let animator = match args.animate {
+        true => Some(Animator::start().unwrap_or_else(|error| {
+            eprintln!("Animating in the terminal failed! {}", error);
+            std::process::exit(1);
+        })),
+        false => None,
+    };
+    let recorder = args.record_frames.as_ref().map(|_| Recorder::new());
+    let renderer = visualizer
+        .as_ref()
+        .map(|visualizer| visualizer.renderer().clone())
+        .or_else(|| {
+            animator
+                .as_ref()
+                .map(|animator| animator.renderer().clone())
+        })
+        .or_else(|| {
+            recorder
+                .as_ref()
+                .map(|recorder| recorder.renderer().clone())
+        })
+        .unwrap_or_default();
+    if args.watch {
+        watch(&args, &fetcher, &store, days, &renderer).await;
+        return;
+    }
+    let mut reporter = Reporter::new(args.format);
+    let start = Instant::now();
+    // The animation has the screen, reports are shown once it is done
+    let mut held = vec![];
+    run_days(
+        &args,
+        &fetcher,
+        &store,
+        &jobs,
+        &renderer,
+        |report| match animator {
+            Some(_) => held.push(report),
+            None => reporter.day(&report),
+        },
+    )
+    .await;
+    if let Some(animator) = animator {
+        animator.finish();
+        held.iter().for_each(|report| reporter.day(report));
+    }
+    reporter.total(start.elapsed());
+    if let (Some(recorder), Some(path)) = (&recorder, &args.record_frames) {
+        match recorder.save(path) {
+            Ok(0) => eprintln!("No frames were drawn, nothing to record"),
+            Ok(frames) => eprintln!("Recorded {} frames to {}", frames, path.display()),
+            Err(error) => {
+                eprintln!(
+                    "Recording the frames to {} failed! {}",
+                    path.display(),
+                    error
+                );
+                std::process::exit(1);
+            }
+        }
+    }
+    if visualizer.is_some() {
+        eprintln!("Showing the last frame, press Ctrl-C to stop");
+        std::future::pending::<()>().await;
+    }
+    if reporter.mismatches() > 0 {
+        std::process::exit(1);
     }
-
 }
 
-async fn execute_day(day: u8, aoc_session: Option<String>) -> Duration {
-    let mut solution = match days::get_day(day) {
-        Ok(solution) => solution,
-        Err(_) => return Duration::ZERO
-    };
-    
-    let input = get_input(day, aoc_session).await.unwrap();
-    
-    let start = std::time::Instant::now();
+async fn start_visualizer() -> Visualizer {
+    let visualizer = Visualizer::serve(visualize::PORT)
+        .await
+        .unwrap_or_else(|error| {
+            eprintln!(
+                "Serving the visualization on port {} failed! {}",
+                visualize::PORT,
+                error
+            );
+            std::process::exit(1);
+        });
+    eprintln!(
+        "Waiting for the visualization to be opened on http://localhost:{}",
+        visualize::PORT
+    );
+    visualizer.wait_for_page().await;
+    visualizer
+}
 
-    let result_a = solution.solve_a(input.clone()).await;
-    let time_a = start.elapsed();
-    let start_b = std::time::Instant::now();
-    let result_b = solution.solve_b(input.clone()).await;
-    let time_b = start_b.elapsed();
-    let time = start.elapsed();
+fn job_list(args: &Args, days: &[u8], inputs: &[InputSource]) -> Vec<(u8, InputSource)> {
+    days.iter()
+        .flat_map(|day| {
+            let sources = match args.example {
+                true => InputSource::examples(args.year, *day),
+                false => inputs.to_vec(),
+            };
+            sources.into_iter().map(move |source| (*day, source))
+        })
+        .collect()
+}
 
-    match result_a {
-        Ok(answer) => println!("Day {}, Part A = {}", day, answer.get_result()),
-        Err(error) => println!("Day {}, Part A failed! {}", day, error),
+/// Runs the jobs, handing their reports to `report` in job order.
+async fn run_days(
+    args: &Args,
+    fetcher: &Fetcher,
+    store: &AnswerStore,
+    jobs: &[(u8, InputSource)],
+    renderer: &Renderer,
+    mut report: impl FnMut(DayReport),
+) {
+    let semaphore = Arc::new(Semaphore::new(args.jobs as usize));
+    let board = ProgressBoard::new();
+    if !args.animate {
+        board.render();
     }
-    match result_b {
-        Ok(answer) => println!("Day {}, Part B = {}", day, answer.get_result()),
-        Err(error) => println!("Day {}, Part B failed! {}", day, error),
+    let handles: Vec<_> = jobs
+        .iter()
+        .map(|(day, source)| {
+            let (year, day, source) = (args.year, *day, source.clone());
+            let fetcher = fetcher.clone();
+            let timeout = args.timeout;
+            let parts = args.part.parts();
+            let semaphore = semaphore.clone();
+            let label = match source.label() {
+                Some(input) => format!("Day {} ({})", day, input),
+                None => format!("Day {}", day),
+            };
+            let board = board.clone();
+            let progress = board.track(label);
+            let hooks = Hooks {
+                progress: progress.clone(),
+                renderer: renderer.clone(),
+            };
+            tokio::spawn(async move {
+                let _permit = semaphore.acquire_owned().await.ok()?;
+                // Solvers are CPU bound, keep them off the async workers
+                let report = tokio::task::spawn_blocking(move || {
+                    tokio::runtime::Handle::current().block_on(runner::execute_day(
+                        year, day, source, &fetcher, &parts, timeout, &hooks,
+                    ))
+                })
+                .await
+                .ok()
+                .flatten();
+                board.untrack(&progress);
+                report
+            })
+        })
+        .collect();
+    // Await in day order so the output stays ordered regardless of which day finishes first
+    for ((_, source), handle) in jobs.iter().zip(handles) {
+        if let Ok(Some(mut day_report)) = handle.await {
+            if let InputSource::Example(index) = source {
+                verify_example(&mut day_report, *index);
+            }
+            if args.verify {
+                verify_day(store, &mut day_report);
+            }
+            if args.record {
+                record_day(store, &day_report);
+            }
+            board.print(|| report(day_report));
+        }
     }
-
-    println!("Day {} time: {:.2?} (A: {:.2?}, B: {:.2?})", day, time, time_a, time_b);
-    time
+    board.finish();
 }
 
-async fn get_input(day: u8, aoc_session: Option<String>) -> Result<String, String> {
-    let filename = format!("input_{}.txt", day);
-    let input_path = path::Path::new(&env::current_dir().map_err(|e| e.to_string())?)
-        .join("inputs")
-        .join(&filename);
-    let aoc_session = aoc_session.or(env::var_os("AOC_SESSION").and_then(|v| v.into_string().ok()));
+/// Runs the jobs again whenever one of their input files changes, until interrupted.
+async fn watch(
+    args: &Args,
+    fetcher: &Fetcher,
+    store: &AnswerStore,
+    days: &[u8],
+    renderer: &Renderer,
+) {
+    let mut history = History::default();
+    let mut changed: Vec<PathBuf> = vec![];
+    loop {
+        // Expand a directory again, files may have been added or removed
+        let jobs = match &args.input {
+            Some(path) => InputSource::from_arg(path),
+            None => Ok(vec![InputSource::Puzzle]),
+        }
+        .map(|inputs| {
+            // The examples are compiled in, they run along with the watched inputs
+            days.iter()
+                .flat_map(|day| {
+                    InputSource::examples(args.year, *day)
+                        .into_iter()
+                        .chain(inputs.iter().cloned())
+                        .map(move |source| (*day, source))
+                })
+                .collect::<Vec<_>>()
+        });
+        watch::clear_screen();
+        if !changed.is_empty() {
+            let changed = changed
+                .iter()
+                .map(|path| path.display().to_string())
+                .collect::<Vec<_>>();
+            eprintln!("Changed: {}", changed.join(", "));
+        }
+        let jobs = jobs.unwrap_or_else(|error| {
+            eprintln!("{}", error);
+            vec![]
+        });
+        let mut reporter = Reporter::new(args.format);
+        run_days(args, fetcher, store, &jobs, renderer, |report| {
+            reporter.day(&report);
+            if args.format == Format::Text {
+                history
+                    .compare(&report)
+                    .iter()
+                    .for_each(|line| println!("{}", line));
+            }
+        })
+        .await;
+
+        let mut paths: Vec<PathBuf> = jobs
+            .iter()
+            .filter_map(|(day, source)| match source {
+                InputSource::Puzzle => Some(fetcher.input_path(args.year, *day)),
+                InputSource::File(path) => Some(path.clone()),
+                _ => None,
+            })
+            .chain(args.input.clone().filter(|path| path.is_dir()))
+            .collect();
+        paths.sort();
+        paths.dedup();
+        let mut watcher = Watcher::new(paths);
+        eprintln!(
+            "Watching {} input files for changes, press Ctrl-C to stop",
+            watcher.files()
+        );
+        changed = watcher.changed().await;
+    }
+}
 
-    if input_path.is_dir() {
-        panic!("Input file is a directory!")
+async fn run_bench(
+    args: &Args,
+    fetcher: &Fetcher,
+    data_dir: &Path,
+    jobs: &[(u8, InputSource)],
+    iterations: usize,
+) {
+    let baseline = args.baseline.as_ref().map(|name| {
+        let path = bench::baseline_path(data_dir, args.year, name);
+        bench::load_baseline(&path).unwrap_or_else(|error| {
+            eprintln!("{}", error);
+            std::process::exit(1);
+        })
+    });
+    let mut bench = bench::Bench::new(iterations, args.warmup, args.format, baseline);
+    for (day, source) in jobs {
+        if years::get_day(args.year, *day).is_err() {
+            continue;
+        }
+        let input = match source.load(args.year, *day, fetcher).await {
+            Ok(input) => input,
+            Err(error) => {
+                eprintln!(
+                    "{} failed! {}",
+                    day_title(*day, source.label().as_deref()),
+                    error
+                );
+                continue;
+            }
+        };
+        let parts = args.part.parts();
+        bench
+            .run_day(args.year, *day, source, &parts, input.into(), args.timeout)
+            .await;
     }
-    if input_path.exists() && input_path.is_file() {
-        if let Ok(data) = fs::read_to_string(&input_path) {
-            return Ok(data);
+    if let Some(name) = &args.save_baseline {
+        let path = bench::baseline_path(data_dir, args.year, name);
+        match bench.save(&path) {
+            Ok(_) => eprintln!("Saved baseline to {}", path.display()),
+            Err(error) => eprintln!("Saving baseline to {} failed! {}", path.display(), error),
         }
     }
-    fs::create_dir_all(input_path.parent().unwrap()).map_err(|e| e.to_string())?;
+}
 
-    let aoc_session = if let Some(session_id) = aoc_session {
-        session_id
-    } else {
-        return Err("Cannot download input, AOC_SESSION unavailable".to_string());
+async fn refresh_inputs(fetcher: &Fetcher, year: u16, days: &[u8]) {
+    for day in days {
+        if years::get_day(year, *day).is_err() {
+            continue;
+        }
+        match fetcher.refresh(year, *day).await {
+            Ok(Refresh::New) => eprintln!("Downloaded input for Day {}", day),
+            Ok(Refresh::Unchanged) => eprintln!("Input for Day {} is unchanged", day),
+            Ok(Refresh::Changed) => eprintln!("Input for Day {} changed", day),
+            Err(error) => eprintln!("Refreshing input for Day {} failed! {}", day, error),
+        }
+    }
+}
+
+fn verify_example(report: &mut DayReport, index: usize) {
+    let example = match years::get_day(report.year, report.day)
+        .ok()
+        .and_then(|solution| solution.examples().into_iter().nth(index))
+    {
+        Some(example) => example,
+        None => return,
     };
+    for part in report.parts.iter_mut() {
+        let expected = match part.part {
+            Part::A => &example.part_a,
+            Part::B => &example.part_b,
+        };
+        part.verification = Some(Verification::of(
+            expected.as_ref().map(|answer| answer.get_result()),
+            part.result.as_ref().ok(),
+        ));
+    }
+}
 
-    let url = format!("https://adventofcode.com/{}/day/{}/input", "2022", day);
-    let client = reqwest::Client::new();
-    let response = client
-        .get(url)
-        .header("cookie", format!("session={}", aoc_session))
-        .send()
-        .await
-        .map_err(|e| e.to_string())?;
-    let status = response.status();
-    let text: String = response.text().await.map_err(|e| e.to_string())?;
-    if !status.is_success() {
-        return Err(format!("Downloading input failed: {}; {}", status, text));
-    }
-    fs::write(input_path, text.trim_end()).map_err(|e| e.to_string())?;
-    Ok(text)
+fn verify_day(store: &AnswerStore, report: &mut DayReport) {
+    for part in report.parts.iter_mut() {
+        part.verification = Some(store.verify(report.day, part.part, part.result.as_ref().ok()));
+    }
+}
+
+fn record_day(store: &AnswerStore, report: &DayReport) {
+    for part in report.parts.iter() {
+        let answer = match &part.result {
+            Ok(answer) => answer,
+            Err(_) => continue,
+        };
+        match store.record(report.day, part.part, answer) {
+            Ok(true) => eprintln!("Recorded answer for Day {}, Part {}", report.day, part.part),
+            Ok(false) => {}
+            Err(error) => eprintln!(
+                "Recording answer for Day {}, Part {} failed! {}",
+                report.day, part.part, error
+            ),
+        }
+    }
+}
+
+fn list_days(fetcher: &Fetcher, data_dir: &Path, year: u16) {
+    let store = AnswerStore::in_data_dir(data_dir, year);
+    for day in registry::days().into_iter().filter(|day| day.year == year) {
+        let input = match fetcher.input_path(year, day.day).is_file() {
+            true => "cached",
+            false => "missing",
+        };
+        let answers = [Part::A, Part::B]
+            .into_iter()
+            .filter(|part| store.expected(day.day, *part).is_some())
+            .map(|part| part.id())
+            .collect::<Vec<_>>();
+        let answers = match answers.is_empty() {
+            true => String::from("-"),
+            false => answers.join(" "),
+        };
+        println!(
+            "Day {:>2}: {:<25} input {:<7}  answers {:<3}  examples {}  [{}]",
+            day.day,
+            day.title,
+            input,
+            answers,
+            day.solution().examples().len(),
+            day.tags.join(", ")
+        );
+    }
 }
@@ -0,0 +1,185 @@
+use std::{
+    io::{self, IsTerminal, Write},
+    sync::{Arc, Mutex, MutexGuard},
+    time::Duration,
+};
+
+use common::{Progress, Status};
+use crossterm::terminal;
+
+/// How often the live line is redrawn on a terminal
+const LIVE_INTERVAL: Duration = Duration::from_millis(100);
+/// How often progress is logged when stderr is not a terminal
+const LOG_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Progress of the running days on stderr: a live line on a terminal, otherwise a log line per day
+/// whenever its progress changed since the last one. Days show up once they report a phase.
+#[derive(Clone)]
+pub struct ProgressBoard {
+    live: bool,
+    board: Arc<Mutex<Board>>,
+}
+
+#[derive(Default)]
+struct Board {
+    entries: Vec<Entry>,
+    /// Whether the live line is on screen
+    drawn: bool,
+    stopped: bool,
+}
+
+struct Entry {
+    label: String,
+    progress: Progress,
+    logged: Option<Status>,
+}
+
+impl ProgressBoard {
+    pub fn new() -> Self {
+        Self {
+            live: io::stderr().is_terminal(),
+            board: Arc::default(),
+        }
+    }
+
+    /// A handle for the day with this label, shown until it is untracked.
+    pub fn track(&self, label: String) -> Progress {
+        let progress = Progress::tracked();
+        self.lock().entries.push(Entry {
+            label,
+            progress: progress.clone(),
+            logged: None,
+        });
+        progress
+    }
+
+    pub fn untrack(&self, progress: &Progress) {
+        self.lock()
+            .entries
+            .retain(|entry| !entry.progress.same(progress));
+    }
+
+    /// Runs `print` with the live line cleared, so it does not end up in the middle of the output.
+    pub fn print<R>(&self, print: impl FnOnce() -> R) -> R {
+        let mut board = self.lock();
+        if board.drawn {
+            eprint!("\r\x1b[2K");
+            board.drawn = false;
+        }
+        print()
+    }
+
+    /// Shows progress in the background until [`ProgressBoard::finish`].
+    pub fn render(&self) {
+        let board = self.clone();
+        let period = match self.live {
+            true => LIVE_INTERVAL,
+            false => LOG_INTERVAL,
+        };
+        tokio::spawn(async move {
+            let mut interval =
+                tokio::time::interval_at(tokio::time::Instant::now() + period, period);
+            loop {
+                interval.tick().await;
+                if !board.draw() {
+                    break;
+                }
+            }
+        });
+    }
+
+    /// Stops rendering and clears the live line.
+    pub fn finish(&self) {
+        self.lock().stopped = true;
+        self.print(|| ());
+    }
+
+    /// Draws the current progress; false once rendering stopped.
+    fn draw(&self) -> bool {
+        let mut board = self.lock();
+        if board.stopped {
+            return false;
+        }
+        let mut stderr = io::stderr().lock();
+        if self.live {
+            let line = truncate(&board.live_line(), width());
+            let _ = write!(stderr, "\r\x1b[2K{}", line);
+            board.drawn = !line.is_empty();
+        } else {
+            for entry in board.entries.iter_mut() {
+                let status = match entry.progress.status() {
+                    Some(status) if status.phase.is_some() => status,
+                    _ => continue,
+                };
+                if entry.logged.as_ref() != Some(&status) {
+                    let _ = writeln!(stderr, "{}, {}", entry.label, status);
+                    entry.logged = Some(status);
+                }
+            }
+        }
+        let _ = stderr.flush();
+        true
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Board> {
+        self.board.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl Board {
+    fn live_line(&self) -> String {
+        self.entries
+            .iter()
+            .filter_map(|entry| {
+                let status = entry.progress.status()?;
+                status.phase.as_ref()?;
+                Some(format!("{}, {}", entry.label, status))
+            })
+            .collect::<Vec<_>>()
+            .join(" | ")
+    }
+}
+
+/// Width of the terminal, so the live line does not wrap.
+fn width() -> usize {
+    terminal::size().map_or(80, |(columns, _)| columns as usize)
+}
+
+fn truncate(line: &str, width: usize) -> String {
+    match line.char_indices().nth(width.saturating_sub(1)) {
+        Some((end, _)) => format!("{}…", &line[..end]),
+        None => line.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{truncate, ProgressBoard};
+
+    #[test]
+    fn live_line() {
+        let board = ProgressBoard::new();
+        let day_16 = board.track(String::from("Day 16"));
+        let day_19 = board.track(String::from("Day 19 (example 1)"));
+        day_16.task("Part A");
+        day_19.task("Part B");
+        assert_eq!(board.lock().live_line(), "");
+
+        day_19.phase("blueprint", Some(3));
+        day_19.update(1);
+        day_16.phase("states", None);
+        day_16.update(20000);
+        assert_eq!(
+            board.lock().live_line(),
+            "Day 16, Part A: states 20000 | Day 19 (example 1), Part B: blueprint 1/3 (33%)"
+        );
+
+        board.untrack(&day_16);
+        assert_eq!(
+            board.lock().live_line(),
+            "Day 19 (example 1), Part B: blueprint 1/3 (33%)"
+        );
+        assert_eq!(truncate("Day 19, Part B", 7), "Day 19…");
+        assert_eq!(truncate("Day 19", 7), "Day 19");
+    }
+}
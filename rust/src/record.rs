@@ -0,0 +1,344 @@
+use std::{
+    fs::File,
+    io::{self, BufWriter, Write},
+    path::Path,
+    sync::{Arc, Mutex, MutexGuard},
+};
+
+use common::{Color, Frame, FrameSink, Renderer};
+
+use crate::animate::raster;
+
+/// Pixels per side of a cell
+const CELL_PIXELS: usize = 4;
+/// Time each frame is shown, in hundredths of a second
+const FRAME_DELAY: u16 = 4;
+/// Colors of the cells; index 0 is the background
+const MAX_COLORS: usize = 256;
+/// Frames kept; beyond this every other frame is dropped and fewer frames are recorded from then on
+const MAX_FRAMES: usize = 1000;
+
+/// Records the frames of the running days into an animated GIF, written by
+/// [`Recorder::save`]. All frames share the canvas around the cells of every frame, so the image
+/// stays put when the drawn area moves; text is left out.
+pub struct Recorder {
+    renderer: Renderer,
+    recording: Arc<Mutex<Recording>>,
+}
+
+struct Recording {
+    colors: Vec<Color>,
+    frames: Vec<Raster>,
+    /// Every how many frames one is recorded, doubled whenever `MAX_FRAMES` is reached
+    stride: usize,
+    /// Frames drawn so far, recorded or not
+    drawn: usize,
+}
+
+impl Default for Recording {
+    fn default() -> Self {
+        Self {
+            colors: vec![],
+            frames: vec![],
+            stride: 1,
+            drawn: 0,
+        }
+    }
+}
+
+/// A frame as runs of cells of the same palette index on every row, which keeps the recording
+/// small for grids with large areas of the same cell. `origin` is the cell of its top left corner.
+#[derive(Debug, PartialEq)]
+struct Raster {
+    origin: (i64, i64),
+    width: usize,
+    rows: Vec<Vec<(u8, usize)>>,
+}
+
+impl Recording {
+    /// Palette index of the color, the closest recorded color once the palette is full.
+    fn index(&mut self, color: Color) -> u8 {
+        if let Some(index) = self.colors.iter().position(|c| *c == color) {
+            return index as u8 + 1;
+        }
+        if self.colors.len() + 1 < MAX_COLORS {
+            self.colors.push(color);
+            return self.colors.len() as u8;
+        }
+        let distance = |c: &Color| {
+            let d = |a: u8, b: u8| (a as i32 - b as i32).pow(2);
+            d(c.0, color.0) + d(c.1, color.1) + d(c.2, color.2)
+        };
+        let closest = (0..self.colors.len())
+            .min_by_key(|index| distance(&self.colors[*index]))
+            .unwrap_or(0);
+        closest as u8 + 1
+    }
+
+    fn record(&mut self, frame: &Frame) {
+        self.drawn += 1;
+        if !(self.drawn - 1).is_multiple_of(self.stride) {
+            return;
+        }
+        let mut cells: Vec<_> = raster(frame)
+            .into_iter()
+            .filter_map(|(point, cell)| cell.color.map(|color| (point, color)))
+            .collect();
+        // Row by row, so new colors get the same palette index on every run
+        cells.sort_unstable_by_key(|((x, y), _)| (*y, *x));
+        if cells.is_empty() {
+            return;
+        }
+        let xs = cells.iter().map(|((x, _), _)| *x);
+        let ys = cells.iter().map(|((_, y), _)| *y);
+        let min = (xs.clone().min().unwrap_or(0), ys.clone().min().unwrap_or(0));
+        let max = (xs.max().unwrap_or(0), ys.max().unwrap_or(0));
+        let (width, height) = ((max.0 - min.0 + 1) as usize, (max.1 - min.1 + 1) as usize);
+        let mut indices = vec![vec![0; width]; height];
+        for ((x, y), color) in cells {
+            indices[(y - min.1) as usize][(x - min.0) as usize] = self.index(color);
+        }
+        let rows = indices
+            .into_iter()
+            .map(|row| {
+                let mut runs: Vec<(u8, usize)> = vec![];
+                for index in row {
+                    match runs.last_mut() {
+                        Some((last, length)) if *last == index => *length += 1,
+                        _ => runs.push((index, 1)),
+                    }
+                }
+                runs
+            })
+            .collect();
+        self.frames.push(Raster {
+            origin: min,
+            width,
+            rows,
+        });
+        if self.frames.len() > MAX_FRAMES {
+            self.frames = self.frames.drain(..).step_by(2).collect();
+            self.stride *= 2;
+        }
+    }
+
+    fn encode(&self, writer: impl Write) -> Result<(), gif::EncodingError> {
+        let min = (
+            self.frames.iter().map(|f| f.origin.0).min().unwrap_or(0),
+            self.frames.iter().map(|f| f.origin.1).min().unwrap_or(0),
+        );
+        let max = (
+            self.frames
+                .iter()
+                .map(|f| f.origin.0 + f.width as i64)
+                .max()
+                .unwrap_or(1),
+            self.frames
+                .iter()
+                .map(|f| f.origin.1 + f.rows.len() as i64)
+                .max()
+                .unwrap_or(1),
+        );
+        // Limited to the largest image GIF supports
+        let size = |cells: i64| (cells as usize * CELL_PIXELS).min(u16::MAX as usize) as u16;
+        let (width, height) = (size(max.0 - min.0), size(max.1 - min.1));
+        let palette: Vec<u8> = std::iter::once(Color::WHITE)
+            .chain(self.colors.iter().copied())
+            .flat_map(|Color(r, g, b)| [r, g, b])
+            .collect();
+        let mut encoder = gif::Encoder::new(writer, width, height, &palette)?;
+        encoder.set_repeat(gif::Repeat::Infinite)?;
+        let (width, height) = (width as usize, height as usize);
+        for raster in self.frames.iter() {
+            let offset = (
+                (raster.origin.0 - min.0) as usize,
+                (raster.origin.1 - min.1) as usize,
+            );
+            let mut pixels = vec![0; width * height];
+            for (y, runs) in raster.rows.iter().enumerate() {
+                let y = offset.1 + y;
+                let mut x = offset.0;
+                for (index, length) in runs {
+                    let (left, right) = (x * CELL_PIXELS, ((x + length) * CELL_PIXELS).min(width));
+                    for row in
+                        (y * CELL_PIXELS..(y + 1) * CELL_PIXELS).take_while(|row| *row < height)
+                    {
+                        if left < right {
+                            pixels[row * width + left..row * width + right].fill(*index);
+                        }
+                    }
+                    x += length;
+                }
+            }
+            let mut frame =
+                gif::Frame::from_indexed_pixels(width as u16, height as u16, pixels, None);
+            frame.delay = FRAME_DELAY;
+            encoder.write_frame(&frame)?;
+        }
+        Ok(())
+    }
+}
+
+impl Recorder {
+    pub fn new() -> Self {
+        let recording = Arc::new(Mutex::new(Recording::default()));
+        Self {
+            renderer: Renderer::new(RecordingSink(recording.clone())),
+            recording,
+        }
+    }
+
+    pub fn renderer(&self) -> &Renderer {
+        &self.renderer
+    }
+
+    /// Writes the frames recorded so far, returning how many there were.
+    pub fn save(&self, path: &Path) -> io::Result<usize> {
+        let recording = lock(&self.recording);
+        if recording.frames.is_empty() {
+            return Ok(0);
+        }
+        let mut writer = BufWriter::new(File::create(path)?);
+        recording.encode(&mut writer).map_err(|error| match error {
+            gif::EncodingError::Io(error) => error,
+            error => io::Error::new(io::ErrorKind::InvalidData, error),
+        })?;
+        writer.flush()?;
+        Ok(recording.frames.len())
+    }
+}
+
+fn lock(recording: &Mutex<Recording>) -> MutexGuard<'_, Recording> {
+    recording.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+struct RecordingSink(Arc<Mutex<Recording>>);
+
+impl FrameSink for RecordingSink {
+    fn frame(&mut self, frame: Frame) {
+        lock(&self.0).record(&frame);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use common::{Color, Frame, Shape};
+
+    use super::{Raster, Recording, CELL_PIXELS, MAX_FRAMES};
+
+    fn frame(sand: (i64, i64)) -> Frame {
+        Frame {
+            shapes: vec![
+                Shape::Grid {
+                    origin: (-1, 0),
+                    rows: vec![String::from("##.#")],
+                    palette: vec![('#', Color::GRAY)],
+                },
+                Shape::Points {
+                    points: vec![sand],
+                    color: Color::ORANGE,
+                },
+                Shape::Text {
+                    at: (-1, -3),
+                    text: String::from("sand: 1"),
+                    color: Color::BLACK,
+                },
+            ],
+        }
+    }
+
+    #[test]
+    fn records_frames_as_runs() {
+        let mut recording = Recording::default();
+        recording.record(&frame((0, 1)));
+        recording.record(&Frame::default());
+        assert_eq!(
+            recording.frames,
+            vec![Raster {
+                origin: (-1, 0),
+                width: 4,
+                rows: vec![vec![(1, 2), (0, 1), (1, 1)], vec![(0, 1), (2, 1), (0, 2)]],
+            }]
+        );
+        assert_eq!(recording.colors, vec![Color::GRAY, Color::ORANGE]);
+    }
+
+    #[test]
+    fn encodes_gif() {
+        let mut recording = Recording::default();
+        recording.record(&frame((0, 1)));
+        recording.record(&frame((1, 2)));
+        let mut gif = vec![];
+        recording.encode(&mut gif).unwrap();
+
+        let mut options = gif::DecodeOptions::new();
+        options.set_color_output(gif::ColorOutput::RGBA);
+        let mut decoder = options.read_info(gif.as_slice()).unwrap();
+        assert_eq!(
+            (decoder.width() as usize, decoder.height() as usize),
+            (4 * CELL_PIXELS, 3 * CELL_PIXELS)
+        );
+        let mut frames = 0;
+        while let Some(frame) = decoder.read_next_frame().unwrap() {
+            let pixel = |x: usize, y: usize| {
+                let at = (y * CELL_PIXELS * frame.width as usize + x * CELL_PIXELS) * 4;
+                frame.buffer[at..at + 3].to_vec()
+            };
+            assert_eq!(pixel(0, 0), vec![128, 128, 128]);
+            assert_eq!(pixel(2, 0), vec![255, 255, 255]);
+            assert_eq!(pixel(frames + 1, frames + 1), vec![220, 130, 40]);
+            frames += 1;
+        }
+        assert_eq!(frames, 2);
+    }
+
+    fn sand(at: (i64, i64)) -> Frame {
+        Frame {
+            shapes: vec![Shape::Points {
+                points: vec![at],
+                color: Color::ORANGE,
+            }],
+        }
+    }
+
+    #[test]
+    fn shares_canvas_between_frames() {
+        let mut recording = Recording::default();
+        recording.record(&sand((5, 5)));
+        recording.record(&sand((6, 7)));
+        let mut gif = vec![];
+        recording.encode(&mut gif).unwrap();
+
+        let mut options = gif::DecodeOptions::new();
+        options.set_color_output(gif::ColorOutput::RGBA);
+        let mut decoder = options.read_info(gif.as_slice()).unwrap();
+        assert_eq!(
+            (decoder.width() as usize, decoder.height() as usize),
+            (2 * CELL_PIXELS, 3 * CELL_PIXELS)
+        );
+        let mut sand = vec![];
+        while let Some(frame) = decoder.read_next_frame().unwrap() {
+            let orange = (0..frame.buffer.len() / 4)
+                .find(|pixel| frame.buffer[pixel * 4..pixel * 4 + 3] == [220, 130, 40])
+                .unwrap();
+            let width = frame.width as usize;
+            sand.push((
+                (orange % width) / CELL_PIXELS,
+                (orange / width) / CELL_PIXELS,
+            ));
+        }
+        assert_eq!(sand, vec![(0, 0), (1, 2)]);
+    }
+
+    #[test]
+    fn subsamples_long_recordings() {
+        let mut recording = Recording::default();
+        for x in 0..MAX_FRAMES as i64 * 2 + 2 {
+            recording.record(&sand((x, 0)));
+        }
+        assert!(recording.frames.len() <= MAX_FRAMES);
+        assert_eq!(recording.stride, 4);
+        let origins: Vec<_> = recording.frames.iter().take(3).map(|f| f.origin).collect();
+        assert_eq!(origins, vec![(0, 0), (4, 0), (8, 0)]);
+    }
+}
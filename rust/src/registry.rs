@@ -0,0 +1,75 @@
+use common::{DynSolution, Solution};
+
+/// A puzzle day compiled into the binary. Every day module registers itself with
+/// `inventory::submit!`, so adding a day only needs its `mod` declaration in `days/mod.rs`.
+pub struct Day {
+    pub year: u16,
+    pub day: u8,
+    pub title: &'static str,
+    pub tags: &'static [&'static str],
+    construct: fn() -> Box<dyn DynSolution>,
+}
+
+fn construct<T: Solution + Default + 'static>() -> Box<dyn DynSolution> {
+    Box::<T>::default()
+}
+
+impl Day {
+    pub const fn new<T: Solution + Default + 'static>(
+        year: u16,
+        day: u8,
+        title: &'static str,
+        tags: &'static [&'static str],
+    ) -> Self {
+        Self {
+            year,
+            day,
+            title,
+            tags,
+            construct: construct::<T>,
+        }
+    }
+
+    /// A fresh instance of the day's solution.
+    pub fn solution(&self) -> Box<dyn DynSolution> {
+        (self.construct)()
+    }
+}
+
+inventory::collect!(Day);
+
+/// All registered days, ordered by year and day.
+pub fn days() -> Vec<&'static Day> {
+    let mut days: Vec<&Day> = inventory::iter::<Day>.into_iter().collect();
+    days.sort_by_key(|day| (day.year, day.day));
+    days
+}
+
+pub fn find(year: u16, day: u8) -> Option<&'static Day> {
+    inventory::iter::<Day>
+        .into_iter()
+        .find(|registered| registered.year == year && registered.day == day)
+}
+
+pub fn get_day(year: u16, day: u8) -> Result<Box<dyn DynSolution>, String> {
+    find(year, day)
+        .map(Day::solution)
+        .ok_or_else(|| String::from("Day not yet created"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{days, get_day};
+
+    #[test]
+    fn registered_days() {
+        let days = days();
+        assert_eq!(days.len(), 25);
+        assert!(days
+            .windows(2)
+            .all(|pair| (pair[0].year, pair[0].day) < (pair[1].year, pair[1].day)));
+        assert!(days.iter().all(|day| !day.title.is_empty()));
+        assert!(get_day(2022, 25).is_ok());
+        assert!(get_day(2022, 26).is_err());
+    }
+}
@@ -0,0 +1,393 @@
+use std::time::Duration;
+
+use clap::ValueEnum;
+use common::{Answer, SolveError};
+use serde::Serialize;
+
+use crate::answers::Verification;
+use crate::fetch::FetchError;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Format {
+    /// Human readable lines
+    Text,
+    /// One JSON object per line
+    Json,
+    /// Comma separated values with a header row
+    Csv,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum Part {
+    A,
+    B,
+}
+
+impl Part {
+    pub fn id(&self) -> &'static str {
+        match self {
+            Part::A => "a",
+            Part::B => "b",
+        }
+    }
+}
+
+impl std::fmt::Display for Part {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Part::A => write!(f, "A"),
+            Part::B => write!(f, "B"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Failure {
+    Error(SolveError),
+    /// The input could not be loaded, so the part did not run
+    Input(FetchError),
+    Panicked(String),
+    /// The part ran past the timeout, its thread is left running as it cannot be stopped
+    TimedOut(Duration),
+}
+
+impl std::fmt::Display for Failure {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Failure::Error(error) => write!(f, "{}", error),
+            Failure::Input(error) => write!(f, "{}", error),
+            Failure::Panicked(message) => write!(f, "panicked: {}", message),
+            Failure::TimedOut(timeout) => write!(
+                f,
+                "timed out after {:.2?}, left running in the background",
+                timeout
+            ),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct PartReport {
+    pub part: Part,
+    pub result: Result<Answer, Failure>,
+    pub duration: Duration,
+    pub verification: Option<Verification>,
+}
+
+#[derive(Debug)]
+pub struct DayReport {
+    pub year: u16,
+    pub day: u8,
+    /// Name of the input file, when not running on the puzzle input
+    pub input: Option<String>,
+    /// Time spent parsing the input, including any parse again after a part was lost
+    pub parse: Duration,
+    pub parts: Vec<PartReport>,
+    pub duration: Duration,
+    /// CPU time of the parse and the parts, on their own threads
+    pub cpu: Duration,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(tag = "record", rename_all = "lowercase")]
+enum Record<'a> {
+    Parse {
+        year: u16,
+        day: u8,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        input: Option<&'a str>,
+        duration_ms: f64,
+    },
+    Part {
+        year: u16,
+        day: u8,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        input: Option<&'a str>,
+        part: Part,
+        answer: Option<String>,
+        error: Option<&'a str>,
+        duration_ms: f64,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        verification: Option<&'a str>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        expected: Option<&'a str>,
+    },
+    Total {
+        days: usize,
+        failed: usize,
+        cpu_ms: f64,
+        wall_ms: f64,
+    },
+}
+
+const CSV_HEADER: &str =
+    "record,year,day,part,answer,error,duration_ms,verification,expected,wall_ms,input";
+
+fn to_ms(duration: Duration) -> f64 {
+    duration.as_secs_f64() * 1000.0
+}
+
+fn title(report: &DayReport) -> String {
+    day_title(report.day, report.input.as_deref())
+}
+
+/// `Day 1`, or `Day 1 (edge-case.txt)` when not running on the puzzle input.
+pub fn day_title(day: u8, input: Option<&str>) -> String {
+    match input {
+        Some(input) => format!("Day {} ({})", day, input),
+        None => format!("Day {}", day),
+    }
+}
+
+pub fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+pub struct Reporter {
+    format: Format,
+    days: usize,
+    failed: usize,
+    mismatches: usize,
+    total: Duration,
+}
+
+impl Reporter {
+    pub fn new(format: Format) -> Self {
+        if format == Format::Csv {
+            println!("{}", CSV_HEADER);
+        }
+        Self {
+            format,
+            days: 0,
+            failed: 0,
+            mismatches: 0,
+            total: Duration::ZERO,
+        }
+    }
+
+    pub fn day(&mut self, report: &DayReport) {
+        self.days += 1;
+        self.total += report.cpu;
+        self.parse(report);
+        for part in report.parts.iter() {
+            if part.result.is_err() {
+                self.failed += 1;
+            }
+            if let Some(Verification::Fail { .. }) = part.verification {
+                self.mismatches += 1;
+            }
+            self.part(report, part);
+        }
+        if self.format == Format::Text {
+            let times = std::iter::once(format!("parse: {:.2?}", report.parse))
+                .chain(
+                    report
+                        .parts
+                        .iter()
+                        .map(|p| format!("{}: {:.2?}", p.part, p.duration)),
+                )
+                .collect::<Vec<_>>()
+                .join(", ");
+            println!(
+                "{} time: {:.2?} ({})",
+                title(report),
+                report.duration,
+                times
+            );
+        }
+    }
+
+    fn parse(&self, report: &DayReport) {
+        match self.format {
+            Format::Text => {}
+            Format::Json => {
+                let record = Record::Parse {
+                    year: report.year,
+                    day: report.day,
+                    input: report.input.as_deref(),
+                    duration_ms: to_ms(report.parse),
+                };
+                println!(
+                    "{}",
+                    serde_json::to_string(&record).expect("Serializable record")
+                );
+            }
+            Format::Csv => println!(
+                "parse,{},{},,,,{:.3},,,,{}",
+                report.year,
+                report.day,
+                to_ms(report.parse),
+                csv_field(report.input.as_deref().unwrap_or_default())
+            ),
+        }
+    }
+
+    fn part(&self, day_report: &DayReport, report: &PartReport) {
+        let day = day_report.day;
+        let (answer, error) = match &report.result {
+            Ok(answer) if answer.is_none() => (None, None),
+            Ok(answer) => (Some(answer.get_result()), None),
+            Err(failure) => (None, Some(failure.to_string())),
+        };
+        let expected = match &report.verification {
+            Some(Verification::Fail { expected }) => Some(expected.as_str()),
+            _ => None,
+        };
+        match self.format {
+            Format::Text => {
+                let verification = match &report.verification {
+                    Some(Verification::Fail { expected }) => {
+                        format!(" [FAIL, expected {}]", expected)
+                    }
+                    Some(verification) => format!(" [{}]", verification.name()),
+                    None => String::new(),
+                };
+                match &report.result {
+                    Ok(_) => println!(
+                        "{}, Part {} = {}{}",
+                        title(day_report),
+                        report.part,
+                        answer.as_deref().unwrap_or("(no answer)"),
+                        verification
+                    ),
+                    Err(failure @ (Failure::Error(_) | Failure::Input(_))) => println!(
+                        "{}, Part {} failed! {}{}",
+                        title(day_report),
+                        report.part,
+                        failure,
+                        verification
+                    ),
+                    Err(failure) => println!(
+                        "{}, Part {} {}{}",
+                        title(day_report),
+                        report.part,
+                        failure,
+                        verification
+                    ),
+                }
+            }
+            Format::Json => {
+                let record = Record::Part {
+                    year: day_report.year,
+                    day,
+                    input: day_report.input.as_deref(),
+                    part: report.part,
+                    answer,
+                    error: error.as_deref(),
+                    duration_ms: to_ms(report.duration),
+                    verification: report.verification.as_ref().map(|v| v.name()),
+                    expected,
+                };
+                println!(
+                    "{}",
+                    serde_json::to_string(&record).expect("Serializable record")
+                );
+            }
+            Format::Csv => println!(
+                "part,{},{},{},{},{},{:.3},{},{},,{}",
+                day_report.year,
+                day,
+                report.part.id(),
+                csv_field(&answer.unwrap_or_default()),
+                csv_field(&error.unwrap_or_default()),
+                to_ms(report.duration),
+                report
+                    .verification
+                    .as_ref()
+                    .map(|v| v.name())
+                    .unwrap_or_default(),
+                csv_field(expected.unwrap_or_default()),
+                csv_field(day_report.input.as_deref().unwrap_or_default())
+            ),
+        }
+    }
+
+    /// Number of parts whose answer did not match the expected answer.
+    pub fn mismatches(&self) -> usize {
+        self.mismatches
+    }
+
+    /// Emits the aggregate record of the run: the CPU time summed over the days, and `wall`, the
+    /// elapsed time of the whole run, which is less than the CPU time when days ran concurrently.
+    /// In CSV the CPU time goes in the `duration_ms` column.
+    pub fn total(&self, wall: Duration) {
+        match self.format {
+            Format::Text => println!(
+                "Total CPU time: {:.2?} (wall clock: {:.2?})",
+                self.total, wall
+            ),
+            Format::Json => {
+                let record = Record::Total {
+                    days: self.days,
+                    failed: self.failed,
+                    cpu_ms: to_ms(self.total),
+                    wall_ms: to_ms(wall),
+                };
+                println!(
+                    "{}",
+                    serde_json::to_string(&record).expect("Serializable record")
+                );
+            }
+            Format::Csv => {
+                let failed = match self.failed {
+                    0 => String::new(),
+                    failed => format!("{} parts failed", failed),
+                };
+                println!(
+                    "total,,,,,{},{:.3},,,{:.3},",
+                    failed,
+                    to_ms(self.total),
+                    to_ms(wall)
+                )
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{csv_field, Part, Record};
+
+    #[test]
+    fn csv_escaping() {
+        assert_eq!(csv_field("1651"), "1651");
+        assert_eq!(csv_field("a,b"), "\"a,b\"");
+        assert_eq!(csv_field("\n##\n.."), "\"\n##\n..\"");
+        assert_eq!(csv_field("say \"hi\""), "\"say \"\"hi\"\"\"");
+    }
+
+    #[test]
+    fn json_records() {
+        let record = Record::Part {
+            year: 2022,
+            day: 1,
+            input: None,
+            part: Part::A,
+            answer: Some(String::from("24000")),
+            error: None,
+            duration_ms: 1.5,
+            verification: None,
+            expected: None,
+        };
+        assert_eq!(
+            serde_json::to_string(&record).unwrap(),
+            r#"{"record":"part","year":2022,"day":1,"part":"a","answer":"24000","error":null,"duration_ms":1.5}"#
+        );
+
+        let record = Record::Parse {
+            year: 2022,
+            day: 1,
+            input: Some("example_01_1.txt"),
+            duration_ms: 0.25,
+        };
+        assert_eq!(
+            serde_json::to_string(&record).unwrap(),
+            r#"{"record":"parse","year":2022,"day":1,"input":"example_01_1.txt","duration_ms":0.25}"#
+        );
+    }
+}
@@ -0,0 +1,326 @@
+use std::{
+    any::Any,
+    panic::{self, AssertUnwindSafe},
+    sync::Arc,
+    thread,
+    time::{Duration, Instant},
+};
+
+use common::{Answer, DynSolution, ParsedInput, Progress, Renderer};
+use tokio::{runtime::Handle, sync::oneshot};
+
+use crate::fetch::Fetcher;
+use crate::input::InputSource;
+use crate::report::{DayReport, Failure, Part, PartReport};
+use crate::years;
+
+/// Where a running day reports its progress and draws its frames.
+#[derive(Clone, Default)]
+pub struct Hooks {
+    pub progress: Progress,
+    pub renderer: Renderer,
+}
+
+pub async fn execute_day(
+    year: u16,
+    day: u8,
+    source: InputSource,
+    fetcher: &Fetcher,
+    parts: &[Part],
+    timeout: Option<Duration>,
+    hooks: &Hooks,
+) -> Option<DayReport> {
+    years::get_day(year, day).ok()?;
+
+    let input: Arc<str> = match source.load(year, day, fetcher).await {
+        Ok(input) => input.into(),
+        Err(error) => {
+            return Some(DayReport {
+                year,
+                day,
+                input: source.label(),
+                parse: Duration::ZERO,
+                parts: parts
+                    .iter()
+                    .map(|part| report(*part, Err(Failure::Input(error.clone())), Duration::ZERO))
+                    .collect(),
+                duration: Duration::ZERO,
+                cpu: Duration::ZERO,
+            })
+        }
+    };
+
+    let start = Instant::now();
+    let mut parse = Duration::ZERO;
+    let mut cpu = Duration::ZERO;
+    let mut prepared = None;
+    let mut reports = vec![];
+    for (index, part) in parts.iter().enumerate() {
+        // A panicked or abandoned part takes its puzzle and parsed input with it, the next part
+        // continues on a fresh puzzle with the input parsed again
+        let (solution, parsed) = match prepared.take() {
+            Some(prepared) => prepared,
+            None => {
+                let (result, duration, spent) =
+                    prepare(year, day, &source, input.clone(), timeout, hooks).await?;
+                parse += duration;
+                cpu += spent;
+                match result {
+                    Ok(prepared) => prepared,
+                    Err(failure) => {
+                        reports.extend(
+                            parts[index..]
+                                .iter()
+                                .map(|part| report(*part, Err(failure.clone()), Duration::ZERO)),
+                        );
+                        break;
+                    }
+                }
+            }
+        };
+        hooks.progress.task(format!("Part {}", part));
+        let (returned, report, spent) = run_part(solution, parsed, *part, timeout).await;
+        prepared = returned;
+        cpu += spent;
+        reports.push(report);
+    }
+    let time = start.elapsed();
+
+    Some(DayReport {
+        year,
+        day,
+        input: source.label(),
+        parse,
+        parts: reports,
+        duration: time,
+        cpu,
+    })
+}
+
+type Prepared = (Box<dyn DynSolution>, ParsedInput);
+
+/// A fresh puzzle with the input parsed, isolated the same way as the parts.
+async fn prepare(
+    year: u16,
+    day: u8,
+    source: &InputSource,
+    input: Arc<str>,
+    timeout: Option<Duration>,
+    hooks: &Hooks,
+) -> Option<(Result<Prepared, Failure>, Duration, Duration)> {
+    let mut solution = years::get_day(year, day).ok()?;
+    if let InputSource::Example(_) = source {
+        solution.example_mode();
+    }
+    solution.set_progress(hooks.progress.clone());
+    solution.set_renderer(hooks.renderer.clone());
+    hooks.progress.task("Parse");
+    let (outcome, duration, cpu) = isolate(
+        move || {
+            let parsed = solution.parse(&input);
+            (solution, parsed)
+        },
+        timeout,
+    )
+    .await;
+    let prepared =
+        outcome.and_then(|(solution, parsed)| Ok((solution, parsed.map_err(Failure::Error)?)));
+    Some((prepared, duration, cpu))
+}
+
+/// Runs a single part, handing the puzzle and its parsed input back when the part finished, along
+/// with the CPU time the part used.
+async fn run_part(
+    mut solution: Box<dyn DynSolution>,
+    parsed: ParsedInput,
+    part: Part,
+    timeout: Option<Duration>,
+) -> (Option<Prepared>, PartReport, Duration) {
+    let (outcome, duration, cpu) = isolate(
+        move || {
+            let result = match part {
+                Part::A => solution.solve_a(&parsed),
+                Part::B => solution.solve_b(&parsed),
+            };
+            (solution, parsed, result)
+        },
+        timeout,
+    )
+    .await;
+    match outcome {
+        Ok((solution, parsed, result)) => (
+            Some((solution, parsed)),
+            report(part, result.map_err(Failure::Error), duration),
+            cpu,
+        ),
+        Err(failure) => (None, report(part, Err(failure), duration), cpu),
+    }
+}
+
+/// Runs the work on its own thread, so a panic is caught and a runaway search can be left behind
+/// once the timeout passes. Returns the wall-clock and the CPU time of the work; a timed out part
+/// is counted as busy for the whole timeout.
+pub async fn isolate<T: Send + 'static>(
+    work: impl FnOnce() -> T + Send + 'static,
+    timeout: Option<Duration>,
+) -> (Result<T, Failure>, Duration, Duration) {
+    let (sender, receiver) = oneshot::channel();
+    // Entered on the thread, so async solutions can use the timers and IO of the runtime
+    let runtime = Handle::try_current().ok();
+    thread::spawn(move || {
+        let _runtime = runtime.as_ref().map(Handle::enter);
+        let start = Instant::now();
+        let cpu = thread_cpu_time();
+        let outcome = panic::catch_unwind(AssertUnwindSafe(work));
+        let duration = start.elapsed();
+        let cpu = match (cpu, thread_cpu_time()) {
+            (Some(start), Some(end)) => end.saturating_sub(start),
+            _ => duration,
+        };
+        let _ = sender.send((outcome, duration, cpu));
+    });
+
+    let received = match timeout {
+        Some(timeout) => match tokio::time::timeout(timeout, receiver).await {
+            Ok(received) => received,
+            Err(_) => return (Err(Failure::TimedOut(timeout)), timeout, timeout),
+        },
+        None => receiver.await,
+    };
+    match received {
+        Ok((Ok(value), duration, cpu)) => (Ok(value), duration, cpu),
+        Ok((Err(payload), duration, cpu)) => (
+            Err(Failure::Panicked(panic_message(payload))),
+            duration,
+            cpu,
+        ),
+        Err(_) => (
+            Err(Failure::Panicked(String::from("part was aborted"))),
+            Duration::ZERO,
+            Duration::ZERO,
+        ),
+    }
+}
+
+/// CPU time used by the calling thread so far, where the platform can tell.
+#[cfg(unix)]
+fn thread_cpu_time() -> Option<Duration> {
+    let mut time = libc::timespec {
+        tv_sec: 0,
+        tv_nsec: 0,
+    };
+    // SAFETY: `time` is a valid timespec for the duration of the call
+    let result = unsafe { libc::clock_gettime(libc::CLOCK_THREAD_CPUTIME_ID, &mut time) };
+    (result == 0).then(|| Duration::new(time.tv_sec as u64, time.tv_nsec as u32))
+}
+
+#[cfg(not(unix))]
+fn thread_cpu_time() -> Option<Duration> {
+    None
+}
+
+fn report(part: Part, result: Result<Answer, Failure>, duration: Duration) -> PartReport {
+    PartReport {
+        part,
+        result,
+        duration,
+        verification: None,
+    }
+}
+
+fn panic_message(payload: Box<dyn Any + Send>) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message.to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        String::from("unknown panic")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use common::{
+        async_trait, Answer, AsyncAdapter, AsyncSolution, DynSolution, Solution, SolveError,
+    };
+
+    use super::run_part;
+    use crate::report::{Failure, Part};
+
+    struct Misbehaving {}
+
+    impl Solution for Misbehaving {
+        type Parsed = ();
+
+        fn parse(&self, _input: &str) -> Result<(), SolveError> {
+            Ok(())
+        }
+
+        fn solve_a(&mut self, _parsed: &()) -> Result<Answer, SolveError> {
+            panic!("Unknown command: {}", "jump")
+        }
+
+        fn solve_b(&mut self, _parsed: &()) -> Result<Answer, SolveError> {
+            std::thread::sleep(Duration::from_secs(5));
+            Answer::from("too late").into()
+        }
+    }
+
+    fn misbehaving() -> (Box<dyn DynSolution>, common::ParsedInput) {
+        let solution = Misbehaving {};
+        let parsed = DynSolution::parse(&solution, "").unwrap();
+        (Box::new(solution), parsed)
+    }
+
+    #[derive(Default)]
+    struct Sleeping {}
+
+    #[async_trait]
+    impl AsyncSolution for Sleeping {
+        type Parsed = ();
+
+        fn parse(&self, _input: &str) -> Result<(), SolveError> {
+            Ok(())
+        }
+
+        async fn solve_a(&mut self, _parsed: &()) -> Result<Answer, SolveError> {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+            Answer::from("awake").into()
+        }
+
+        async fn solve_b(&mut self, _parsed: &()) -> Result<Answer, SolveError> {
+            Answer::none().into()
+        }
+    }
+
+    #[tokio::test]
+    async fn runs_async_parts_on_the_runtime() {
+        let solution: Box<dyn DynSolution> = Box::<AsyncAdapter<Sleeping>>::default();
+        let parsed = solution.parse("").unwrap();
+        let (_, report, _) = run_part(solution, parsed, Part::A, None).await;
+        assert_eq!(report.result, Ok(Answer::from("awake")));
+    }
+
+    #[tokio::test]
+    async fn catches_panics() {
+        let (solution, parsed) = misbehaving();
+        let (returned, report, _) = run_part(solution, parsed, Part::A, None).await;
+        assert!(returned.is_none());
+        assert_eq!(
+            report.result.unwrap_err(),
+            Failure::Panicked(String::from("Unknown command: jump"))
+        );
+    }
+
+    #[tokio::test]
+    async fn times_out() {
+        let timeout = Duration::from_millis(50);
+        let (solution, parsed) = misbehaving();
+        let (returned, report, cpu) = run_part(solution, parsed, Part::B, Some(timeout)).await;
+        assert!(returned.is_none());
+        assert_eq!(report.result.unwrap_err(), Failure::TimedOut(timeout));
+        assert_eq!(cpu, timeout);
+    }
+}
@@ -0,0 +1,245 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use clap::Args;
+
+use crate::{fetch::Fetcher, input::InputSource, selection, years};
+
+const TEMPLATE: &str = include_str!("days/day_00.rs");
+const TEMPLATE_REGISTRATION: &str = "Day::new::<Puzzle>(2022, 0, \"\", &[])";
+const TEMPLATE_TEST_INPUT: &str = "const TEST_INPUT: &str = \"\";";
+
+#[derive(Args, Debug)]
+pub struct NewArgs {
+    /// Puzzle day to create
+    #[arg(short, long, value_parser = selection::parse_day)]
+    day: u8,
+    /// Title of the puzzle; taken from the puzzle page when `--html` is given
+    #[arg(short, long)]
+    title: Option<String>,
+    /// Locally saved puzzle page to take the title and the example input from
+    #[arg(long, value_name = "PATH")]
+    html: Option<PathBuf>,
+}
+
+fn days_dir() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("src")
+        .join("days")
+}
+
+/// Module of a day: `day_NN` for the default year and `day_YYYY_NN` for other years, so days of
+/// different years do not collide.
+fn module_name(year: u16, day: u8) -> String {
+    match year {
+        years::DEFAULT_YEAR => format!("day_{:02}", day),
+        year => format!("day_{}_{:02}", year, day),
+    }
+}
+
+/// Creates the day's module from the `day_00.rs` template, declares it in `days/mod.rs` and makes
+/// sure there is an input file to run it on.
+pub async fn new_day(args: &NewArgs, year: u16, fetcher: &Fetcher) -> Result<(), String> {
+    let days_dir = days_dir();
+    let module = module_name(year, args.day);
+    let path = days_dir.join(format!("{}.rs", module));
+    if path.exists() {
+        return Err(format!("{} already exists", path.display()));
+    }
+
+    let (title, example) = match &args.html {
+        Some(html) => {
+            let page = fs::read_to_string(html)
+                .map_err(|e| format!("Reading {} failed: {}", html.display(), e))?;
+            (scrape_title(&page), scrape_example(&page))
+        }
+        None => (None, None),
+    };
+    if args.html.is_some() && example.is_none() {
+        eprintln!("No example found in the puzzle page, TEST_INPUT is left empty");
+    }
+    let title = args.title.clone().or(title).unwrap_or_default();
+    let source = render(year, args.day, &title, example.as_deref())?;
+    fs::write(&path, source).map_err(|e| format!("Writing {} failed: {}", path.display(), e))?;
+    println!("Created {}", path.display());
+
+    let mod_path = days_dir.join("mod.rs");
+    let modules = fs::read_to_string(&mod_path)
+        .map_err(|e| format!("Reading {} failed: {}", mod_path.display(), e))?;
+    if let Some(modules) = add_module(&modules, year, args.day) {
+        fs::write(&mod_path, modules)
+            .map_err(|e| format!("Writing {} failed: {}", mod_path.display(), e))?;
+        println!("Added {} to {}", module, mod_path.display());
+    }
+
+    let input_path = fetcher.input_path(year, args.day);
+    match InputSource::Puzzle.load(year, args.day, fetcher).await {
+        Ok(_) => println!("Input available at {}", input_path.display()),
+        Err(error) => {
+            if let Some(parent) = input_path.parent() {
+                fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+            }
+            fs::write(&input_path, "").map_err(|e| e.to_string())?;
+            println!("Created empty input {} ({})", input_path.display(), error);
+        }
+    }
+    Ok(())
+}
+
+fn render(year: u16, day: u8, title: &str, example: Option<&str>) -> Result<String, String> {
+    if !TEMPLATE.contains(TEMPLATE_REGISTRATION) || !TEMPLATE.contains(TEMPLATE_TEST_INPUT) {
+        return Err(String::from(
+            "The day_00.rs template is missing its placeholders",
+        ));
+    }
+    let registration = format!("Day::new::<Puzzle>({}, {}, {:?}, &[])", year, day, title);
+    let test_input = format!(
+        "const TEST_INPUT: &str = \"{}\";",
+        escape(example.unwrap_or(""))
+    );
+    Ok(TEMPLATE
+        .replace(TEMPLATE_REGISTRATION, &registration)
+        .replace(TEMPLATE_TEST_INPUT, &test_input))
+}
+
+/// Escapes text for a regular string literal, keeping newlines as they are so the example stays
+/// readable in the source.
+fn escape(text: &str) -> String {
+    text.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+/// Inserts the `mod` line of the day in order of year and day; `None` when the module is declared
+/// already.
+fn add_module(modules: &str, year: u16, day: u8) -> Option<String> {
+    let declaration = format!("mod {};", module_name(year, day));
+    let mut lines: Vec<&str> = modules.lines().collect();
+    if lines.contains(&declaration.as_str()) {
+        return None;
+    }
+    let position = lines
+        .iter()
+        .position(|line| matches!(declared_day(line), Some(declared) if declared > (year, day)))
+        .or_else(|| {
+            lines
+                .iter()
+                .rposition(|line| declared_day(line).is_some())
+                .map(|last| last + 1)
+        })
+        .unwrap_or(lines.len());
+    lines.insert(position, &declaration);
+    Some(lines.join("\n") + "\n")
+}
+
+/// Year and day of a `mod day_NN;` or `mod day_YYYY_NN;` line.
+fn declared_day(line: &str) -> Option<(u16, u8)> {
+    let module = line.strip_prefix("mod day_")?.strip_suffix(';')?;
+    match module.split_once('_') {
+        Some((year, day)) => Some((year.parse().ok()?, day.parse().ok()?)),
+        None => Some((years::DEFAULT_YEAR, module.parse().ok()?)),
+    }
+}
+
+/// Title from the `<h2>--- Day 1: Calorie Counting ---</h2>` heading of a puzzle page.
+fn scrape_title(page: &str) -> Option<String> {
+    let start = page.find("<h2>")? + "<h2>".len();
+    let end = start + page[start..].find("</h2>")?;
+    let heading = unescape(&strip_tags(&page[start..end]));
+    let heading = heading.trim().trim_matches('-').trim();
+    let title = heading.split_once(": ").map_or(heading, |(_, title)| title);
+    Some(title.to_string())
+}
+
+/// The first code block following "for example" in a puzzle page, or else the first code block.
+fn scrape_example(page: &str) -> Option<String> {
+    // ASCII only, so the offset stays valid in the page
+    let from = page.to_ascii_lowercase().find("for example").unwrap_or(0);
+    let start = from + page[from..].find("<pre><code>")? + "<pre><code>".len();
+    let end = start + page[start..].find("</code></pre>")?;
+    let example = unescape(&strip_tags(&page[start..end]));
+    Some(example.trim_end_matches('\n').to_string())
+}
+
+fn strip_tags(html: &str) -> String {
+    let mut text = String::with_capacity(html.len());
+    let mut in_tag = false;
+    for c in html.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' if in_tag => in_tag = false,
+            _ if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+    text
+}
+
+fn unescape(text: &str) -> String {
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&amp;", "&")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{add_module, render, scrape_example, scrape_title};
+
+    const PAGE: &str = "<main><article class=\"day-desc\"><h2>--- Day 5: Supply Stacks ---</h2>
+<p>The expedition can depart as soon as the final supplies have been unloaded.</p>
+<pre><code>not the example</code></pre>
+<p>For example:</p>
+<pre><code>    [D]
+[N] [C]
+<em>move 1</em> from 2 to 1 &amp;&gt;
+</code></pre></article></main>";
+
+    #[test]
+    fn scrapes_puzzle_page() {
+        assert_eq!(scrape_title(PAGE), Some(String::from("Supply Stacks")));
+        assert_eq!(
+            scrape_example(PAGE),
+            Some(String::from("    [D]\n[N] [C]\nmove 1 from 2 to 1 &>"))
+        );
+        // `İ` grows when lowercased
+        let page = format!("<p>{}</p>{}", "İ".repeat(20), PAGE);
+        assert_eq!(scrape_example(&page), scrape_example(PAGE));
+    }
+
+    #[test]
+    fn renders_template() {
+        let source = render(2022, 5, "Supply Stacks", Some("say \"hi\"")).unwrap();
+        assert!(source.contains("Day::new::<Puzzle>(2022, 5, \"Supply Stacks\", &[])"));
+        assert!(source.contains("const TEST_INPUT: &str = \"say \\\"hi\\\"\";"));
+    }
+
+    #[test]
+    fn adds_module_in_order() {
+        let modules = "mod day_01;\nmod day_03;\n";
+        assert_eq!(
+            add_module(modules, 2022, 2),
+            Some(String::from("mod day_01;\nmod day_02;\nmod day_03;\n"))
+        );
+        assert_eq!(
+            add_module(modules, 2022, 4),
+            Some(String::from("mod day_01;\nmod day_03;\nmod day_04;\n"))
+        );
+        assert_eq!(add_module(modules, 2022, 3), None);
+
+        let modules = "mod day_01;\nmod day_25;\nmod day_2023_07;\n";
+        assert_eq!(
+            add_module(modules, 2023, 5),
+            Some(String::from(
+                "mod day_01;\nmod day_25;\nmod day_2023_05;\nmod day_2023_07;\n"
+            ))
+        );
+        assert_eq!(
+            add_module(modules, 2021, 5),
+            Some(String::from(
+                "mod day_2021_05;\nmod day_01;\nmod day_25;\nmod day_2023_07;\n"
+            ))
+        );
+    }
+}
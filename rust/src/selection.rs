@@ -0,0 +1,114 @@
+use clap::ValueEnum;
+
+use crate::report::Part;
+
+pub const FIRST_DAY: u8 = 1;
+pub const LAST_DAY: u8 = 25;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum PartSelection {
+    A,
+    B,
+    Both,
+}
+
+impl PartSelection {
+    pub fn parts(&self) -> Vec<Part> {
+        match self {
+            PartSelection::A => vec![Part::A],
+            PartSelection::B => vec![Part::B],
+            PartSelection::Both => vec![Part::A, Part::B],
+        }
+    }
+}
+
+/// A set of days like `1-5,12,20-25`, always sorted and without duplicates.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct DaySelection(Vec<u8>);
+
+impl DaySelection {
+    pub fn all() -> Self {
+        Self((FIRST_DAY..=LAST_DAY).collect())
+    }
+
+    pub fn single(day: u8) -> Self {
+        Self(vec![day])
+    }
+
+    pub fn without(&self, skip: &DaySelection) -> Self {
+        Self(
+            self.0
+                .iter()
+                .filter(|day| !skip.0.contains(day))
+                .copied()
+                .collect(),
+        )
+    }
+
+    pub fn days(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+pub fn parse_day(value: &str) -> Result<u8, String> {
+    let day: u8 = value
+        .trim()
+        .parse()
+        .map_err(|_| format!("'{}' is not a day number", value))?;
+    if !(FIRST_DAY..=LAST_DAY).contains(&day) {
+        return Err(format!(
+            "day {} is outside of {}-{}",
+            day, FIRST_DAY, LAST_DAY
+        ));
+    }
+    Ok(day)
+}
+
+/// Value parser for clap, so invalid selections are reported as a usage error.
+pub fn parse_days(value: &str) -> Result<DaySelection, String> {
+    let mut days = vec![];
+    for item in value.split(',') {
+        match item.split_once('-') {
+            Some((start, end)) => {
+                let (start, end) = (parse_day(start)?, parse_day(end)?);
+                if start > end {
+                    return Err(format!("range {} is descending", item.trim()));
+                }
+                days.extend(start..=end);
+            }
+            None => days.push(parse_day(item)?),
+        }
+    }
+    days.sort();
+    days.dedup();
+    Ok(DaySelection(days))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_days, DaySelection};
+
+    #[test]
+    fn ranges() {
+        assert_eq!(
+            parse_days("1-5,12,20-25").unwrap().days(),
+            &[1, 2, 3, 4, 5, 12, 20, 21, 22, 23, 24, 25]
+        );
+        assert_eq!(parse_days("3,1,3").unwrap().days(), &[1, 3]);
+        assert_eq!(
+            DaySelection::all()
+                .without(&parse_days("2-24").unwrap())
+                .days(),
+            &[1, 25]
+        );
+    }
+
+    #[test]
+    fn invalid() {
+        assert!(parse_days("0").is_err());
+        assert!(parse_days("26").is_err());
+        assert!(parse_days("5-1").is_err());
+        assert!(parse_days("1,,2").is_err());
+        assert!(parse_days("a-b").is_err());
+    }
+}
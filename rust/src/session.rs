@@ -0,0 +1,211 @@
+use std::{
+    collections::BTreeMap,
+    env, fs,
+    path::{Path, PathBuf},
+};
+
+use clap::{Args, Subcommand};
+use serde::Deserialize;
+
+use crate::fetch::Fetcher;
+
+/// `config.toml` in the XDG config directory, e.g.
+///
+/// ```toml
+/// default_profile = "daan"
+///
+/// [profiles.daan]
+/// session = "53616c7465645f5f..."
+/// expires = "2023-12-01"
+///
+/// [profiles.team]
+/// session = "53616c7465645f5f..."
+/// ```
+///
+/// A top level `session` is used when no profile is selected.
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Config {
+    session: Option<String>,
+    expires: Option<String>,
+    default_profile: Option<String>,
+    #[serde(default)]
+    profiles: BTreeMap<String, ProfileConfig>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ProfileConfig {
+    session: Option<String>,
+    /// Expiry of the cookie as shown by the browser, only reported by `session check`
+    expires: Option<String>,
+}
+
+impl Config {
+    pub fn path() -> Option<PathBuf> {
+        let config_home = env::var_os("XDG_CONFIG_HOME")
+            .filter(|dir| !dir.is_empty())
+            .map(PathBuf::from)
+            .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))?;
+        Some(config_home.join("aoc2022").join("config.toml"))
+    }
+
+    /// The config file, or an empty config when there is none.
+    pub fn load() -> Result<Self, String> {
+        match Self::path() {
+            Some(path) if path.is_file() => Self::read(&path),
+            _ => Ok(Self::default()),
+        }
+    }
+
+    fn read(path: &Path) -> Result<Self, String> {
+        let data = fs::read_to_string(path)
+            .map_err(|e| format!("Reading {} failed: {}", path.display(), e))?;
+        toml::from_str(&data).map_err(|e| format!("Invalid config {}: {}", path.display(), e))
+    }
+}
+
+/// The account to run as. Named profiles keep their inputs and answers apart in
+/// `profiles/{name}`, the default profile uses the current directory.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Profile {
+    pub name: Option<String>,
+    pub session: Option<String>,
+    pub expires: Option<String>,
+}
+
+impl Profile {
+    /// Picks the session from, in order: `--aoc-session`, the profile given with `--profile`,
+    /// `AOC_SESSION`, the config's default profile and the config's top level session.
+    pub fn resolve(
+        config: &Config,
+        profile: Option<&str>,
+        session: Option<String>,
+    ) -> Result<Self, String> {
+        Self::resolve_with_env(config, profile, session, env::var("AOC_SESSION").ok())
+    }
+
+    fn resolve_with_env(
+        config: &Config,
+        profile: Option<&str>,
+        session: Option<String>,
+        env_session: Option<String>,
+    ) -> Result<Self, String> {
+        let name = match profile {
+            Some(profile) => Some(profile.to_string()),
+            None if session.is_some() || env_session.is_some() => None,
+            None => config.default_profile.clone(),
+        };
+        let (configured, expires) = match &name {
+            Some(name) => config
+                .profiles
+                .get(name)
+                .map(|profile| (profile.session.clone(), profile.expires.clone()))
+                .ok_or_else(|| format!("Unknown profile {}", name))?,
+            None => (config.session.clone(), config.expires.clone()),
+        };
+        let session = match name {
+            Some(_) => session.or(configured),
+            None => session.or(env_session).or(configured),
+        };
+        Ok(Self {
+            name,
+            session,
+            expires,
+        })
+    }
+
+    pub fn data_dir(&self) -> PathBuf {
+        let current_dir = env::current_dir().unwrap_or_default();
+        match &self.name {
+            Some(name) => current_dir.join("profiles").join(name),
+            None => current_dir,
+        }
+    }
+
+    pub fn label(&self) -> &str {
+        self.name.as_deref().unwrap_or("default")
+    }
+}
+
+#[derive(Args, Debug)]
+pub struct SessionArgs {
+    #[command(subcommand)]
+    command: SessionCommand,
+}
+
+#[derive(Subcommand, Debug)]
+enum SessionCommand {
+    /// Check the session cookie against the server and show who it belongs to
+    Check,
+}
+
+pub async fn run(
+    args: &SessionArgs,
+    profile: &Profile,
+    fetcher: &Fetcher,
+    year: u16,
+) -> Result<(), String> {
+    match args.command {
+        SessionCommand::Check => {
+            let info = fetcher
+                .check_session(year)
+                .await
+                .map_err(|e| e.to_string())?;
+            println!("Session of profile {} is valid", profile.label());
+            println!("Owner: {}", info.owner);
+            match info.expires.or(profile.expires.clone()) {
+                Some(expires) => println!("Expires: {}", expires),
+                None => println!("Expires: unknown"),
+            }
+            Ok(())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Config, Profile};
+
+    const CONFIG: &str = r#"
+session = "top"
+default_profile = "daan"
+
+[profiles.daan]
+session = "daans"
+expires = "2023-12-01"
+
+[profiles.team]
+session = "teams"
+"#;
+
+    #[test]
+    fn resolves_profiles() {
+        let config: Config = toml::from_str(CONFIG).unwrap();
+        let resolve = |profile: Option<&str>, session: Option<&str>, env: Option<&str>| {
+            Profile::resolve_with_env(
+                &config,
+                profile,
+                session.map(String::from),
+                env.map(String::from),
+            )
+        };
+
+        let default = resolve(None, None, None).unwrap();
+        assert_eq!(default.name.as_deref(), Some("daan"));
+        assert_eq!(default.session.as_deref(), Some("daans"));
+        assert_eq!(default.expires.as_deref(), Some("2023-12-01"));
+
+        let team = resolve(Some("team"), None, Some("env")).unwrap();
+        assert_eq!(team.session.as_deref(), Some("teams"));
+        assert!(team.data_dir().ends_with("profiles/team"));
+
+        let env = resolve(None, None, Some("env")).unwrap();
+        assert_eq!((env.name, env.session.as_deref()), (None, Some("env")));
+        let cli = resolve(Some("team"), Some("cli"), Some("env")).unwrap();
+        assert_eq!(cli.session.as_deref(), Some("cli"));
+
+        assert!(resolve(Some("nobody"), None, None).is_err());
+        assert!(toml::from_str::<Config>("sesion = \"typo\"").is_err());
+    }
+}
@@ -0,0 +1,69 @@
+//! A minimal HTTP server standing in for the Advent of Code site in tests.
+
+use std::{
+    io::{BufRead, BufReader, Read, Write},
+    net::TcpListener,
+    thread,
+};
+
+pub struct Request {
+    pub line: String,
+    pub headers: Vec<(String, String)>,
+    pub body: String,
+}
+
+impl Request {
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(header, _)| header.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+/// Answers one request per response, in order, then hands back the requests it received.
+pub fn serve(responses: Vec<(u16, &'static str)>) -> (String, thread::JoinHandle<Vec<Request>>) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    let handle = thread::spawn(move || {
+        let mut requests = vec![];
+        for (status, body) in responses {
+            let (stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream);
+            let mut line = String::new();
+            reader.read_line(&mut line).unwrap();
+            let mut headers = vec![];
+            loop {
+                let mut header = String::new();
+                reader.read_line(&mut header).unwrap();
+                match header.trim().split_once(':') {
+                    Some((name, value)) => {
+                        headers.push((name.trim().to_string(), value.trim().to_string()))
+                    }
+                    None => break,
+                }
+            }
+            let mut request = Request {
+                line: line.trim().to_string(),
+                headers,
+                body: String::new(),
+            };
+            let length = request
+                .header("content-length")
+                .map_or(0, |length| length.parse().unwrap());
+            let mut request_body = vec![0; length];
+            reader.read_exact(&mut request_body).unwrap();
+            request.body = String::from_utf8(request_body).unwrap();
+            let response = format!(
+                "HTTP/1.1 {} Stand-in\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{}",
+                status,
+                body.len(),
+                body
+            );
+            reader.get_mut().write_all(response.as_bytes()).unwrap();
+            requests.push(request);
+        }
+        requests
+    });
+    (url, handle)
+}
@@ -0,0 +1,213 @@
+use std::{
+    fs,
+    io::{self, Write},
+    path::{Path, PathBuf},
+};
+
+use clap::Args;
+
+use crate::{
+    answers::AnswerStore,
+    client::{AocClient, Outcome},
+    fetch::Fetcher,
+    input::InputSource,
+    report::Part,
+    runner::{self, Hooks},
+    selection,
+};
+
+#[derive(Args, Debug)]
+pub struct SubmitArgs {
+    /// Puzzle day to submit
+    #[arg(short, long, value_parser = selection::parse_day)]
+    day: u8,
+    /// Puzzle part to submit
+    #[arg(short, long, value_enum)]
+    part: Part,
+}
+
+/// Every answer submitted for a part with its outcome, one `answer<TAB>outcome` line each, stored
+/// as `answers/{year}/submissions_{day}_{part}.txt`. Rate limited attempts are not kept.
+pub struct SubmissionLog {
+    dir: PathBuf,
+}
+
+impl SubmissionLog {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    pub fn in_data_dir(data_dir: &Path, year: u16) -> Self {
+        Self::new(data_dir.join("answers").join(year.to_string()))
+    }
+
+    fn path(&self, day: u8, part: Part) -> PathBuf {
+        self.dir
+            .join(format!("submissions_{}_{}.txt", day, part.id()))
+    }
+
+    pub fn submissions(&self, day: u8, part: Part) -> Vec<(String, Outcome)> {
+        fs::read_to_string(self.path(day, part))
+            .unwrap_or_default()
+            .lines()
+            .filter_map(|line| {
+                let (answer, outcome) = line.rsplit_once('\t')?;
+                Some((answer.to_string(), Outcome::from_id(outcome)?))
+            })
+            .collect()
+    }
+
+    pub fn add(&self, day: u8, part: Part, answer: &str, outcome: &Outcome) -> io::Result<()> {
+        if let Outcome::RateLimited(_) = outcome {
+            return Ok(());
+        }
+        fs::create_dir_all(&self.dir)?;
+        let mut file = fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(self.path(day, part))?;
+        writeln!(file, "{}\t{}", answer, outcome.id())
+    }
+
+    /// Why submitting this answer is pointless given the earlier submissions, if it is.
+    pub fn rejection(&self, day: u8, part: Part, answer: &str) -> Option<String> {
+        let number = answer.parse::<i128>().ok();
+        for (previous, outcome) in self.submissions(day, part) {
+            if outcome == Outcome::Correct {
+                return Some(format!("Part was solved before with {}", previous));
+            }
+            if previous == answer {
+                return Some(format!("{} was submitted before: {}", answer, outcome));
+            }
+            let bound = match (number, previous.parse::<i128>()) {
+                (Some(number), Ok(bound)) => (number, bound),
+                _ => continue,
+            };
+            match outcome {
+                Outcome::TooHigh if bound.0 >= bound.1 => {
+                    return Some(format!(
+                        "{} is not lower than {}, which was too high",
+                        answer, previous
+                    ))
+                }
+                Outcome::TooLow if bound.0 <= bound.1 => {
+                    return Some(format!(
+                        "{} is not higher than {}, which was too low",
+                        answer, previous
+                    ))
+                }
+                _ => {}
+            }
+        }
+        None
+    }
+}
+
+/// Solves the part on the puzzle input and submits the answer, unless an earlier submission
+/// already shows it is wrong. A correct answer is recorded as the expected answer as well.
+pub async fn submit(
+    args: &SubmitArgs,
+    year: u16,
+    fetcher: &Fetcher,
+    data_dir: &Path,
+) -> Result<(), String> {
+    let session = fetcher
+        .session()
+        .ok_or_else(|| String::from("Cannot submit, AOC_SESSION unavailable"))?;
+    InputSource::Puzzle
+        .load(year, args.day, fetcher)
+        .await
+        .map_err(|e| e.to_string())?;
+    let report = runner::execute_day(
+        year,
+        args.day,
+        InputSource::Puzzle,
+        fetcher,
+        &[args.part],
+        None,
+        &Hooks::default(),
+    )
+    .await
+    .ok_or_else(|| format!("Day {} not yet created", args.day))?;
+    let answer = match &report.parts[0].result {
+        Ok(answer) => answer.get_result(),
+        Err(failure) => return Err(format!("Part {} failed! {}", args.part, failure)),
+    };
+    let answer = answer.trim();
+    if answer.is_empty() {
+        return Err(format!("Part {} has no answer to submit", args.part));
+    }
+
+    let log = SubmissionLog::in_data_dir(data_dir, year);
+    if let Some(reason) = log.rejection(args.day, args.part, answer) {
+        return Err(format!("Not submitting {}: {}", answer, reason));
+    }
+    let client = AocClient::with_base_url(fetcher.get_base_url(), session.to_string())
+        .user_agent(fetcher.get_user_agent());
+    let outcome = client.submit(year, args.day, args.part, answer).await?;
+    println!(
+        "Day {}, Part {} = {}: {}",
+        args.day, args.part, answer, outcome
+    );
+    log.add(args.day, args.part, answer, &outcome)
+        .map_err(|e| format!("Recording submission failed: {}", e))?;
+    if outcome == Outcome::Correct {
+        let store = AnswerStore::in_data_dir(data_dir, year);
+        store
+            .record(args.day, args.part, &answer.into())
+            .map_err(|e| format!("Recording answer failed: {}", e))?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SubmissionLog;
+    use crate::client::{AocClient, Outcome};
+    use crate::report::Part;
+    use crate::stand_in;
+
+    #[tokio::test]
+    async fn submits_to_stand_in_server() {
+        let (url, server) = stand_in::serve(vec![(
+            200,
+            "<article><p>That's not the right answer; your answer is too low.</p></article>",
+        )]);
+        let client = AocClient::with_base_url(&url, String::from("secret"));
+        let outcome = client.submit(2022, 1, Part::B, "45000").await;
+        assert_eq!(outcome, Ok(Outcome::TooLow));
+        let requests = server.join().unwrap();
+        assert_eq!(requests[0].line, "POST /2022/day/1/answer HTTP/1.1");
+        assert_eq!(requests[0].header("cookie"), Some("session=secret"));
+        assert_eq!(requests[0].body, "level=2&answer=45000");
+    }
+
+    #[test]
+    fn never_resubmits_wrong_answers() {
+        let dir = std::env::temp_dir().join(format!("aoc2022-submissions-{}", std::process::id()));
+        let log = SubmissionLog::new(&dir);
+
+        assert_eq!(log.rejection(1, Part::A, "100"), None);
+        log.add(1, Part::A, "100", &Outcome::TooHigh).unwrap();
+        log.add(1, Part::A, "10", &Outcome::TooLow).unwrap();
+        log.add(1, Part::A, "abc", &Outcome::Wrong).unwrap();
+        log.add(
+            1,
+            Part::A,
+            "50",
+            &Outcome::RateLimited(std::time::Duration::from_secs(5)),
+        )
+        .unwrap();
+
+        assert!(log.rejection(1, Part::A, "100").is_some());
+        assert!(log.rejection(1, Part::A, "120").is_some());
+        assert!(log.rejection(1, Part::A, "5").is_some());
+        assert!(log.rejection(1, Part::A, "abc").is_some());
+        assert_eq!(log.rejection(1, Part::A, "50"), None);
+        assert_eq!(log.rejection(1, Part::B, "100"), None);
+
+        log.add(1, Part::A, "50", &Outcome::Correct).unwrap();
+        assert!(log.rejection(1, Part::A, "51").is_some());
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,245 @@
+use std::sync::Arc;
+
+use common::{Color, Frame, FrameSink, Point, Renderer, Shape};
+use futures_util::{SinkExt, StreamExt};
+use serde_json::{json, Value};
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::{TcpListener, TcpStream},
+    sync::watch,
+};
+use tokio_tungstenite::tungstenite::Message;
+
+/// Port the browser page connects to
+pub const PORT: u16 = 9898;
+/// The page replaying the canvas calls of the latest frame. A copy of the TS tree's
+/// `TS/shared/browser/index.html` so the crate builds on its own; keep the two in sync.
+const PAGE: &str = include_str!("../assets/index.html");
+/// Pixels per cell
+const CELL_SIZE: i64 = 5;
+/// Pixels between the edge of the canvas and the first cell
+const MARGIN: i64 = 20;
+
+type Latest = watch::Receiver<Option<Arc<Frame>>>;
+
+/// Serves frames to the browser page. The page is served over HTTP and connects back over a
+/// WebSocket on the same port, the same way it connects to the TS `PuzzleServer`.
+pub struct Visualizer {
+    renderer: Renderer,
+    connections: watch::Receiver<usize>,
+}
+
+impl Visualizer {
+    pub async fn serve(port: u16) -> std::io::Result<Self> {
+        let listener = TcpListener::bind(("127.0.0.1", port)).await?;
+        let (frames, latest) = watch::channel(None);
+        let (connected, connections) = watch::channel(0);
+        tokio::spawn(async move {
+            while let Ok((stream, _)) = listener.accept().await {
+                tokio::spawn(connection(stream, latest.clone(), connected.clone()));
+            }
+        });
+        Ok(Self {
+            renderer: Renderer::new(CanvasSink(frames)),
+            connections,
+        })
+    }
+
+    pub fn renderer(&self) -> &Renderer {
+        &self.renderer
+    }
+
+    /// Waits for a page to connect, so it does not miss the first frames.
+    pub async fn wait_for_page(&self) {
+        let _ = self.connections.clone().wait_for(|count| *count > 0).await;
+    }
+}
+
+async fn connection(stream: TcpStream, mut latest: Latest, connected: watch::Sender<usize>) {
+    let mut head = [0; 4096];
+    let read = stream.peek(&mut head).await.unwrap_or(0);
+    let request = String::from_utf8_lossy(&head[..read]).to_ascii_lowercase();
+    if !request.contains("upgrade: websocket") {
+        let _ = serve_page(stream).await;
+        return;
+    }
+    let mut socket = match tokio_tungstenite::accept_async(stream).await {
+        Ok(socket) => socket,
+        Err(_) => return,
+    };
+    connected.send_modify(|count| *count += 1);
+    // Like the TS server, a page that connects gets the latest frame right away
+    let mut frame = latest.borrow_and_update().clone();
+    loop {
+        if let Some(frame) = frame.take() {
+            let calls = Value::Array(canvas_calls(&frame)).to_string();
+            if socket.send(Message::Text(calls)).await.is_err() {
+                break;
+            }
+        }
+        tokio::select! {
+            changed = latest.changed() => match changed {
+                Ok(_) => frame = latest.borrow_and_update().clone(),
+                Err(_) => break,
+            },
+            message = socket.next() => match message {
+                Some(Ok(Message::Close(_))) | Some(Err(_)) | None => break,
+                Some(Ok(_)) => {}
+            },
+        }
+    }
+    connected.send_modify(|count| *count -= 1);
+}
+
+async fn serve_page(mut stream: TcpStream) -> std::io::Result<()> {
+    let mut request = [0; 4096];
+    let _ = stream.read(&mut request).await?;
+    let response = format!(
+        "HTTP/1.1 200 OK\r\nContent-Type: text/html; charset=utf-8\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        PAGE.len(),
+        PAGE
+    );
+    stream.write_all(response.as_bytes()).await?;
+    stream.shutdown().await
+}
+
+/// Keeps the latest frame for the connected pages; pages that cannot keep up skip to the latest
+/// frame, and frames are only turned into canvas calls when they are sent.
+struct CanvasSink(watch::Sender<Option<Arc<Frame>>>);
+
+impl FrameSink for CanvasSink {
+    fn frame(&mut self, frame: Frame) {
+        self.0.send_replace(Some(Arc::new(frame)));
+    }
+}
+
+fn prop(identifier: &str, value: Value) -> Value {
+    json!({ "type": "PROP", "identifier": identifier, "args": [value] })
+}
+
+fn call(identifier: &str, args: Value) -> Value {
+    json!({ "type": "FUNC", "identifier": identifier, "args": args })
+}
+
+/// The frame as the draw calls of the TS `PuzzleRenderer`, placed so its top left cell is in the
+/// top left of the canvas.
+fn canvas_calls(frame: &Frame) -> Vec<Value> {
+    let mut calls = vec![json!({ "type": "CLEAR", "identifier": "", "args": [] })];
+    let (min, _) = match frame.bounds() {
+        Some(bounds) => bounds,
+        None => return calls,
+    };
+    let pixel = |(x, y): Point| {
+        (
+            (x - min.0) * CELL_SIZE + MARGIN,
+            (y - min.1) * CELL_SIZE + MARGIN,
+        )
+    };
+    let mut fill = None;
+    let mut fill_style = |calls: &mut Vec<Value>, color: Color| {
+        if fill != Some(color) {
+            calls.push(prop("fillStyle", json!(color.hex())));
+            fill = Some(color);
+        }
+    };
+    for shape in frame.shapes.iter() {
+        match shape {
+            Shape::Grid {
+                origin,
+                rows,
+                palette,
+            } => {
+                for (y, row) in rows.iter().enumerate() {
+                    let cells: Vec<char> = row.chars().collect();
+                    // One rectangle per run of cells of the same color
+                    let mut x = 0;
+                    while x < cells.len() {
+                        let color = palette.iter().find(|(c, _)| *c == cells[x]).map(|p| p.1);
+                        let run = cells[x..]
+                            .iter()
+                            .take_while(|cell| **cell == cells[x])
+                            .count();
+                        if let Some(color) = color {
+                            fill_style(&mut calls, color);
+                            let (px, py) = pixel((origin.0 + x as i64, origin.1 + y as i64));
+                            calls.push(call(
+                                "fillRect",
+                                json!([px, py, run as i64 * CELL_SIZE, CELL_SIZE]),
+                            ));
+                        }
+                        x += run;
+                    }
+                }
+            }
+            Shape::Points { points, color } => {
+                fill_style(&mut calls, *color);
+                for point in points {
+                    let (px, py) = pixel(*point);
+                    calls.push(call("fillRect", json!([px, py, CELL_SIZE, CELL_SIZE])));
+                }
+            }
+            Shape::Path { points, color } => {
+                calls.push(prop("strokeStyle", json!(color.hex())));
+                calls.push(prop("lineWidth", json!(2)));
+                calls.push(call("beginPath", json!([])));
+                for (index, point) in points.iter().enumerate() {
+                    let (px, py) = pixel(*point);
+                    let method = if index == 0 { "moveTo" } else { "lineTo" };
+                    calls.push(call(
+                        method,
+                        json!([px + CELL_SIZE / 2, py + CELL_SIZE / 2]),
+                    ));
+                }
+                calls.push(call("stroke", json!([])));
+            }
+            Shape::Text { at, text, color } => {
+                fill_style(&mut calls, *color);
+                calls.push(prop("font", json!("12px monospace")));
+                calls.push(prop("textBaseline", json!("top")));
+                let (px, py) = pixel(*at);
+                calls.push(call("fillText", json!([text, px, py])));
+            }
+        }
+    }
+    calls
+}
+
+#[cfg(test)]
+mod tests {
+    use common::{Color, Frame, Shape};
+    use serde_json::{json, Value};
+
+    use super::canvas_calls;
+
+    #[test]
+    fn frame_to_canvas_calls() {
+        let frame = Frame {
+            shapes: vec![
+                Shape::Grid {
+                    origin: (-1, 0),
+                    rows: vec![String::from("##.#")],
+                    palette: vec![('#', Color::GRAY)],
+                },
+                Shape::Path {
+                    points: vec![(0, 0), (0, 1)],
+                    color: Color::RED,
+                },
+            ],
+        };
+        assert_eq!(
+            Value::Array(canvas_calls(&frame)),
+            json!([
+                { "type": "CLEAR", "identifier": "", "args": [] },
+                { "type": "PROP", "identifier": "fillStyle", "args": ["#808080"] },
+                { "type": "FUNC", "identifier": "fillRect", "args": [20, 20, 10, 5] },
+                { "type": "FUNC", "identifier": "fillRect", "args": [35, 20, 5, 5] },
+                { "type": "PROP", "identifier": "strokeStyle", "args": ["#dc322f"] },
+                { "type": "PROP", "identifier": "lineWidth", "args": [2] },
+                { "type": "FUNC", "identifier": "beginPath", "args": [] },
+                { "type": "FUNC", "identifier": "moveTo", "args": [27, 22] },
+                { "type": "FUNC", "identifier": "lineTo", "args": [27, 27] },
+                { "type": "FUNC", "identifier": "stroke", "args": [] },
+            ])
+        );
+    }
+}
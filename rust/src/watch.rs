@@ -0,0 +1,241 @@
+use std::{
+    collections::{BTreeMap, BTreeSet, HashMap},
+    fs,
+    io::{self, IsTerminal},
+    path::{Path, PathBuf},
+    time::{Duration, SystemTime},
+};
+
+use crate::report::{DayReport, Part, PartReport};
+
+/// How often the watched files are checked for changes
+const POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+type Stamp = Option<(SystemTime, u64)>;
+
+/// Watches files, and the files in directories, by polling their modification time and size. This
+/// picks up editors replacing a file on save as well as ones writing it in place.
+pub struct Watcher {
+    paths: Vec<PathBuf>,
+    stamps: BTreeMap<PathBuf, Stamp>,
+}
+
+impl Watcher {
+    pub fn new(paths: Vec<PathBuf>) -> Self {
+        let stamps = snapshot(&paths);
+        Self { paths, stamps }
+    }
+
+    /// Number of files watched, including the files in watched directories.
+    pub fn files(&self) -> usize {
+        self.stamps.len()
+    }
+
+    /// Waits until the watched files changed and settled, and returns the ones that changed.
+    pub async fn changed(&mut self) -> Vec<PathBuf> {
+        loop {
+            tokio::time::sleep(POLL_INTERVAL).await;
+            let mut stamps = snapshot(&self.paths);
+            if stamps == self.stamps {
+                continue;
+            }
+            // Do not read a file that is still being written
+            loop {
+                tokio::time::sleep(POLL_INTERVAL).await;
+                let settled = snapshot(&self.paths);
+                if settled == stamps {
+                    break;
+                }
+                stamps = settled;
+            }
+            let changed = changes(&self.stamps, &stamps);
+            self.stamps = stamps;
+            return changed;
+        }
+    }
+}
+
+fn stamp(path: &Path) -> Stamp {
+    let metadata = fs::metadata(path).ok()?;
+    Some((metadata.modified().ok()?, metadata.len()))
+}
+
+fn snapshot(paths: &[PathBuf]) -> BTreeMap<PathBuf, Stamp> {
+    let mut stamps = BTreeMap::new();
+    for path in paths {
+        match fs::read_dir(path) {
+            Ok(entries) => stamps.extend(
+                entries
+                    .filter_map(|entry| entry.ok().map(|e| e.path()))
+                    .filter(|path| path.is_file())
+                    .map(|path| (path.clone(), stamp(&path))),
+            ),
+            // Missing files are watched too, they may show up later
+            Err(_) => {
+                stamps.insert(path.clone(), stamp(path));
+            }
+        }
+    }
+    stamps
+}
+
+fn changes(before: &BTreeMap<PathBuf, Stamp>, after: &BTreeMap<PathBuf, Stamp>) -> Vec<PathBuf> {
+    before
+        .keys()
+        .chain(after.keys())
+        .filter(|path| before.get(*path) != after.get(*path))
+        .cloned()
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .collect()
+}
+
+/// Clears the terminal so every run replaces the output of the previous one.
+pub fn clear_screen() {
+    if io::stdout().is_terminal() {
+        print!("\x1b[2J\x1b[H");
+    }
+}
+
+/// Answers and timings of the previous run of every day and input.
+#[derive(Default)]
+pub struct History(HashMap<(u8, Option<String>), Run>);
+
+struct Run {
+    parse: Duration,
+    parts: Vec<(Part, String, Duration)>,
+}
+
+impl Run {
+    fn of(report: &DayReport) -> Self {
+        Self {
+            parse: report.parse,
+            parts: report
+                .parts
+                .iter()
+                .map(|part| (part.part, outcome(part), part.duration))
+                .collect(),
+        }
+    }
+}
+
+impl History {
+    /// Lines telling how the report differs from the previous run of the same day and input, which
+    /// it then replaces.
+    pub fn compare(&mut self, report: &DayReport) -> Vec<String> {
+        let run = Run::of(report);
+        let key = (report.day, report.input.clone());
+        let lines = match self.0.get(&key) {
+            Some(previous) => diff(previous, &run),
+            None => vec![],
+        };
+        self.0.insert(key, run);
+        lines
+    }
+}
+
+/// The answer or failure of a part as shown in the report.
+fn outcome(part: &PartReport) -> String {
+    match &part.result {
+        Ok(answer) if answer.is_none() => String::from("(no answer)"),
+        Ok(answer) => answer.get_result(),
+        Err(failure) => failure.to_string(),
+    }
+}
+
+fn diff(previous: &Run, run: &Run) -> Vec<String> {
+    let mut lines = vec![format!("  Parse: {}", timing(previous.parse, run.parse))];
+    for (part, outcome, duration) in run.parts.iter() {
+        let (before, was) = match previous.parts.iter().find(|(p, _, _)| p == part) {
+            Some((_, before, was)) => (before, was),
+            None => continue,
+        };
+        let answer = match before == outcome {
+            true => String::from("unchanged"),
+            false if before.contains('\n') || outcome.contains('\n') => String::from("changed"),
+            false => format!("changed from {}", before),
+        };
+        lines.push(format!(
+            "  Part {}: {}, {}",
+            part,
+            answer,
+            timing(*was, *duration)
+        ));
+    }
+    lines
+}
+
+fn timing(before: Duration, after: Duration) -> String {
+    if before.is_zero() {
+        return format!("{:.2?} -> {:.2?}", before, after);
+    }
+    let change = (after.as_secs_f64() / before.as_secs_f64() - 1.0) * 100.0;
+    format!("{:.2?} -> {:.2?} ({:+.0}%)", before, after, change)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use common::{Answer, SolveError};
+
+    use super::{changes, snapshot, History};
+    use crate::report::{DayReport, Failure, Part, PartReport};
+
+    fn report(a: Result<Answer, Failure>, millis: u64) -> DayReport {
+        DayReport {
+            year: 2022,
+            day: 1,
+            input: None,
+            parse: Duration::from_millis(1),
+            parts: vec![PartReport {
+                part: Part::A,
+                result: a,
+                duration: Duration::from_millis(millis),
+                verification: None,
+            }],
+            duration: Duration::from_millis(millis + 1),
+            cpu: Duration::from_millis(millis + 1),
+        }
+    }
+
+    #[test]
+    fn compares_runs() {
+        let mut history = History::default();
+        assert!(history
+            .compare(&report(Ok(Answer::from(24000)), 10))
+            .is_empty());
+        assert_eq!(
+            history.compare(&report(Ok(Answer::from(24000)), 5)),
+            vec![
+                "  Parse: 1.00ms -> 1.00ms (+0%)",
+                "  Part A: unchanged, 10.00ms -> 5.00ms (-50%)"
+            ]
+        );
+        let failure = Failure::Error(SolveError::unsupported("No elves"));
+        assert_eq!(
+            history.compare(&report(Err(failure), 5))[1],
+            "  Part A: changed from 24000, 5.00ms -> 5.00ms (+0%)"
+        );
+    }
+
+    #[test]
+    fn detects_changes() {
+        let dir = std::env::temp_dir().join(format!("aoc2022-watch-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("a.txt"), "1").unwrap();
+        let paths = vec![dir.clone(), dir.join("input_1.txt")];
+        let before = snapshot(&paths);
+        assert_eq!(before.len(), 2);
+
+        std::fs::write(dir.join("a.txt"), "12").unwrap();
+        std::fs::write(dir.join("b.txt"), "2").unwrap();
+        let after = snapshot(&paths);
+        assert_eq!(
+            changes(&before, &after),
+            vec![dir.join("a.txt"), dir.join("b.txt")]
+        );
+        assert!(changes(&after, &snapshot(&paths)).is_empty());
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,48 @@
+use common::DynSolution;
+
+use crate::registry;
+
+pub const DEFAULT_YEAR: u16 = 2022;
+
+pub fn get_day(year: u16, day: u8) -> Result<Box<dyn DynSolution>, String> {
+    if !years().contains(&year) {
+        return Err(format!("Year {} not available", year));
+    }
+    registry::get_day(year, day)
+}
+
+/// Years with at least one registered day.
+pub fn years() -> Vec<u16> {
+    let mut years: Vec<u16> = registry::days().iter().map(|day| day.year).collect();
+    years.dedup();
+    years
+}
+
+/// Value parser for clap, only accepting the years compiled into the binary.
+pub fn parse_year(value: &str) -> Result<u16, String> {
+    let year: u16 = value
+        .parse()
+        .map_err(|_| format!("'{}' is not a year", value))?;
+    if !years().contains(&year) {
+        return Err(format!(
+            "year {} is not available, choose one of {:?}",
+            year,
+            years()
+        ));
+    }
+    Ok(year)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{get_day, parse_year, DEFAULT_YEAR};
+
+    #[test]
+    fn registry() {
+        assert!(get_day(DEFAULT_YEAR, 1).is_ok());
+        assert!(get_day(DEFAULT_YEAR, 26).is_err());
+        assert!(get_day(1999, 1).is_err());
+        assert_eq!(parse_year("2022"), Ok(2022));
+        assert!(parse_year("1999").is_err());
+    }
+}